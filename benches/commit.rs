@@ -0,0 +1,140 @@
+use criterion::*;
+use ff::Field;
+use nova_snark::{
+  provider::{MultiCommit, PallasEngine},
+  traits::{commitment::CommitmentEngineTrait, Engine},
+};
+use rand::{rngs::StdRng, SeedableRng};
+use std::time::Duration;
+
+type E = PallasEngine;
+type Fr = <E as Engine>::Scalar;
+type CE = <E as Engine>::CE;
+
+// To run these benchmarks, first download `criterion` with `cargo install cargo-criterion`.
+// Then `cargo criterion --bench commit`. The results are located in `target/criterion/data/<name-of-benchmark>`.
+criterion_group! {
+  name = commit;
+  config = Criterion::default().warm_up_time(Duration::from_millis(3000)).sample_size(10);
+  targets = bench_commit_batch, bench_multi_commit, bench_commit_low_memory, bench_commit_fn
+}
+
+criterion_main!(commit);
+
+fn bench_commit_batch(c: &mut Criterion) {
+  let mut rng = StdRng::seed_from_u64(0);
+
+  // the vectors a Spartan prover commits to: three large witness vectors and a few smaller
+  // auxiliary ones
+  for &num_vars in [16, 18, 20].iter() {
+    let mut group = c.benchmark_group(format!("CommitBatch-NumVars-{num_vars}"));
+
+    let ck = CE::setup(b"bench", 1 << num_vars);
+    let polys = [
+      1 << num_vars,
+      1 << num_vars,
+      1 << num_vars,
+      1 << (num_vars - 4),
+      1 << (num_vars - 6),
+      1 << (num_vars - 6),
+    ]
+    .iter()
+    .map(|&n| (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>())
+    .collect::<Vec<_>>();
+    let polys = polys.iter().map(|p| p.as_slice()).collect::<Vec<_>>();
+
+    group.bench_function("Sequential", |b| {
+      b.iter(|| {
+        for p in polys.iter() {
+          black_box(CE::commit(black_box(&ck), black_box(p)));
+        }
+      })
+    });
+
+    group.bench_function("Batched", |b| {
+      b.iter(|| black_box(ck.commit_batch(black_box(&polys)).unwrap()))
+    });
+
+    group.finish();
+  }
+}
+
+fn bench_multi_commit(c: &mut Criterion) {
+  let mut rng = StdRng::seed_from_u64(0);
+
+  // many short vectors, such as the witnesses of the steps of an IVC
+  let num_vectors = 1000;
+  for &len in [16, 64].iter() {
+    let mut group = c.benchmark_group(format!("MultiCommit-{num_vectors}x{len}"));
+
+    let ck = CE::setup(b"bench", len);
+    let vs = (0..num_vectors)
+      .map(|_| (0..len).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>())
+      .collect::<Vec<_>>();
+
+    group.bench_function("Sequential", |b| {
+      b.iter(|| {
+        for v in vs.iter() {
+          black_box(CE::commit(black_box(&ck), black_box(v)));
+        }
+      })
+    });
+
+    group.bench_function("MultiCommit", |b| {
+      b.iter(|| black_box(ck.multi_commit(black_box(&vs)).unwrap()))
+    });
+
+    group.finish();
+  }
+}
+
+fn bench_commit_low_memory(c: &mut Criterion) {
+  let mut rng = StdRng::seed_from_u64(0);
+
+  let num_vars = 18;
+  let mut group = c.benchmark_group(format!("CommitLowMemory-NumVars-{num_vars}"));
+
+  let ck = CE::setup(b"bench", 1 << num_vars);
+  let v = (0..1 << num_vars)
+    .map(|_| Fr::random(&mut rng))
+    .collect::<Vec<Fr>>();
+
+  group.bench_function("Commit", |b| {
+    b.iter(|| black_box(CE::commit(black_box(&ck), black_box(&v))))
+  });
+
+  for window_bits in [4, 8] {
+    group.bench_function(format!("LowMemory-Window-{window_bits}"), |b| {
+      b.iter(|| black_box(ck.commit_low_memory(black_box(&v), window_bits).unwrap()))
+    });
+  }
+
+  group.finish();
+}
+
+fn bench_commit_fn(c: &mut Criterion) {
+  let mut rng = StdRng::seed_from_u64(0);
+
+  // a polynomial defined implicitly as f(i) = g(i)^2: materializing it holds another 2^20
+  // scalars (32 MiB), while `commit_fn` holds one chunk of 2^16 scalars (2 MiB) per thread
+  let num_vars = 20;
+  let mut group = c.benchmark_group(format!("CommitFn-NumVars-{num_vars}"));
+
+  let ck = CE::setup(b"bench", 1 << num_vars);
+  let g = (0..1 << num_vars)
+    .map(|_| Fr::random(&mut rng))
+    .collect::<Vec<Fr>>();
+
+  group.bench_function("Materialized", |b| {
+    b.iter(|| {
+      let v = g.iter().map(|g_i| g_i.square()).collect::<Vec<Fr>>();
+      black_box(CE::commit(black_box(&ck), black_box(&v)))
+    })
+  });
+
+  group.bench_function("CommitFn", |b| {
+    b.iter(|| black_box(ck.commit_fn(g.len(), |i| g[i].square()).unwrap()))
+  });
+
+  group.finish();
+}
@@ -0,0 +1,290 @@
+#![allow(non_snake_case)]
+
+use criterion::*;
+use ff::Field;
+use nova_snark::{
+  provider::{
+    ipa_pc::EvaluationEngine, Bn256EngineIPA, GrumpkinEngine, PallasEngine, Secp256k1Engine,
+    Secq256k1Engine, VestaEngine,
+  },
+  traits::{
+    commitment::CommitmentEngineTrait, evaluation::EvaluationEngineTrait, Engine,
+    TranscriptEngineTrait,
+  },
+};
+use rand::{rngs::StdRng, SeedableRng};
+use std::time::{Duration, Instant};
+
+type E = PallasEngine;
+type Fr = <E as Engine>::Scalar;
+type CE = <E as Engine>::CE;
+type TE = <E as Engine>::TE;
+
+// To run these benchmarks, first download `criterion` with `cargo install cargo-criterion`.
+// Then `cargo criterion --bench ipa`. The results are located in `target/criterion/data/<name-of-benchmark>`.
+criterion_group! {
+  name = ipa;
+  config = Criterion::default().warm_up_time(Duration::from_millis(3000)).sample_size(10);
+  targets = bench_ipa_verify_many, bench_ipa_backends, bench_ipa_tiny_opens
+}
+
+criterion_main!(ipa);
+
+// evaluates the multilinear polynomial with evaluations `poly` over the hypercube at `point`
+fn evaluate<F: Field>(poly: &[F], point: &[F]) -> F {
+  let mut evals = poly.to_vec();
+  for r in point {
+    let n = evals.len() / 2;
+    evals = (0..n)
+      .map(|i| evals[i] + *r * (evals[i + n] - evals[i]))
+      .collect();
+  }
+  evals[0]
+}
+
+fn bench_ipa_verify_many(c: &mut Criterion) {
+  let num_vars = 10;
+  let mut rng = StdRng::seed_from_u64(0);
+  let ck = CE::setup(b"bench", 1 << num_vars);
+  let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+
+  for &num_proofs in [4, 16, 64].iter() {
+    let mut group = c.benchmark_group(format!("IPA-VerifyMany-{num_proofs}"));
+
+    // produce the proofs one after another on a single transcript
+    let mut transcript = TE::new(b"bench");
+    let instances = (0..num_proofs)
+      .map(|_| {
+        let poly = (0..1 << num_vars)
+          .map(|_| Fr::random(&mut rng))
+          .collect::<Vec<Fr>>();
+        let point = (0..num_vars)
+          .map(|_| Fr::random(&mut rng))
+          .collect::<Vec<Fr>>();
+        let eval = evaluate(&poly, &point);
+        let comm = CE::commit(&ck, &poly);
+        let arg =
+          EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+        (comm, point, eval, arg)
+      })
+      .collect::<Vec<_>>();
+
+    group.bench_function("Sequential", |b| {
+      b.iter(|| {
+        let mut transcript = TE::new(b"bench");
+        for (comm, point, eval, arg) in instances.iter() {
+          assert!(EvaluationEngine::verify(
+            black_box(&vk),
+            black_box(&mut transcript),
+            black_box(comm),
+            black_box(point),
+            black_box(eval),
+            black_box(arg),
+          )
+          .is_ok());
+        }
+      })
+    });
+
+    group.bench_function("Batched", |b| {
+      b.iter(|| {
+        let mut transcript = TE::new(b"bench");
+        assert!(EvaluationEngine::verify_many(
+          black_box(&vk),
+          black_box(&instances),
+          black_box(&mut transcript),
+        )
+        .is_ok());
+      })
+    });
+
+    group.finish();
+  }
+}
+
+// the numbers of variables of the polynomials in the comparison of the backends
+const BACKEND_NUM_VARS: [usize; 6] = [10, 12, 14, 16, 18, 20];
+
+// a row of the table printed by `bench_ipa_backends`
+struct BackendReport {
+  backend: &'static str,
+  num_vars: usize,
+  prove_time: Duration,
+  verify_time: Duration,
+  proof_size: usize,
+}
+
+fn bench_ipa_backends(c: &mut Criterion) {
+  let mut reports = Vec::new();
+
+  // to compare another curve, add a line with its engine
+  bench_ipa_backend::<PallasEngine>(c, "pallas", &mut reports);
+  bench_ipa_backend::<VestaEngine>(c, "vesta", &mut reports);
+  bench_ipa_backend::<Bn256EngineIPA>(c, "bn256", &mut reports);
+  bench_ipa_backend::<GrumpkinEngine>(c, "grumpkin", &mut reports);
+  bench_ipa_backend::<Secp256k1Engine>(c, "secp256k1", &mut reports);
+  bench_ipa_backend::<Secq256k1Engine>(c, "secq256k1", &mut reports);
+
+  // criterion reports the statistics of the timings, and we summarize a single run of each
+  // configuration along with the sizes of the proofs
+  println!(
+    "{:<12}{:>10}{:>14}{:>14}{:>14}",
+    "backend", "num_vars", "prove (ms)", "verify (ms)", "size (bytes)"
+  );
+  for r in reports {
+    println!(
+      "{:<12}{:>10}{:>14.1}{:>14.1}{:>14}",
+      r.backend,
+      r.num_vars,
+      r.prove_time.as_secs_f64() * 1000.0,
+      r.verify_time.as_secs_f64() * 1000.0,
+      r.proof_size
+    );
+  }
+}
+
+fn bench_ipa_backend<E: Engine>(
+  c: &mut Criterion,
+  backend: &'static str,
+  reports: &mut Vec<BackendReport>,
+) where
+  EvaluationEngine<E>: EvaluationEngineTrait<E>,
+{
+  let mut rng = StdRng::seed_from_u64(0);
+  for num_vars in BACKEND_NUM_VARS {
+    let mut group = c.benchmark_group(format!("IPA-{backend}-NumVars-{num_vars}"));
+
+    let ck = E::CE::setup(b"bench", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let poly = (0..1 << num_vars)
+      .map(|_| E::Scalar::random(&mut rng))
+      .collect::<Vec<E::Scalar>>();
+    let point = (0..num_vars)
+      .map(|_| E::Scalar::random(&mut rng))
+      .collect::<Vec<E::Scalar>>();
+    let eval = evaluate(&poly, &point);
+    let comm = E::CE::commit(&ck, &poly);
+
+    let start = Instant::now();
+    let arg = EvaluationEngine::prove(
+      &ck,
+      &pk,
+      &mut E::TE::new(b"bench"),
+      &comm,
+      &poly,
+      &point,
+      &eval,
+    )
+    .unwrap();
+    let prove_time = start.elapsed();
+
+    let start = Instant::now();
+    assert!(
+      EvaluationEngine::verify(&vk, &mut E::TE::new(b"bench"), &comm, &point, &eval, &arg).is_ok()
+    );
+    let verify_time = start.elapsed();
+
+    reports.push(BackendReport {
+      backend,
+      num_vars,
+      prove_time,
+      verify_time,
+      proof_size: bincode::serialize(&arg).unwrap().len(),
+    });
+
+    group.bench_function("Prove", |b| {
+      b.iter(|| {
+        assert!(EvaluationEngine::prove(
+          black_box(&ck),
+          black_box(&pk),
+          black_box(&mut E::TE::new(b"bench")),
+          black_box(&comm),
+          black_box(&poly),
+          black_box(&point),
+          black_box(&eval),
+        )
+        .is_ok());
+      })
+    });
+
+    group.bench_function("Verify", |b| {
+      b.iter(|| {
+        assert!(EvaluationEngine::verify(
+          black_box(&vk),
+          black_box(&mut E::TE::new(b"bench")),
+          black_box(&comm),
+          black_box(&point),
+          black_box(&eval),
+          black_box(&arg),
+        )
+        .is_ok());
+      })
+    });
+
+    group.finish();
+  }
+}
+
+fn bench_ipa_tiny_opens(c: &mut Criterion) {
+  let num_vars = 1;
+  let mut rng = StdRng::seed_from_u64(0);
+  let mut group = c.benchmark_group(format!("IPA-TinyOpen-NumVars-{num_vars}"));
+
+  let ck = CE::setup(b"bench", 1 << num_vars);
+  let (pk, _vk) = EvaluationEngine::<E>::setup(&ck);
+  let poly = (0..1 << num_vars)
+    .map(|_| Fr::random(&mut rng))
+    .collect::<Vec<Fr>>();
+  let point = (0..num_vars)
+    .map(|_| Fr::random(&mut rng))
+    .collect::<Vec<Fr>>();
+  let eval = evaluate(&poly, &point);
+  let comm = CE::commit(&ck, &poly);
+
+  // the key with the generator of the prover key, and the same commitment with its table
+  let ck_s = CE::setup(b"ipa", 1);
+  let x = Fr::random(&mut rng);
+  assert_eq!(CE::commit(&ck_s, &[x]), pk.commit_scalar(&x));
+  group.bench_function("CommitScalar-Commit", |b| {
+    b.iter(|| black_box(CE::commit(black_box(&ck_s), black_box(&[x]))))
+  });
+  group.bench_function("CommitScalar-Table", |b| {
+    b.iter(|| black_box(pk.commit_scalar(black_box(&x))))
+  });
+
+  // `prove_deferred` commits to the inner products with the key, and `prove` with the table
+  group.bench_function("Prove-Commit", |b| {
+    b.iter(|| {
+      let mut transcript = TE::new(b"bench");
+      let state = EvaluationEngine::prove_deferred(
+        black_box(&ck),
+        black_box(&pk),
+        &mut transcript,
+        black_box(&comm),
+        black_box(&poly),
+        black_box(&point),
+        black_box(&eval),
+      )
+      .unwrap();
+      black_box(EvaluationEngine::finish_deferred(state, &mut transcript).unwrap())
+    })
+  });
+  group.bench_function("Prove-Table", |b| {
+    b.iter(|| {
+      black_box(
+        EvaluationEngine::prove(
+          black_box(&ck),
+          black_box(&pk),
+          &mut TE::new(b"bench"),
+          black_box(&comm),
+          black_box(&poly),
+          black_box(&point),
+          black_box(&eval),
+        )
+        .unwrap(),
+      )
+    })
+  });
+
+  group.finish();
+}
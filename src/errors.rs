@@ -43,9 +43,12 @@ pub enum NovaError {
   /// returned when the step execution produces an output whose length differs from a previously declared arity
   #[error("InvalidStepOutputLength")]
   InvalidStepOutputLength,
-  /// returned when the transcript engine encounters an overflow of the round number
-  #[error("InternalTranscriptError")]
-  InternalTranscriptError,
+  /// returned when the transcript cannot derive a challenge
+  #[error("TranscriptError: failed to derive the challenge labeled {label}")]
+  TranscriptError {
+    /// The label of the challenge that could not be derived
+    label: String,
+  },
   /// returned when the multiset check fails
   #[error("InvalidMultisetProof")]
   InvalidMultisetProof,
@@ -67,6 +70,76 @@ pub enum NovaError {
   /// returned when the prover cannot prove the provided statement due to completeness error
   #[error("InternalError")]
   InternalError,
+  /// returned when a powers-of-tau file is malformed or does not match the expected curve
+  #[error("InvalidPtauFile: {reason}")]
+  InvalidPtauFile {
+    /// The reason the file was rejected
+    reason: String,
+  },
+  /// returned when the supplied Poseidon round configuration or MDS matrix cannot be used
+  #[error("InvalidPoseidonConstants")]
+  InvalidPoseidonConstants,
+  /// returned when a commitment does not match the digest it is expected to have
+  #[error("InvalidCommitmentDigest")]
+  InvalidCommitmentDigest,
+  /// returned when the number of rounds of a proof does not match the size of the statement,
+  /// or the parts of the proof have inconsistent lengths
+  #[error("ProofLengthMismatch")]
+  ProofLengthMismatch,
+  /// returned when a proof has more rounds than supported
+  #[error("RoundCountExceeded")]
+  RoundCountExceeded,
+  /// returned when an encoded group element of a proof is not a point on the curve
+  #[error("PointNotOnCurve")]
+  PointNotOnCurve,
+  /// returned when a well-formed proof fails the final verification check
+  #[error("FinalCheckFailed")]
+  FinalCheckFailed,
+  /// returned when a Merkle path does not lead from the claimed leaf to the root
+  #[error("InvalidMerklePath")]
+  InvalidMerklePath,
+  /// returned when a serialized proof is truncated or structurally inconsistent
+  #[error("MalformedProof")]
+  MalformedProof,
+  /// returned when a commitment to the zero polynomial is claimed to have a nonzero evaluation
+  #[error("TrivialCommitment")]
+  TrivialCommitment,
+  /// returned when an input exceeds a configured size limit
+  #[error("InputTooLarge")]
+  InputTooLarge,
+  /// returned when commitment keys that must be independent share generators
+  #[error("OverlappingCommitmentKeys")]
+  OverlappingCommitmentKeys,
+  /// returned when a commitment is opened in a basis other than the one it was made in
+  #[error("BasisMismatch")]
+  BasisMismatch,
+  /// returned when a challenge squeezed from a transcript is zero, so that it cannot be inverted
+  #[error("DegenerateChallenge")]
+  DegenerateChallenge,
+  /// returned when the proof of a VRF output is rejected
+  #[error("InvalidVrfProof")]
+  InvalidVrfProof,
+  /// returned when a proof was produced under a different commitment key than the verifier's
+  #[error("CommitmentKeyMismatch")]
+  CommitmentKeyMismatch,
+  /// returned when the self-test of a proof system accepts a corrupted proof
+  #[error("SelfTestFailed")]
+  SelfTestFailed,
+  /// returned when the scalar field is too small for the requested soundness level
+  #[error("InsufficientSoundness")]
+  InsufficientSoundness,
+  /// returned when a proof was produced for another instance than the one being verified
+  #[error("InstanceMismatch")]
+  InstanceMismatch,
+  /// returned when a JSON encoding of a proof is malformed
+  #[error("InvalidJson: {reason}")]
+  InvalidJson {
+    /// The reason the encoding was rejected
+    reason: String,
+  },
+  /// returned when claimed evaluations do not satisfy the public relation they must satisfy
+  #[error("EvaluationRelationFailed")]
+  EvaluationRelationFailed,
 }
 
 impl From<bellpepper_core::SynthesisError> for NovaError {
@@ -144,10 +144,7 @@ impl<Scalar: PrimeField> BigNat<Scalar> {
               Ok(vs[limb_i])
             }
             // Hack b/c SynthesisError and io::Error don't implement Clone
-            Err(ref e) => Err(SynthesisError::from(std::io::Error::new(
-              std::io::ErrorKind::Other,
-              format!("{e}"),
-            ))),
+            Err(ref e) => Err(SynthesisError::from(std::io::Error::other(format!("{e}")))),
           },
         )
         .map(|v| LinearCombination::zero() + v)
@@ -196,10 +193,7 @@ impl<Scalar: PrimeField> BigNat<Scalar> {
               Ok(vs[limb_i])
             }
             // Hack b/c SynthesisError and io::Error don't implement Clone
-            Err(ref e) => Err(SynthesisError::from(std::io::Error::new(
-              std::io::ErrorKind::Other,
-              format!("{e}"),
-            ))),
+            Err(ref e) => Err(SynthesisError::from(std::io::Error::other(format!("{e}")))),
           },
         )
         .map(|v| LinearCombination::zero() + v)
@@ -10,12 +10,15 @@
 #![allow(non_snake_case)]
 #![forbid(unsafe_code)]
 
+extern crate alloc;
+
 // private modules
 mod bellpepper;
 mod circuit;
 mod constants;
 mod digest;
 mod nifs;
+mod parallel;
 mod r1cs;
 
 // public modules
@@ -47,7 +50,7 @@ use r1cs::{
 use serde::{Deserialize, Serialize};
 use traits::{
   circuit::StepCircuit, commitment::CommitmentEngineTrait, snark::RelaxedR1CSSNARKTrait,
-  AbsorbInROTrait, Engine, ROConstants, ROConstantsCircuit, ROTrait,
+  AbsorbInROTrait, Engine, ROCircuitTrait, ROConstants, ROConstantsCircuit, ROTrait,
 };
 
 /// A type that holds public parameters of Nova
@@ -141,22 +144,67 @@ where
     c_secondary: &C2,
     ck_hint1: &CommitmentKeyHint<E1>,
     ck_hint2: &CommitmentKeyHint<E2>,
+  ) -> Result<Self, NovaError> {
+    // ro_consts_circuit_primary are parameterized by E2 because the type alias uses E2::Base = E1::Scalar
+    Self::setup_with_constants(
+      c_primary,
+      c_secondary,
+      ck_hint1,
+      ck_hint2,
+      ROConstants::<E1>::default(),
+      ROConstantsCircuit::<E2>::default(),
+      ROConstants::<E2>::default(),
+      ROConstantsCircuit::<E1>::default(),
+    )
+  }
+
+  /// Creates a new `PublicParams` like `setup`, but with caller-supplied constants for the random oracles,
+  /// e.g., Poseidon constants imported with `PoseidonConstantsCircuit::from_tables`.
+  ///
+  /// `ro_consts_primary` are used by the primary's RO and by the secondary circuit's RO gadget, both over `E1::Base`,
+  /// and `ro_consts_secondary` are used by the secondary's RO and by the primary circuit's RO gadget, both over `E2::Base`.
+  pub fn setup_with_ro_constants(
+    c_primary: &C1,
+    c_secondary: &C2,
+    ck_hint1: &CommitmentKeyHint<E1>,
+    ck_hint2: &CommitmentKeyHint<E2>,
+    ro_consts_primary: ROConstants<E1>,
+    ro_consts_secondary: ROConstants<E2>,
+  ) -> Result<Self, NovaError>
+  where
+    E1::ROCircuit: ROCircuitTrait<E1::Base, Constants = ROConstants<E1>>,
+    E2::ROCircuit: ROCircuitTrait<E2::Base, Constants = ROConstants<E2>>,
+  {
+    Self::setup_with_constants(
+      c_primary,
+      c_secondary,
+      ck_hint1,
+      ck_hint2,
+      ro_consts_primary.clone(),
+      ro_consts_secondary.clone(),
+      ro_consts_secondary,
+      ro_consts_primary,
+    )
+  }
+
+  fn setup_with_constants(
+    c_primary: &C1,
+    c_secondary: &C2,
+    ck_hint1: &CommitmentKeyHint<E1>,
+    ck_hint2: &CommitmentKeyHint<E2>,
+    ro_consts_primary: ROConstants<E1>,
+    ro_consts_circuit_primary: ROConstantsCircuit<E2>,
+    ro_consts_secondary: ROConstants<E2>,
+    ro_consts_circuit_secondary: ROConstantsCircuit<E1>,
   ) -> Result<Self, NovaError> {
     let augmented_circuit_params_primary =
       NovaAugmentedCircuitParams::new(BN_LIMB_WIDTH, BN_N_LIMBS, true);
     let augmented_circuit_params_secondary =
       NovaAugmentedCircuitParams::new(BN_LIMB_WIDTH, BN_N_LIMBS, false);
 
-    let ro_consts_primary: ROConstants<E1> = ROConstants::<E1>::default();
-    let ro_consts_secondary: ROConstants<E2> = ROConstants::<E2>::default();
-
     let F_arity_primary = c_primary.arity();
     let F_arity_secondary = c_secondary.arity();
 
-    // ro_consts_circuit_primary are parameterized by E2 because the type alias uses E2::Base = E1::Scalar
-    let ro_consts_circuit_primary: ROConstantsCircuit<E2> = ROConstantsCircuit::<E2>::default();
-    let ro_consts_circuit_secondary: ROConstantsCircuit<E1> = ROConstantsCircuit::<E1>::default();
-
     // Initialize ck for the primary
     let circuit_primary: NovaAugmentedCircuit<'_, E2, C1> = NovaAugmentedCircuit::new(
       &augmented_circuit_params_primary,
@@ -543,13 +591,13 @@ where
     }
 
     // check the satisfiability of the provided instances
-    let (res_r_primary, (res_r_secondary, res_l_secondary)) = rayon::join(
+    let (res_r_primary, (res_r_secondary, res_l_secondary)) = crate::parallel::join(
       || {
         pp.r1cs_shape_primary
           .is_sat_relaxed(&pp.ck_primary, &self.r_U_primary, &self.r_W_primary)
       },
       || {
-        rayon::join(
+        crate::parallel::join(
           || {
             pp.r1cs_shape_secondary.is_sat_relaxed(
               &pp.ck_secondary,
@@ -713,7 +761,7 @@ where
     )?;
 
     // create SNARKs proving the knowledge of f_W_primary and f_W_secondary
-    let (r_W_snark_primary, f_W_snark_secondary) = rayon::join(
+    let (r_W_snark_primary, f_W_snark_secondary) = crate::parallel::join(
       || {
         S1::prove(
           &pp.ck_primary,
@@ -823,7 +871,7 @@ where
 
     // check the satisfiability of the folded instances using
     // SNARKs proving the knowledge of their satisfying witnesses
-    let (res_primary, res_secondary) = rayon::join(
+    let (res_primary, res_secondary) = crate::parallel::join(
       || {
         self
           .r_W_snark_primary
@@ -852,8 +900,11 @@ mod tests {
   use super::*;
   use crate::{
     provider::{
-      pedersen::CommitmentKeyExtTrait, traits::DlogGroup, Bn256EngineIPA, Bn256EngineKZG,
-      GrumpkinEngine, PallasEngine, Secp256k1Engine, Secq256k1Engine, VestaEngine,
+      pedersen::CommitmentKeyExtTrait,
+      poseidon::{PoseidonConfig, PoseidonConstantsCircuit},
+      traits::DlogGroup,
+      Bn256EngineIPA, Bn256EngineKZG, GrumpkinEngine, PallasEngine, Secp256k1Engine,
+      Secq256k1Engine, VestaEngine,
     },
     traits::{circuit::TrivialCircuit, evaluation::EvaluationEngineTrait, snark::default_ck_hint},
   };
@@ -1020,6 +1071,94 @@ mod tests {
     test_ivc_trivial_with::<Secp256k1Engine, Secq256k1Engine>();
   }
 
+  // an external Poseidon instance with a symmetric Cauchy MDS matrix and round constants `1, 2, 3, ...`
+  fn external_poseidon_constants<F: PrimeField>() -> PoseidonConstantsCircuit<F> {
+    let width = 25;
+    let config = PoseidonConfig {
+      full_rounds: 8,
+      partial_rounds: 3,
+    };
+    let round_constants = (0..width * (config.full_rounds + config.partial_rounds))
+      .map(|i| F::from(i as u64 + 1))
+      .collect::<Vec<F>>();
+    let mds = (0..width)
+      .map(|i| {
+        (0..width)
+          .map(|j| F::from((2 * i + 2 * j + 2) as u64).invert().unwrap())
+          .collect()
+      })
+      .collect::<Vec<Vec<F>>>();
+    PoseidonConstantsCircuit::from_tables(round_constants, mds, config).unwrap()
+  }
+
+  fn test_ivc_with_ro_constants_with<E1, E2>()
+  where
+    E1: Engine<Base = <E2 as Engine>::Scalar>,
+    E2: Engine<Base = <E1 as Engine>::Scalar>,
+    E1::RO: ROTrait<E1::Base, E1::Scalar, Constants = PoseidonConstantsCircuit<E1::Base>>,
+    E2::RO: ROTrait<E2::Base, E2::Scalar, Constants = PoseidonConstantsCircuit<E2::Base>>,
+    E1::ROCircuit: ROCircuitTrait<E1::Base, Constants = ROConstants<E1>>,
+    E2::ROCircuit: ROCircuitTrait<E2::Base, Constants = ROConstants<E2>>,
+  {
+    let circuit_primary = TrivialCircuit::<<E1 as Engine>::Scalar>::default();
+    let circuit_secondary = CubicCircuit::<<E2 as Engine>::Scalar>::default();
+
+    let pp = PublicParams::<
+      E1,
+      E2,
+      TrivialCircuit<<E1 as Engine>::Scalar>,
+      CubicCircuit<<E2 as Engine>::Scalar>,
+    >::setup_with_ro_constants(
+      &circuit_primary,
+      &circuit_secondary,
+      &*default_ck_hint(),
+      &*default_ck_hint(),
+      external_poseidon_constants(),
+      external_poseidon_constants(),
+    )
+    .unwrap();
+
+    // the supplied constants end up in the public parameters
+    let pp_default = PublicParams::<
+      E1,
+      E2,
+      TrivialCircuit<<E1 as Engine>::Scalar>,
+      CubicCircuit<<E2 as Engine>::Scalar>,
+    >::setup(
+      &circuit_primary,
+      &circuit_secondary,
+      &*default_ck_hint(),
+      &*default_ck_hint(),
+    )
+    .unwrap();
+    assert_ne!(pp.digest(), pp_default.digest());
+
+    let num_steps = 3;
+    let z0_primary = vec![<E1 as Engine>::Scalar::ONE];
+    let z0_secondary = vec![<E2 as Engine>::Scalar::ZERO];
+    let mut recursive_snark = RecursiveSNARK::new(
+      &pp,
+      &circuit_primary,
+      &circuit_secondary,
+      &z0_primary,
+      &z0_secondary,
+    )
+    .unwrap();
+    for _ in 0..num_steps {
+      recursive_snark
+        .prove_step(&pp, &circuit_primary, &circuit_secondary)
+        .unwrap();
+    }
+    assert!(recursive_snark
+      .verify(&pp, num_steps, &z0_primary, &z0_secondary)
+      .is_ok());
+  }
+
+  #[test]
+  fn test_ivc_with_ro_constants() {
+    test_ivc_with_ro_constants_with::<PallasEngine, VestaEngine>();
+  }
+
   fn test_ivc_nontrivial_with<E1, E2>()
   where
     E1: Engine<Base = <E2 as Engine>::Scalar>,
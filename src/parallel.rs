@@ -0,0 +1,168 @@
+//! This module gives the rest of the crate a single place to import its parallel iterators from.
+//! With the `parallel` feature it re-exports rayon; without it, it provides sequential
+//! stand-ins with the same method names, so that the crate builds without a thread pool.
+#[cfg(feature = "parallel")]
+pub use rayon::{current_num_threads, join, prelude};
+
+#[cfg(not(feature = "parallel"))]
+pub use self::serial::{current_num_threads, join};
+
+/// The sequential stand-ins for the parts of rayon's prelude that the crate uses
+#[cfg(not(feature = "parallel"))]
+pub mod prelude {
+  pub use super::serial::{
+    IntoParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelSlice,
+  };
+}
+
+#[cfg(not(feature = "parallel"))]
+mod serial {
+  /// Runs `a` and then `b` on the calling thread
+  pub fn join<A, B, RA, RB>(a: A, b: B) -> (RA, RB)
+  where
+    A: FnOnce() -> RA,
+    B: FnOnce() -> RB,
+  {
+    let ra = a();
+    (ra, b())
+  }
+
+  /// Returns 1, since all of the work runs on the calling thread
+  pub fn current_num_threads() -> usize {
+    1
+  }
+
+  /// A sequential iterator with the adapters of a rayon parallel iterator. The adapters whose
+  /// signature differs from `Iterator` (like `reduce`) are inherent methods, and so take
+  /// precedence over the methods of `Iterator`; the others are forwarded to `Iterator`.
+  pub struct Seq<I>(I);
+
+  impl<I: Iterator> Iterator for Seq<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+      self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+      self.0.size_hint()
+    }
+  }
+
+  impl<I: DoubleEndedIterator> DoubleEndedIterator for Seq<I> {
+    fn next_back(&mut self) -> Option<I::Item> {
+      self.0.next_back()
+    }
+  }
+
+  impl<I: ExactSizeIterator> ExactSizeIterator for Seq<I> {}
+
+  impl<I: Iterator> Seq<I> {
+    pub fn map<B, F: FnMut(I::Item) -> B>(self, f: F) -> Seq<std::iter::Map<I, F>> {
+      Seq(self.0.map(f))
+    }
+
+    pub fn zip<U: IntoIterator>(self, other: U) -> Seq<std::iter::Zip<I, U::IntoIter>> {
+      Seq(self.0.zip(other))
+    }
+
+    pub fn zip_eq<U: IntoIterator>(self, other: U) -> Seq<itertools::ZipEq<I, U::IntoIter>> {
+      Seq(itertools::Itertools::zip_eq(self.0, other))
+    }
+
+    pub fn enumerate(self) -> Seq<std::iter::Enumerate<I>> {
+      Seq(self.0.enumerate())
+    }
+
+    pub fn flat_map<U: IntoIterator, F: FnMut(I::Item) -> U>(
+      self,
+      f: F,
+    ) -> Seq<std::iter::FlatMap<I, U, F>> {
+      Seq(self.0.flat_map(f))
+    }
+
+    pub fn flat_map_iter<U: IntoIterator, F: FnMut(I::Item) -> U>(
+      self,
+      f: F,
+    ) -> Seq<std::iter::FlatMap<I, U, F>> {
+      Seq(self.0.flat_map(f))
+    }
+
+    /// Folds the items with `op`, starting from `identity()`, like rayon's `reduce`
+    pub fn reduce<ID, OP>(self, identity: ID, op: OP) -> I::Item
+    where
+      ID: Fn() -> I::Item,
+      OP: Fn(I::Item, I::Item) -> I::Item,
+    {
+      self.0.fold(identity(), op)
+    }
+  }
+
+  /// Sequential `into_par_iter`
+  pub trait IntoParallelIterator {
+    type Iter;
+
+    fn into_par_iter(self) -> Self::Iter;
+  }
+
+  impl<T: IntoIterator> IntoParallelIterator for T {
+    type Iter = Seq<T::IntoIter>;
+
+    fn into_par_iter(self) -> Self::Iter {
+      Seq(self.into_iter())
+    }
+  }
+
+  /// Sequential `par_iter`
+  pub trait IntoParallelRefIterator<'a> {
+    type Iter;
+
+    fn par_iter(&'a self) -> Self::Iter;
+  }
+
+  impl<'a, T: 'a + ?Sized> IntoParallelRefIterator<'a> for T
+  where
+    &'a T: IntoIterator,
+  {
+    type Iter = Seq<<&'a T as IntoIterator>::IntoIter>;
+
+    fn par_iter(&'a self) -> Self::Iter {
+      Seq(self.into_iter())
+    }
+  }
+
+  /// Sequential `par_iter_mut`
+  pub trait IntoParallelRefMutIterator<'a> {
+    type Iter;
+
+    fn par_iter_mut(&'a mut self) -> Self::Iter;
+  }
+
+  impl<'a, T: 'a + ?Sized> IntoParallelRefMutIterator<'a> for T
+  where
+    &'a mut T: IntoIterator,
+  {
+    type Iter = Seq<<&'a mut T as IntoIterator>::IntoIter>;
+
+    fn par_iter_mut(&'a mut self) -> Self::Iter {
+      Seq(self.into_iter())
+    }
+  }
+
+  /// Sequential `par_chunks` and `par_windows`
+  pub trait ParallelSlice<T> {
+    fn par_chunks(&self, size: usize) -> Seq<std::slice::Chunks<'_, T>>;
+
+    fn par_windows(&self, size: usize) -> Seq<std::slice::Windows<'_, T>>;
+  }
+
+  impl<T> ParallelSlice<T> for [T] {
+    fn par_chunks(&self, size: usize) -> Seq<std::slice::Chunks<'_, T>> {
+      Seq(self.chunks(size))
+    }
+
+    fn par_windows(&self, size: usize) -> Seq<std::slice::Windows<'_, T>> {
+      Seq(self.windows(size))
+    }
+  }
+}
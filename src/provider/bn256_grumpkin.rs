@@ -10,13 +10,13 @@ use group::{cofactor::CofactorCurveAffine, Curve, Group as AnotherGroup};
 use num_bigint::BigInt;
 use num_traits::Num;
 // Remove this when https://github.com/zcash/pasta_curves/issues/41 resolves
+use crate::parallel::prelude::*;
 use halo2curves::{
   bn256::{pairing, G1Affine as Bn256Affine, G2Affine, G2Compressed, Gt, G1 as Bn256Point, G2},
   grumpkin::{G1Affine as GrumpkinAffine, G1 as GrumpkinPoint},
   msm::best_multiexp,
 };
 use pasta_curves::arithmetic::{CurveAffine, CurveExt};
-use rayon::prelude::*;
 use sha3::Shake256;
 use std::io::Read;
 
@@ -0,0 +1,73 @@
+//! This module implements a Merkle tree over Pedersen commitments, whose paths show that the
+//! commitment opened by an IPA evaluation argument is one of the commitments of a registry
+use crate::{
+  errors::NovaError,
+  provider::merkle::{MerklePath, MerkleTree},
+  traits::{Engine, TranscriptReprTrait},
+  Commitment,
+};
+use serde::{Deserialize, Serialize};
+
+/// A Merkle tree over a list of commitments, whose root accumulates them, as maintained by a
+/// registry of commitments. The number of commitments must be a power of two.
+pub struct CommitmentTree {
+  tree: MerkleTree,
+  num_leaves: usize,
+}
+
+impl CommitmentTree {
+  /// Builds the tree over the commitments `comms`
+  pub fn new<E: Engine>(comms: &[Commitment<E>]) -> Result<Self, NovaError> {
+    Ok(CommitmentTree {
+      tree: MerkleTree::from_leaves(comms.iter().map(|comm| comm.to_transcript_bytes()))?,
+      num_leaves: comms.len(),
+    })
+  }
+
+  /// Returns the root of the tree
+  pub fn root(&self) -> [u8; 32] {
+    self.tree.root()
+  }
+
+  /// Returns the path showing that the commitment at `index` is in the tree
+  pub fn path(&self, index: usize) -> Result<MembershipPath, NovaError> {
+    if index >= self.num_leaves {
+      return Err(NovaError::InvalidIndex);
+    }
+
+    Ok(MembershipPath {
+      index,
+      num_leaves: self.num_leaves,
+      path: self.tree.path(index),
+    })
+  }
+}
+
+/// A Merkle path showing that a commitment is in a [`CommitmentTree`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MembershipPath {
+  index: usize,
+  num_leaves: usize,
+  path: MerklePath,
+}
+
+impl MembershipPath {
+  /// Returns the position of the commitment in the tree
+  pub fn index(&self) -> usize {
+    self.index
+  }
+
+  // checks that the path leads from `comm` to `root`
+  pub(crate) fn verify<E: Engine>(
+    &self,
+    root: &[u8; 32],
+    comm: &Commitment<E>,
+  ) -> Result<(), NovaError> {
+    self.path.verify_leaf(
+      root,
+      self.num_leaves,
+      self.index,
+      &comm.to_transcript_bytes(),
+    )
+  }
+}
@@ -0,0 +1,218 @@
+//! This module implements the final checks of IPA evaluation arguments that are deferred to
+//! the caller, and their aggregation into a single multi-scalar multiplication
+use crate::{
+  errors::NovaError,
+  provider::{ipa_pc::VerifierKey, pedersen::CommitmentKeyExtTrait, traits::DlogGroup},
+  traits::{
+    commitment::{CommitmentEngineTrait, CommitmentTrait, Len},
+    Engine,
+  },
+  Commitment, CommitmentKey, CE,
+};
+use ff::Field;
+use rand_core::OsRng;
+
+/// The final check of an inner product argument, left to the caller instead of being performed.
+///
+/// It holds a list of bases and a parallel list of scalars (`scalars[i]` multiplies the `i`-th
+/// base), and the check passes iff the multi-scalar multiplication of the bases by the scalars
+/// is the identity. An aggregation layer can collect these across many proofs, combine them
+/// with random weights using [`DeferredCheck::aggregate`], and run a single MSM.
+#[derive(Clone, Debug)]
+pub struct DeferredCheck<E: Engine> {
+  pub(crate) bases: CommitmentKey<E>,
+  pub(crate) scalars: Vec<E::Scalar>,
+  // the number of leading bases taken from the verifier key, namely `ck_s[0]` followed by a
+  // prefix of `ck_v`; the remaining bases are specific to the argument
+  pub(crate) num_key_bases: usize,
+}
+
+impl<E> DeferredCheck<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+  CommitmentKey<E>: CommitmentKeyExtTrait<E>,
+{
+  /// Returns the bases of the final check
+  pub fn bases(&self) -> &CommitmentKey<E> {
+    &self.bases
+  }
+
+  /// Returns the scalars of the final check, in the same order as the bases
+  pub fn scalars(&self) -> &[E::Scalar] {
+    &self.scalars
+  }
+
+  /// Combines several deferred checks into one, scaling the scalars of `checks[i]` by
+  /// `weights[i]`. For the result to be sound, the weights must be sampled at random
+  /// after all the checks are fixed.
+  pub fn aggregate(checks: &[Self], weights: &[E::Scalar]) -> Result<Self, NovaError> {
+    if checks.is_empty() || checks.len() != weights.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let bases = checks[1..]
+      .iter()
+      .fold(checks[0].bases.clone(), |acc, check| {
+        acc.combine(&check.bases)
+      });
+
+    let scalars = checks
+      .iter()
+      .zip(weights.iter())
+      .flat_map(|(check, w)| check.scalars.iter().map(move |s| *s * w))
+      .collect::<Vec<E::Scalar>>();
+
+    Ok(DeferredCheck {
+      bases,
+      scalars,
+      num_key_bases: checks[0].num_key_bases,
+    })
+  }
+
+  // like `aggregate`, but for checks produced against the verifier key `vk`: the scalars of the
+  // key generators that the checks share are summed so that each generator appears only once
+  pub(crate) fn aggregate_with_key(
+    vk: &VerifierKey<E>,
+    checks: &[Self],
+    weights: &[E::Scalar],
+  ) -> Result<Self, NovaError> {
+    if checks.is_empty() || checks.len() != weights.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let num_key_bases = checks.iter().map(|c| c.num_key_bases).max().unwrap();
+    if num_key_bases > vk.ck_v.length() + 1 {
+      return Err(NovaError::InvalidCommitmentKeyLength);
+    }
+
+    let mut key_scalars = vec![E::Scalar::ZERO; num_key_bases];
+    let mut bases = {
+      let (ck_c, _) = vk.ck_s.split_at(1);
+      let (ck, _) = vk.ck_v.split_at(num_key_bases - 1);
+      ck_c.combine(&ck)
+    };
+    let mut scalars = Vec::new();
+    for (check, w) in checks.iter().zip(weights.iter()) {
+      let (key, rest) = check.scalars.split_at(check.num_key_bases);
+      for (acc, s) in key_scalars.iter_mut().zip(key.iter()) {
+        *acc += *s * w;
+      }
+
+      let (_, rest_bases) = check.bases.split_at(check.num_key_bases);
+      bases = bases.combine(&rest_bases);
+      scalars.extend(rest.iter().map(|s| *s * w));
+    }
+
+    Ok(DeferredCheck {
+      bases,
+      scalars: [key_scalars, scalars].concat(),
+      num_key_bases,
+    })
+  }
+
+  /// Runs the multi-scalar multiplication and checks that it yields the identity
+  pub fn check(&self) -> Result<(), NovaError> {
+    if self.bases.length() != self.scalars.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    if CE::<E>::commit(&self.bases, &self.scalars) == Commitment::<E>::default() {
+      Ok(())
+    } else {
+      Err(NovaError::FinalCheckFailed)
+    }
+  }
+
+  /// Like `check`, but compares the result of the multi-scalar multiplication with the
+  /// identity in constant time, with `CommitmentTrait::is_identity`, so that the comparison
+  /// does not leak how the two differ. The multi-scalar multiplication itself is variable-time,
+  /// as in `check`.
+  pub fn check_constant_time(&self) -> Result<(), NovaError> {
+    if self.bases.length() != self.scalars.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    if bool::from(CE::<E>::commit(&self.bases, &self.scalars).is_identity()) {
+      Ok(())
+    } else {
+      Err(NovaError::FinalCheckFailed)
+    }
+  }
+}
+
+/// A collection of deferred final checks, such as those retained by
+/// `EvaluationEngine::verify_and_accumulate`, which are re-verified together with one MSM
+#[derive(Clone, Debug)]
+pub struct CheckAccumulator<E: Engine> {
+  checks: Vec<DeferredCheck<E>>,
+}
+
+impl<E: Engine> Default for CheckAccumulator<E> {
+  fn default() -> Self {
+    CheckAccumulator { checks: Vec::new() }
+  }
+}
+
+impl<E> CheckAccumulator<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+  CommitmentKey<E>: CommitmentKeyExtTrait<E>,
+{
+  /// Creates an empty accumulator
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Appends a deferred check to the accumulator
+  pub fn push(&mut self, check: DeferredCheck<E>) {
+    self.checks.push(check);
+  }
+
+  /// Returns the number of accumulated checks
+  pub fn len(&self) -> usize {
+    self.checks.len()
+  }
+
+  /// Returns true if no check was accumulated
+  pub fn is_empty(&self) -> bool {
+    self.checks.is_empty()
+  }
+
+  /// Checks a random linear combination of the accumulated checks with a single MSM. The
+  /// weights are sampled here, after all the checks are fixed, so the combination passes with
+  /// negligible probability if any one of the checks fails.
+  pub fn check(&self) -> Result<(), NovaError> {
+    if self.checks.is_empty() {
+      return Ok(());
+    }
+
+    let weights = (0..self.checks.len())
+      .map(|_| E::Scalar::random(OsRng))
+      .collect::<Vec<E::Scalar>>();
+    DeferredCheck::aggregate(&self.checks, &weights)?.check()
+  }
+}
+
+/// An accumulator of evaluation arguments, produced by `EvaluationEngine::aggregate`. It holds
+/// one random linear combination of the final checks of the arguments, in which the scalars of
+/// the generators of the commitment key shared by the arguments are summed, so that the final
+/// check is a single MSM over the key and the round commitments of the arguments.
+#[derive(Clone, Debug)]
+pub struct AccumulatedInstance<E: Engine> {
+  pub(crate) check: DeferredCheck<E>,
+  pub(crate) num_arguments: usize,
+}
+
+impl<E: Engine> AccumulatedInstance<E> {
+  /// Returns the number of arguments folded into the accumulator
+  pub fn len(&self) -> usize {
+    self.num_arguments
+  }
+
+  /// Returns true if no argument was folded into the accumulator
+  pub fn is_empty(&self) -> bool {
+    self.num_arguments == 0
+  }
+}
@@ -0,0 +1,47 @@
+//! This module implements a table of multiples of a generator, with which the IPA prover
+//! commits to scalars with the generator without doubling
+use crate::{traits::Engine, Commitment};
+use ff::{PrimeField, PrimeFieldBits};
+
+// the multiples `d * 2^{4j} * g` of a generator `g` for each digit `d` from 1 to 15 and each
+// window `j` of 4 bits of a scalar, with which `x * g` is the sum of one multiple per window
+#[derive(Debug)]
+pub(crate) struct GeneratorTable<E: Engine> {
+  multiples: Vec<Commitment<E>>,
+}
+
+impl<E: Engine> GeneratorTable<E> {
+  const WINDOW_BITS: usize = 4;
+
+  pub(crate) fn new(g: Commitment<E>) -> Self {
+    let num_windows = (E::Scalar::NUM_BITS as usize).div_ceil(Self::WINDOW_BITS);
+    let mut multiples = Vec::with_capacity(num_windows * ((1 << Self::WINDOW_BITS) - 1));
+    let mut base = g;
+    for _ in 0..num_windows {
+      let mut multiple = base;
+      for _ in 1..(1 << Self::WINDOW_BITS) {
+        multiples.push(multiple);
+        multiple = multiple + base;
+      }
+      // the multiple is now `2^4 * base`, the base of the next window
+      base = multiple;
+    }
+    GeneratorTable { multiples }
+  }
+
+  pub(crate) fn commit(&self, x: &E::Scalar) -> Commitment<E> {
+    let num_bits = E::Scalar::NUM_BITS as usize;
+    let (c, k) = (Self::WINDOW_BITS, (1 << Self::WINDOW_BITS) - 1);
+    let bits = x.to_le_bits();
+    (0..num_bits.div_ceil(c)).fold(Commitment::<E>::default(), |acc, j| {
+      let digit = (j * c..((j + 1) * c).min(num_bits))
+        .rev()
+        .fold(0, |d, b| (d << 1) | usize::from(bits[b]));
+      if digit == 0 {
+        acc
+      } else {
+        acc + self.multiples[j * k + digit - 1]
+      }
+    })
+  }
+}
@@ -6,6 +6,7 @@
 //! (2) HyperKZG is specialized to use KZG as the univariate commitment scheme, so it includes several optimizations (both during the transformation of multilinear-to-univariate claims
 //! and within the KZG commitment scheme implementation itself).
 #![allow(non_snake_case)]
+use crate::parallel::prelude::*;
 use crate::{
   errors::NovaError,
   provider::traits::{DlogGroup, PairingGroup},
@@ -18,13 +19,14 @@ use crate::{
 };
 use core::{
   marker::PhantomData,
-  ops::{Add, Mul, MulAssign},
+  ops::{Add, Mul, MulAssign, Neg, Sub},
 };
 use ff::Field;
+use group::GroupEncoding;
 use itertools::Itertools;
 use rand_core::OsRng;
-use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use subtle::{Choice, ConstantTimeEq};
 
 /// Alias to points on G1 that are in preprocessed form
 type G1Affine<E> = <<E as Engine>::GE as DlogGroup>::AffineGroupElement;
@@ -33,7 +35,7 @@ type G1Affine<E> = <<E as Engine>::GE as DlogGroup>::AffineGroupElement;
 type G2Affine<E> = <<<E as Engine>::GE as PairingGroup>::G2 as DlogGroup>::AffineGroupElement;
 
 /// KZG commitment key
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CommitmentKey<E: Engine>
 where
   E::GE: PairingGroup,
@@ -42,6 +44,15 @@ where
   tau_H: <<E::GE as PairingGroup>::G2 as DlogGroup>::AffineGroupElement, // needed only for the verifier key
 }
 
+impl<E: Engine> CommitmentKey<E>
+where
+  E::GE: PairingGroup,
+{
+  pub(crate) fn new(ck: Vec<G1Affine<E>>, tau_H: G2Affine<E>) -> Self {
+    CommitmentKey { ck, tau_H }
+  }
+}
+
 impl<E: Engine> Len for CommitmentKey<E>
 where
   E::GE: PairingGroup,
@@ -70,6 +81,33 @@ where
   fn to_coordinates(&self) -> (E::Base, E::Base, bool) {
     self.comm.to_coordinates()
   }
+
+  fn compressed_len() -> usize {
+    <E::GE as GroupEncoding>::Repr::default().as_ref().len()
+  }
+
+  fn to_compressed_bytes(&self) -> Vec<u8> {
+    self.comm.to_bytes().as_ref().to_vec()
+  }
+
+  fn from_compressed_bytes(bytes: &[u8]) -> Result<Self, NovaError> {
+    let mut repr = <E::GE as GroupEncoding>::Repr::default();
+    if bytes.len() != repr.as_ref().len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+    repr.as_mut().copy_from_slice(bytes);
+    let comm = Option::from(E::GE::from_bytes(&repr)).ok_or(NovaError::PointNotOnCurve)?;
+    Ok(Commitment { comm })
+  }
+
+  fn is_identity(&self) -> Choice {
+    // the encoding of a point is canonical, so this compares the points
+    self
+      .comm
+      .to_bytes()
+      .as_ref()
+      .ct_eq(E::GE::zero().to_bytes().as_ref())
+  }
 }
 
 impl<E> Default for Commitment<E>
@@ -129,7 +167,7 @@ where
   }
 }
 
-impl<'a, 'b, E> Mul<&'b E::Scalar> for &'a Commitment<E>
+impl<'b, E> Mul<&'b E::Scalar> for &Commitment<E>
 where
   E: Engine,
   E::GE: PairingGroup,
@@ -171,6 +209,34 @@ where
   }
 }
 
+impl<E> Sub for Commitment<E>
+where
+  E: Engine,
+  E::GE: PairingGroup,
+{
+  type Output = Commitment<E>;
+
+  fn sub(self, other: Commitment<E>) -> Commitment<E> {
+    Commitment {
+      comm: self.comm - other.comm,
+    }
+  }
+}
+
+impl<E> Neg for Commitment<E>
+where
+  E: Engine,
+  E::GE: PairingGroup,
+{
+  type Output = Commitment<E>;
+
+  fn neg(self) -> Commitment<E> {
+    Commitment {
+      comm: E::GE::zero() - self.comm,
+    }
+  }
+}
+
 /// Provides a commitment engine
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CommitmentEngine<E: Engine> {
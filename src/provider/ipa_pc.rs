@@ -1,33 +1,193 @@
 //! This module implements `EvaluationEngine` using an IPA-based polynomial commitment scheme
 use crate::{
   errors::NovaError,
-  provider::{pedersen::CommitmentKeyExtTrait, traits::DlogGroup},
-  spartan::polys::eq::EqPolynomial,
+  parallel::prelude::*,
+  provider::{
+    generator_table::GeneratorTable,
+    merkle::{MerklePath, MerkleRoot, MerkleTree},
+    pedersen::{CommitmentEngine, CommitmentKeyExtTrait, PartialCommitment, PreparedCommitmentKey},
+    sharded_prover::Shard,
+    traits::DlogGroup,
+  },
+  spartan::{
+    polys::{eq::EqPolynomial, multilinear::MultilinearPolynomial},
+    sumcheck::SumcheckProof,
+  },
   traits::{
-    commitment::CommitmentEngineTrait, evaluation::EvaluationEngineTrait, Engine,
-    TranscriptEngineTrait, TranscriptReprTrait,
+    commitment::{CommitmentEngineTrait, CommitmentTrait, Len},
+    evaluation::{EvaluationEngineTrait, MultilinearEvaluation},
+    Engine, Group, TranscriptEngineTrait, TranscriptReprTrait,
   },
   Commitment, CommitmentKey, CE,
 };
+use alloc::{collections::BTreeSet, sync::Arc};
+use bincode::Options;
 use core::iter;
-use ff::Field;
-use rayon::prelude::*;
+use core::{marker::PhantomData, mem, ops::Range};
+use ff::{Field, PrimeField, PrimeFieldBits};
+use rand_core::{CryptoRng, OsRng, RngCore};
 use serde::{Deserialize, Serialize};
-use std::marker::PhantomData;
+use sha3::{Digest, Sha3_256};
+use std::sync::OnceLock;
+
+pub use crate::provider::{
+  commitment_tree::{CommitmentTree, MembershipPath},
+  deferred_check::{AccumulatedInstance, CheckAccumulator, DeferredCheck},
+  ipa_zk::{ZKEvaluationEngine, ZKInnerProductArgument, ZKProverKey, ZKVerifierKey},
+  msm_service::{LocalMsmService, MsmService},
+  proof_bundle::ProofBundle,
+  sharded_prover::ShardedProver,
+};
+
+// the default maximum number of variables of a polynomial whose evaluation is proven or
+// verified, which bounds the `2^m` evaluations of the `eq` polynomial held in memory
+const DEFAULT_MAX_NUM_VARS: usize = 30;
+
+fn default_max_num_vars() -> usize {
+  DEFAULT_MAX_NUM_VARS
+}
+
+// the bits of soundness of a challenge of the argument: a cheating prover passes a round for
+// at most 4 challenges, the roots of a Laurent polynomial in `r^2` and `r^-2`, so the soundness
+// error of a challenge is at most `4 / |F| < 2^-(CAPACITY - 2)`
+fn challenge_soundness_bits<E: Engine>() -> u32 {
+  E::Scalar::CAPACITY - 2
+}
+
+// checks that `point` has at most `max_num_vars` coordinates
+pub(crate) fn check_num_vars<S>(point: &[S], max_num_vars: usize) -> Result<(), NovaError> {
+  if point.len() > max_num_vars {
+    return Err(NovaError::InputTooLarge);
+  }
+  Ok(())
+}
 
 /// Provides an implementation of the prover key
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(bound = "")]
 pub struct ProverKey<E: Engine> {
-  ck_s: CommitmentKey<E>,
+  pub(crate) ck_s: CommitmentKey<E>,
+  #[serde(default = "default_max_num_vars")]
+  pub(crate) max_num_vars: usize,
+  // the table of multiples of the generator of `ck_s`, computed on first use
+  #[serde(skip)]
+  ck_s_table: OnceLock<Arc<GeneratorTable<E>>>,
 }
 
 /// Provides an implementation of the verifier key
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(bound = "")]
 pub struct VerifierKey<E: Engine> {
-  ck_v: CommitmentKey<E>,
-  ck_s: CommitmentKey<E>,
+  pub(crate) ck_v: CommitmentKey<E>,
+  pub(crate) ck_s: CommitmentKey<E>,
+  #[serde(default = "default_max_num_vars")]
+  max_num_vars: usize,
+  #[serde(default)]
+  fixed_size: Option<usize>,
+  #[serde(default)]
+  pub(crate) round_order: RoundOrder,
+  #[serde(default)]
+  soundness_bits: Option<u32>,
+}
+
+/// The order in which the commitments `L` and `R` of each round of an inner product argument
+/// are absorbed in the transcript. Implementations that absorb them in different orders derive
+/// different challenges, so their arguments only verify under the order they were made with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoundOrder {
+  /// `L` then `R`, as absorbed by the prover of this crate
+  #[default]
+  LeftFirst,
+  /// `R` then `L`, for arguments produced by implementations with the opposite convention
+  RightFirst,
+}
+
+impl<E: Engine> ProverKey<E> {
+  /// Sets the maximum number of variables of the points at which the prover proves
+  /// evaluations (30 by default). Proving at a larger point returns
+  /// `NovaError::InputTooLarge` before the `2^m` evaluations of `eq` are allocated.
+  pub fn with_max_num_vars(mut self, max_num_vars: usize) -> Self {
+    self.max_num_vars = max_num_vars;
+    self
+  }
+
+  /// Commits to the scalar `x` with the generator of the key, with which the prover commits to
+  /// the inner products of each round. This uses a table of multiples of the generator that is
+  /// computed on the first call and cached in the key, so that each commitment takes one
+  /// group addition per 4 bits of `x` and no doubling. `prove` commits with each inner product
+  /// of a round with the table.
+  pub fn commit_scalar(&self, x: &E::Scalar) -> Commitment<E> {
+    self.ck_s_table().commit(x)
+  }
+
+  fn ck_s_table(&self) -> Arc<GeneratorTable<E>> {
+    self
+      .ck_s_table
+      .get_or_init(|| {
+        Arc::new(GeneratorTable::new(CE::<E>::commit(
+          &self.ck_s,
+          &[E::Scalar::ONE],
+        )))
+      })
+      .clone()
+  }
+}
+
+impl<E: Engine> VerifierKey<E> {
+  /// Derives the verifier key that `setup` returns along with `pk`, from `pk` and the
+  /// commitment key `ck` passed to `setup`, so that a verifier that receives only the prover
+  /// key need not run `setup` again. The verifier key holds `ck` itself, against which the
+  /// polynomials are committed, and the generator for the inner product that is shared with the
+  /// prover key. The other settings of the verifier key take their default values.
+  pub fn from_prover_key(pk: &ProverKey<E>, ck: &CommitmentKey<E>) -> Self {
+    VerifierKey {
+      ck_v: ck.clone(),
+      ck_s: pk.ck_s.clone(),
+      max_num_vars: DEFAULT_MAX_NUM_VARS,
+      fixed_size: None,
+      round_order: RoundOrder::LeftFirst,
+      soundness_bits: None,
+    }
+  }
+
+  /// Returns the bits of soundness per challenge asserted for the key by
+  /// `EvaluationEngine::setup_with_soundness`, or `None` if the key was made by `setup`
+  pub fn soundness_bits(&self) -> Option<u32> {
+    self.soundness_bits
+  }
+
+  /// Sets the maximum number of variables of the points at which the verifier checks
+  /// evaluations (30 by default); larger points are rejected with `NovaError::InputTooLarge`
+  pub fn with_max_num_vars(mut self, max_num_vars: usize) -> Self {
+    self.max_num_vars = max_num_vars;
+    self
+  }
+
+  /// Sets the order in which `verify` absorbs the commitments of each round, so that the
+  /// verifier accepts arguments produced under that order instead of `RoundOrder::LeftFirst`
+  pub fn with_round_order(mut self, round_order: RoundOrder) -> Self {
+    self.round_order = round_order;
+    self
+  }
+
+  /// Fixes the length `n` of the committed vectors, for a deployment that verifies a single
+  /// circuit size. The verifier then rejects points with other than `log2(n)` coordinates, and
+  /// thus arguments for vectors of any other length, with `NovaError::ProofLengthMismatch`.
+  pub fn with_fixed_size(mut self, n: usize) -> Self {
+    self.fixed_size = Some(n);
+    self
+  }
+
+  // checks that the verifier accepts evaluations at points with as many coordinates as `point`
+  pub(crate) fn check_point<S>(&self, point: &[S]) -> Result<(), NovaError> {
+    check_num_vars(point, self.max_num_vars)?;
+    if let Some(n) = self.fixed_size {
+      if point.len() >= usize::BITS as usize || 1 << point.len() != n {
+        return Err(NovaError::ProofLengthMismatch);
+      }
+    }
+    Ok(())
+  }
 }
 
 /// Provides an implementation of a polynomial evaluation engine using IPA
@@ -51,11 +211,12 @@ where
   ) -> (Self::ProverKey, Self::VerifierKey) {
     let ck_c = E::CE::setup(b"ipa", 1);
 
-    let pk = ProverKey { ck_s: ck_c.clone() };
-    let vk = VerifierKey {
-      ck_v: ck.clone(),
+    let pk = ProverKey {
       ck_s: ck_c,
+      max_num_vars: DEFAULT_MAX_NUM_VARS,
+      ck_s_table: OnceLock::new(),
     };
+    let vk = VerifierKey::from_prover_key(&pk, ck);
 
     (pk, vk)
   }
@@ -69,13 +230,14 @@ where
     point: &[E::Scalar],
     eval: &E::Scalar,
   ) -> Result<Self::EvaluationArgument, NovaError> {
-    let u = InnerProductInstance::new(comm, &EqPolynomial::new(point.to_vec()).evals(), eval);
-    let w = InnerProductWitness::new(poly);
+    check_num_vars(point, pk.max_num_vars)?;
 
-    InnerProductArgument::prove(ck, &pk.ck_s, &u, &w, transcript)
+    let eq = EqPolynomial::new(point.to_vec()).evals();
+    Self::prove_with_eq(ck, pk, transcript, comm, poly, point, &eq, eval)
   }
 
-  /// A method to verify purported evaluations of a batch of polynomials
+  /// A method to verify the purported evaluation of a single polynomial; `verify_batch`
+  /// verifies a batch of evaluations with a single argument
   fn verify(
     vk: &Self::VerifierKey,
     transcript: &mut E::TE,
@@ -84,323 +246,7569 @@ where
     eval: &E::Scalar,
     arg: &Self::EvaluationArgument,
   ) -> Result<(), NovaError> {
-    let u = InnerProductInstance::new(comm, &EqPolynomial::new(point.to_vec()).evals(), eval);
-
-    arg.verify(
-      &vk.ck_v,
-      &vk.ck_s,
-      (2_usize).pow(point.len() as u32),
-      &u,
-      transcript,
-    )?;
-
-    Ok(())
+    Self::verify_deferred(vk, transcript, comm, point, eval, arg)?.check()
   }
 }
 
-fn inner_product<T: Field + Send + Sync>(a: &[T], b: &[T]) -> T {
-  assert_eq!(a.len(), b.len());
-  (0..a.len())
-    .into_par_iter()
-    .map(|i| a[i] * b[i])
-    .reduce(|| T::ZERO, |x, y| x + y)
-}
-
-/// An inner product instance consists of a commitment to a vector `a` and another vector `b`
-/// and the claim that c = <a, b>.
-pub struct InnerProductInstance<E: Engine> {
-  comm_a_vec: Commitment<E>,
-  b_vec: Vec<E::Scalar>,
-  c: E::Scalar,
-}
-
-impl<E> InnerProductInstance<E>
+impl<E> MultilinearEvaluation<E> for EvaluationEngine<E>
 where
   E: Engine,
   E::GE: DlogGroup,
+  CommitmentKey<E>: CommitmentKeyExtTrait<E>,
 {
-  fn new(comm_a_vec: &Commitment<E>, b_vec: &[E::Scalar], c: &E::Scalar) -> Self {
-    InnerProductInstance {
-      comm_a_vec: *comm_a_vec,
-      b_vec: b_vec.to_vec(),
-      c: *c,
+  fn open(
+    ck: &CommitmentKey<E>,
+    pk: &Self::ProverKey,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    poly: &[E::Scalar],
+    point: &[E::Scalar],
+  ) -> Result<(E::Scalar, Self::EvaluationArgument), NovaError> {
+    if poly.len() != 1 << point.len() {
+      return Err(NovaError::InvalidInputLength);
     }
-  }
-}
 
-impl<E: Engine> TranscriptReprTrait<E::GE> for InnerProductInstance<E> {
-  fn to_transcript_bytes(&self) -> Vec<u8> {
-    // we do not need to include self.b_vec as in our context it is produced from the transcript
-    [
-      self.comm_a_vec.to_transcript_bytes(),
-      self.c.to_transcript_bytes(),
-    ]
-    .concat()
+    let eval = MultilinearPolynomial::evaluate_with(poly, point);
+    let arg =
+      <Self as EvaluationEngineTrait<E>>::prove(ck, pk, transcript, comm, poly, point, &eval)?;
+    Ok((eval, arg))
   }
-}
-
-struct InnerProductWitness<E: Engine> {
-  a_vec: Vec<E::Scalar>,
-}
 
-impl<E: Engine> InnerProductWitness<E> {
-  fn new(a_vec: &[E::Scalar]) -> Self {
-    InnerProductWitness {
-      a_vec: a_vec.to_vec(),
-    }
+  fn verify_opening(
+    vk: &Self::VerifierKey,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &Self::EvaluationArgument,
+  ) -> Result<(), NovaError> {
+    <Self as EvaluationEngineTrait<E>>::verify(vk, transcript, comm, point, eval, arg)
   }
 }
 
-/// An inner product argument
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(bound = "")]
-pub struct InnerProductArgument<E: Engine> {
-  L_vec: Vec<Commitment<E>>,
-  R_vec: Vec<Commitment<E>>,
-  a_hat: E::Scalar,
-}
-
-impl<E> InnerProductArgument<E>
+impl<E> EvaluationEngine<E>
 where
   E: Engine,
   E::GE: DlogGroup,
   CommitmentKey<E>: CommitmentKeyExtTrait<E>,
 {
-  const fn protocol_name() -> &'static [u8] {
-    b"IPA"
-  }
-
-  fn prove(
+  /// Proves an evaluation like `prove`, with the evaluations `eq` of the `eq` polynomial at
+  /// `point` over the boolean hypercube computed by the caller, e.g., once for several
+  /// polynomials opened at the same point. `eq` must equal `EqPolynomial::new(point).evals()`,
+  /// which is asserted in debug builds. Returns `NovaError::InvalidInputLength` if `eq` does not
+  /// have `2^point.len()` entries.
+  pub fn prove_with_eq(
     ck: &CommitmentKey<E>,
-    ck_c: &CommitmentKey<E>,
-    U: &InnerProductInstance<E>,
-    W: &InnerProductWitness<E>,
+    pk: &ProverKey<E>,
     transcript: &mut E::TE,
-  ) -> Result<Self, NovaError> {
-    transcript.dom_sep(Self::protocol_name());
-
-    let (ck, _) = ck.split_at(U.b_vec.len());
-
-    if U.b_vec.len() != W.a_vec.len() {
+    comm: &Commitment<E>,
+    poly: &[E::Scalar],
+    point: &[E::Scalar],
+    eq: &[E::Scalar],
+    eval: &E::Scalar,
+  ) -> Result<InnerProductArgument<E>, NovaError> {
+    check_num_vars(point, pk.max_num_vars)?;
+    if eq.len() != 1 << point.len() {
       return Err(NovaError::InvalidInputLength);
     }
+    debug_assert_eq!(eq, EqPolynomial::new(point.to_vec()).evals());
 
-    // absorb the instance in the transcript
-    transcript.absorb(b"U", U);
-
-    // sample a random base for committing to the inner product
-    let r = transcript.squeeze(b"r")?;
-    let ck_c = ck_c.scale(&r);
-
-    // a closure that executes a step of the recursive inner product argument
-    let prove_inner = |a_vec: &[E::Scalar],
-                       b_vec: &[E::Scalar],
-                       ck: &CommitmentKey<E>,
-                       transcript: &mut E::TE|
-     -> Result<
-      (
-        Commitment<E>,
-        Commitment<E>,
-        Vec<E::Scalar>,
-        Vec<E::Scalar>,
-        CommitmentKey<E>,
-      ),
-      NovaError,
-    > {
-      let n = a_vec.len();
-      let (ck_L, ck_R) = ck.split_at(n / 2);
-
-      let c_L = inner_product(&a_vec[0..n / 2], &b_vec[n / 2..n]);
-      let c_R = inner_product(&a_vec[n / 2..n], &b_vec[0..n / 2]);
+    let u = InnerProductInstance::new_eq_with_evals(comm, point, eq, eval);
+    let w = InnerProductWitness::new(poly);
 
-      let L = CE::<E>::commit(
-        &ck_R.combine(&ck_c),
-        &a_vec[0..n / 2]
-          .iter()
-          .chain(iter::once(&c_L))
-          .copied()
-          .collect::<Vec<E::Scalar>>(),
-      );
-      let R = CE::<E>::commit(
-        &ck_L.combine(&ck_c),
-        &a_vec[n / 2..n]
-          .iter()
-          .chain(iter::once(&c_R))
-          .copied()
-          .collect::<Vec<E::Scalar>>(),
-      );
+    InnerProductArgument::prove_owned(ck, pk, u, w, transcript)
+  }
 
-      transcript.absorb(b"L", &L);
-      transcript.absorb(b"R", &R);
+  /// Verifies an evaluation like `verify`, for a caller that holds the evaluations `eq` of the
+  /// `eq` polynomial at `point`, as passed to `prove_with_eq`. The verifier does not need the
+  /// table, as it evaluates the `eq` polynomial in closed form from `point`, so `verify` does
+  /// not compute it; the table is only checked against `point`, in debug builds.
+  pub fn verify_with_eq(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    point: &[E::Scalar],
+    eq: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+  ) -> Result<(), NovaError> {
+    if point.len() >= usize::BITS as usize || eq.len() != 1 << point.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+    debug_assert_eq!(eq, EqPolynomial::new(point.to_vec()).evals());
 
-      let r = transcript.squeeze(b"r")?;
-      let r_inverse = r.invert().unwrap();
+    <Self as EvaluationEngineTrait<E>>::verify(vk, transcript, comm, point, eval, arg)
+  }
 
-      // fold the left half and the right half
-      let a_vec_folded = a_vec[0..n / 2]
-        .par_iter()
-        .zip(a_vec[n / 2..n].par_iter())
-        .map(|(a_L, a_R)| *a_L * r + r_inverse * *a_R)
-        .collect::<Vec<E::Scalar>>();
+  /// Returns the evaluations over the boolean hypercube of the multilinear polynomial whose
+  /// evaluations are committed in `poly`, which are `poly` itself: the `i`-th entry is the
+  /// evaluation at the vertex whose coordinates are the bits of `i`, with the most significant
+  /// bit first, as returned by `vertex`
+  pub fn full_table(poly: &[E::Scalar]) -> &[E::Scalar] {
+    poly
+  }
 
-      let b_vec_folded = b_vec[0..n / 2]
-        .par_iter()
-        .zip(b_vec[n / 2..n].par_iter())
-        .map(|(b_L, b_R)| *b_L * r_inverse + r * *b_R)
-        .collect::<Vec<E::Scalar>>();
+  /// Returns the `i`-th vertex of the boolean hypercube with `num_vars` dimensions, whose
+  /// coordinates are the bits of `i`, with the most significant bit first
+  pub fn vertex(num_vars: usize, i: usize) -> Vec<E::Scalar> {
+    (0..num_vars)
+      .map(|j| E::Scalar::from(u64::from(index_bit(i, num_vars, j))))
+      .collect()
+  }
 
-      let ck_folded = ck.fold(&r_inverse, &r);
+  /// Checks the claimed full table `table` of the polynomial committed in `comm` against
+  /// evaluation arguments at some of its vertices: each `(i, arg)` in `openings` is an argument
+  /// produced by `prove` that the polynomial evaluates to `table[i]` at `vertex(num_vars, i)`,
+  /// where the arguments are verified in order with `transcript`. Only the opened entries of
+  /// the table are checked. Returns `NovaError::InvalidInputLength` if the length of the table
+  /// is not a power of two, and `NovaError::InvalidIndex` if an opening is out of range.
+  pub fn verify_all_vertices(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    table: &[E::Scalar],
+    openings: &[(usize, InnerProductArgument<E>)],
+  ) -> Result<(), NovaError> {
+    if !table.len().is_power_of_two() {
+      return Err(NovaError::InvalidInputLength);
+    }
+    let num_vars = table.len().ilog2() as usize;
 
-      Ok((L, R, a_vec_folded, b_vec_folded, ck_folded))
-    };
+    for (i, arg) in openings {
+      let eval = table.get(*i).ok_or(NovaError::InvalidIndex)?;
+      let point = Self::vertex(num_vars, *i);
+      <Self as EvaluationEngineTrait<E>>::verify(vk, transcript, comm, &point, eval, arg)?;
+    }
+    Ok(())
+  }
 
-    // two vectors to hold the logarithmic number of group elements
-    let mut L_vec: Vec<Commitment<E>> = Vec::new();
-    let mut R_vec: Vec<Commitment<E>> = Vec::new();
+  /// Verifies an evaluation like `verify`, comparing the final check with the identity in
+  /// constant time as in `DeferredCheck::check_constant_time`. `verify` compares the group
+  /// elements with their `PartialEq`, which may return early, so this is meant for verifiers
+  /// that run on secret inputs.
+  pub fn verify_constant_time(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+  ) -> Result<(), NovaError> {
+    Self::verify_deferred(vk, transcript, comm, point, eval, arg)?.check_constant_time()
+  }
 
-    // we create mutable copies of vectors and generators
-    let mut a_vec = W.a_vec.to_vec();
-    let mut b_vec = U.b_vec.to_vec();
-    let mut ck = ck;
-    for _i in 0..usize::try_from(U.b_vec.len().ilog2()).unwrap() {
-      let (L, R, a_vec_folded, b_vec_folded, ck_folded) =
-        prove_inner(&a_vec, &b_vec, &ck, transcript)?;
-      L_vec.push(L);
-      R_vec.push(R);
+  /// Proves an evaluation like `prove`, except that the final check, namely that the folded
+  /// commitment `P_hat` equals `a_hat * (G_hat + b_hat * ck_c)`, is turned into a Schnorr-style
+  /// proof of knowledge of `a_hat` with a mask drawn from `rng`, so that `a_hat` is not
+  /// revealed. The round commitments are those of `prove`, which is enough for protocols that
+  /// only compose with the opening of `a_hat`; `ZKEvaluationEngine` also blinds the rounds.
+  pub fn prove_final_knowledge<R: RngCore + CryptoRng>(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    poly: &[E::Scalar],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    rng: &mut R,
+  ) -> Result<FinalKnowledgeArgument<E>, NovaError> {
+    check_num_vars(point, pk.max_num_vars)?;
 
-      a_vec = a_vec_folded;
-      b_vec = b_vec_folded;
-      ck = ck_folded;
+    let u = InnerProductInstance::new_eq(comm, point, eval);
+    let w = InnerProductWitness::new(poly);
+    let mut state = InnerProductArgument::<E>::prove_deferred(ck, &pk.ck_s, &u, &w, transcript)?;
+    while state.a_vec.len() > 1 {
+      state.prove_round(transcript)?;
     }
 
-    Ok(InnerProductArgument {
-      L_vec,
-      R_vec,
-      a_hat: a_vec[0],
+    // `P_hat = a_hat * H` for `H = G_hat + b_hat * ck_c`
+    let (a_hat, b_hat) = (state.a_vec[0], state.b_vec[0]);
+    let H = CE::<E>::commit(&state.ck.combine(&state.ck_c), &[E::Scalar::ONE, b_hat]);
+    let d = E::Scalar::random(&mut *rng);
+    let T = H * d;
+    transcript.absorb(b"T", &T);
+    let e = transcript.squeeze(b"e")?;
+
+    Ok(FinalKnowledgeArgument {
+      L_vec: state.L_vec,
+      R_vec: state.R_vec,
+      T,
+      z: d + e * a_hat,
+      instance_digest: state.instance_digest,
     })
   }
 
-  fn verify(
-    &self,
-    ck: &CommitmentKey<E>,
-    ck_c: &CommitmentKey<E>,
-    n: usize,
-    U: &InnerProductInstance<E>,
+  /// Verifies an argument produced by `prove_final_knowledge`: the verifier replays the rounds
+  /// as in `verify`, and then checks `z * H == T + e * P_hat` in place of the final check
+  pub fn verify_final_knowledge(
+    vk: &VerifierKey<E>,
     transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &FinalKnowledgeArgument<E>,
   ) -> Result<(), NovaError> {
-    let (ck, _) = ck.split_at(U.b_vec.len());
+    // the rounds are those of an argument whose final scalar is the response `z`
+    let rounds = InnerProductArgument {
+      L_vec: arg.L_vec.clone(),
+      R_vec: arg.R_vec.clone(),
+      a_hat: arg.z,
+      instance_digest: arg.instance_digest,
+    };
+    let n = Self::check_claim(vk, comm, point, eval, &rounds)?;
+    let u = InnerProductInstance::new_eq(comm, point, eval);
+    let challenges = rounds.challenges(vk, n, &u, transcript)?;
+    transcript.absorb(b"T", &arg.T);
+    let e = transcript.squeeze(b"e")?;
 
-    transcript.dom_sep(Self::protocol_name());
-    if U.b_vec.len() != n
-      || n != (1 << self.L_vec.len())
-      || self.L_vec.len() != self.R_vec.len()
-      || self.L_vec.len() >= 32
-    {
-      return Err(NovaError::InvalidInputLength);
+    // the final check of `rounds` against the claim `e * eval` is `P_hat' - z * H = 0`, where
+    // `P_hat'` differs from `P_hat` in the multiple `e * eval` of `ck_c`; we scale the other
+    // terms of `P_hat`, which are the round commitments and `comm`, by `e`, and add `T`
+    let u_e = InnerProductInstance::new_eq(comm, point, &(e * eval));
+    let mut check: DeferredCheck<E> =
+      rounds.verify_deferred_with_challenges(&vk.ck_v, &vk.ck_s, n, &u_e, &challenges)?;
+    for s in check.scalars[check.num_key_bases..].iter_mut() {
+      *s *= e;
     }
+    check.bases = check
+      .bases
+      .combine(&CommitmentKey::<E>::reinterpret_commitments_as_ck(
+        &[arg.T],
+      )?);
+    check.scalars.push(E::Scalar::ONE);
+    check.check()
+  }
 
-    // absorb the instance in the transcript
-    transcript.absorb(b"U", U);
+  /// Like `setup`, and checks that the scalar field is large enough for each challenge of the
+  /// argument to provide at least `target_bits` bits of soundness, which the verifier key then
+  /// records (see `VerifierKey::soundness_bits`). Returns `NovaError::InsufficientSoundness` if
+  /// the field is too small.
+  pub fn setup_with_soundness(
+    ck: &CommitmentKey<E>,
+    target_bits: u32,
+  ) -> Result<(ProverKey<E>, VerifierKey<E>), NovaError> {
+    if challenge_soundness_bits::<E>() < target_bits {
+      return Err(NovaError::InsufficientSoundness);
+    }
 
-    // sample a random base for committing to the inner product
-    let r = transcript.squeeze(b"r")?;
-    let ck_c = ck_c.scale(&r);
+    let (pk, mut vk) = <Self as EvaluationEngineTrait<E>>::setup(ck);
+    vk.soundness_bits = Some(target_bits);
+    Ok((pk, vk))
+  }
 
-    let P = U.comm_a_vec + CE::<E>::commit(&ck_c, &[U.c]);
+  /// Proves and verifies the evaluation of a small fixed polynomial, and checks that the
+  /// argument is rejected once corrupted, so that a service can detect a broken build, e.g.,
+  /// wrong constants or broken curve arithmetic, before serving proofs. Returns the error of
+  /// the prover or the verifier if the honest argument fails, and `NovaError::SelfTestFailed`
+  /// if the corrupted one is accepted.
+  pub fn self_test() -> Result<(), NovaError> {
+    let num_vars = 3;
+    let ck = E::CE::setup(b"ipa-self-test", 1 << num_vars);
+    let (pk, vk) = <Self as EvaluationEngineTrait<E>>::setup(&ck);
 
-    let batch_invert = |v: &[E::Scalar]| -> Result<Vec<E::Scalar>, NovaError> {
-      let mut products = vec![E::Scalar::ZERO; v.len()];
-      let mut acc = E::Scalar::ONE;
+    let poly = (0..1 << num_vars)
+      .map(|i| E::Scalar::from(i as u64 + 1))
+      .collect::<Vec<_>>();
+    let point = [2, 3, 5].map(E::Scalar::from);
+    let eval = MultilinearPolynomial::evaluate_with(&poly, &point);
+    let comm = E::CE::commit(&ck, &poly);
 
-      for i in 0..v.len() {
-        products[i] = acc;
-        acc *= v[i];
-      }
+    let mut transcript = E::TE::new(b"ipa-self-test");
+    let mut arg = <Self as EvaluationEngineTrait<E>>::prove(
+      &ck,
+      &pk,
+      &mut transcript,
+      &comm,
+      &poly,
+      &point,
+      &eval,
+    )?;
 
-      // return error if acc is zero
-      acc = match Option::from(acc.invert()) {
-        Some(inv) => inv,
-        None => return Err(NovaError::InternalError),
-      };
+    let mut transcript = E::TE::new(b"ipa-self-test");
+    <Self as EvaluationEngineTrait<E>>::verify(&vk, &mut transcript, &comm, &point, &eval, &arg)?;
 
-      // compute the inverse once for all entries
-      let mut inv = vec![E::Scalar::ZERO; v.len()];
-      for i in (0..v.len()).rev() {
-        let tmp = acc * v[i];
-        inv[i] = products[i] * acc;
-        acc = tmp;
-      }
+    arg.a_hat += E::Scalar::ONE;
+    let mut transcript = E::TE::new(b"ipa-self-test");
+    match <Self as EvaluationEngineTrait<E>>::verify(
+      &vk,
+      &mut transcript,
+      &comm,
+      &point,
+      &eval,
+      &arg,
+    ) {
+      Ok(()) => Err(NovaError::SelfTestFailed),
+      Err(_) => Ok(()),
+    }
+  }
 
-      Ok(inv)
-    };
+  /// Proves an evaluation like `prove` on the threads of `pool`, so that a service running
+  /// several provers can cap the parallelism of each job. The parallel iterators of the prover
+  /// run on the pool from which they are called, so none of the work leaves `pool`.
+  #[cfg(feature = "parallel")]
+  pub fn prove_in_pool(
+    pool: &rayon::ThreadPool,
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    poly: &[E::Scalar],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+  ) -> Result<InnerProductArgument<E>, NovaError> {
+    pool.install(|| {
+      <Self as EvaluationEngineTrait<E>>::prove(ck, pk, transcript, comm, poly, point, eval)
+    })
+  }
 
-    // compute a vector of public coins using self.L_vec and self.R_vec
-    let r = (0..self.L_vec.len())
-      .map(|i| {
-        transcript.absorb(b"L", &self.L_vec[i]);
-        transcript.absorb(b"R", &self.R_vec[i]);
-        transcript.squeeze(b"r")
-      })
-      .collect::<Result<Vec<E::Scalar>, NovaError>>()?;
+  /// Verifies an evaluation argument like `verify`, except that the final check is returned
+  /// as a [`DeferredCheck`] instead of being performed
+  pub fn verify_deferred(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+  ) -> Result<DeferredCheck<E>, NovaError> {
+    let n = Self::check_claim(vk, comm, point, eval, arg)?;
+    let u = InnerProductInstance::new_eq(comm, point, eval);
 
-    // precompute scalars necessary for verification
-    let r_square: Vec<E::Scalar> = (0..self.L_vec.len())
-      .into_par_iter()
-      .map(|i| r[i] * r[i])
-      .collect();
-    let r_inverse = batch_invert(&r)?;
-    let r_inverse_square: Vec<E::Scalar> = (0..self.L_vec.len())
-      .into_par_iter()
-      .map(|i| r_inverse[i] * r_inverse[i])
-      .collect();
+    arg.verify_deferred(vk, n, &u, transcript)
+  }
 
-    // compute the vector with the tensor structure
-    let s = {
-      let mut s = vec![E::Scalar::ZERO; n];
-      s[0] = {
-        let mut v = E::Scalar::ONE;
-        for r_inverse_i in r_inverse {
-          v *= r_inverse_i;
-        }
-        v
-      };
-      for i in 1..n {
-        let pos_in_r = (31 - (i as u32).leading_zeros()) as usize;
-        s[i] = s[i - (1 << pos_in_r)] * r_square[(self.L_vec.len() - 1) - pos_in_r];
-      }
-      s
-    };
+  /// Verifies an evaluation argument like `verify`, and on success also appends its final
+  /// check to `acc`, so that [`CheckAccumulator::check`] can later re-verify all the
+  /// accumulated arguments with a single MSM. A failed argument is not appended.
+  pub fn verify_and_accumulate(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+    acc: &mut CheckAccumulator<E>,
+  ) -> Result<(), NovaError> {
+    let check = Self::verify_deferred(vk, transcript, comm, point, eval, arg)?;
+    check.check()?;
+    acc.push(check);
+    Ok(())
+  }
 
-    let ck_hat = {
-      let c = CE::<E>::commit(&ck, &s);
-      CommitmentKey::<E>::reinterpret_commitments_as_ck(&[c])?
-    };
+  /// Starts verifying an evaluation argument for a commitment that is not known yet. This does
+  /// the work that does not depend on the commitment, such as checking the shape of `arg`, and
+  /// the verification is completed with
+  /// [`PendingVerification::finalize`] once the commitment is available.
+  ///
+  /// The order in which the transcript absorbs messages is unchanged: the commitment is still
+  /// absorbed before any challenge is derived, so all challenges are derived in `finalize`. This
+  /// is necessary for soundness. If challenges were derived before the commitment is bound, a
+  /// malicious prover could choose a commitment after seeing them and open it to any value.
+  /// The accept/reject decision is therefore the same as with `verify`.
+  pub fn verify_incremental<'a>(
+    vk: &'a VerifierKey<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &'a InnerProductArgument<E>,
+  ) -> Result<PendingVerification<'a, E>, NovaError> {
+    Self::check_shape(vk, point, arg)?;
 
-    let b_hat = inner_product(&U.b_vec, &s);
+    Ok(PendingVerification {
+      vk,
+      arg,
+      point: point.to_vec(),
+      eval: *eval,
+    })
+  }
 
-    let P_hat = {
-      let ck_folded = {
-        let ck_L = CommitmentKey::<E>::reinterpret_commitments_as_ck(&self.L_vec)?;
-        let ck_R = CommitmentKey::<E>::reinterpret_commitments_as_ck(&self.R_vec)?;
-        let ck_P = CommitmentKey::<E>::reinterpret_commitments_as_ck(&[P])?;
-        ck_L.combine(&ck_R).combine(&ck_P)
-      };
+  /// Proves an evaluation like `prove`, except that the prover stops before the final rounds,
+  /// once the folded vectors have length at most 4, and returns its state. This lets an
+  /// aggregator inspect or combine the folded states of several proofs; the proof is then
+  /// completed on the same transcript with `finish_deferred`.
+  pub fn prove_deferred(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    poly: &[E::Scalar],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+  ) -> Result<InnerProductProverState<E>, NovaError> {
+    check_num_vars(point, pk.max_num_vars)?;
 
-      CE::<E>::commit(
-        &ck_folded,
-        &r_square
-          .iter()
-          .chain(r_inverse_square.iter())
-          .chain(iter::once(&E::Scalar::ONE))
-          .copied()
-          .collect::<Vec<E::Scalar>>(),
-      )
-    };
+    let u = InnerProductInstance::new_eq(comm, point, eval);
+    let w = InnerProductWitness::new(poly);
 
-    if P_hat == CE::<E>::commit(&ck_hat.combine(&ck_c), &[self.a_hat, self.a_hat * b_hat]) {
-      Ok(())
+    InnerProductArgument::prove_deferred(ck, &pk.ck_s, &u, &w, transcript)
+  }
+
+  /// Completes a proof started with `prove_deferred`. The result is the same argument as the
+  /// one output by `prove`, provided the transcript was not modified in between.
+  pub fn finish_deferred(
+    state: InnerProductProverState<E>,
+    transcript: &mut E::TE,
+  ) -> Result<InnerProductArgument<E>, NovaError> {
+    state.finish(transcript)
+  }
+
+  /// Proves an evaluation like `prove`, and also returns the commitment key folded by the
+  /// rounds of the argument, which has a single generator. This is the key that the verifier
+  /// reconstructs as `commit(ck, s)` from the challenges, so that an accumulator can commit to
+  /// further vectors against it.
+  pub fn prove_with_folded_ck(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    poly: &[E::Scalar],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+  ) -> Result<(InnerProductArgument<E>, CommitmentKey<E>), NovaError> {
+    check_num_vars(point, pk.max_num_vars)?;
+
+    let u = InnerProductInstance::new_eq(comm, point, eval);
+    let w = InnerProductWitness::new(poly);
+
+    InnerProductArgument::prove_deferred(ck, &pk.ck_s, &u, &w, transcript)?
+      .finish_with_folded_ck(transcript)
+  }
+
+  /// Like `prove`, but the argument does not add its protocol name as a domain separator to
+  /// the transcript. This is meant for embedding the argument in a larger protocol that already
+  /// domain-separates; the proof must then be checked with `verify_without_dom_sep`.
+  pub fn prove_without_dom_sep(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    poly: &[E::Scalar],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+  ) -> Result<InnerProductArgument<E>, NovaError> {
+    check_num_vars(point, pk.max_num_vars)?;
+
+    let u = InnerProductInstance::new_eq(comm, point, eval).without_dom_sep();
+    let w = InnerProductWitness::new(poly);
+
+    InnerProductArgument::prove(ck, &pk.ck_s, &u, &w, transcript)
+  }
+
+  /// Verifies an evaluation argument produced by `prove_without_dom_sep`
+  pub fn verify_without_dom_sep(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+  ) -> Result<(), NovaError> {
+    let n = Self::check_claim(vk, comm, point, eval, arg)?;
+    let u = InnerProductInstance::new_eq(comm, point, eval).without_dom_sep();
+
+    arg.verify(vk, n, &u, transcript)
+  }
+
+  /// Verifies many evaluation arguments, which need not share a commitment or a point, with a
+  /// single MSM. The arguments must have been produced one after another on the same
+  /// transcript, which the verifier replays before sampling random weights from it and checking
+  /// one random linear combination of the final checks of all arguments. If any argument is
+  /// invalid, the combination passes with negligible probability.
+  pub fn verify_many(
+    vk: &VerifierKey<E>,
+    instances: &[(
+      Commitment<E>,
+      Vec<E::Scalar>,
+      E::Scalar,
+      InnerProductArgument<E>,
+    )],
+    transcript: &mut E::TE,
+  ) -> Result<(), NovaError> {
+    Self::verify_many_inner(vk, instances, transcript, E::Scalar::CAPACITY, 1)
+  }
+
+  /// Like `verify_many`, but chooses the random weights of the combination so that the
+  /// probability that a batch containing an invalid argument passes the combined check is at
+  /// most `2^-target_bits`, and reports the choice. Each combination uses weights of up to
+  /// `E::Scalar::CAPACITY` bits, so a target beyond that runs independent combinations.
+  /// The bound covers the batching step only, on top of the soundness error of each argument.
+  pub fn verify_many_with_soundness(
+    vk: &VerifierKey<E>,
+    instances: &[(
+      Commitment<E>,
+      Vec<E::Scalar>,
+      E::Scalar,
+      InnerProductArgument<E>,
+    )],
+    transcript: &mut E::TE,
+    target_bits: u32,
+  ) -> Result<SoundnessReport, NovaError> {
+    if target_bits == 0 {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let num_repetitions = target_bits.div_ceil(E::Scalar::CAPACITY);
+    let challenge_bits = target_bits.div_ceil(num_repetitions);
+    Self::verify_many_inner(
+      vk,
+      instances,
+      transcript,
+      challenge_bits,
+      num_repetitions as usize,
+    )?;
+
+    Ok(SoundnessReport {
+      challenge_bits,
+      num_repetitions: num_repetitions as usize,
+      soundness_bits: challenge_bits * num_repetitions,
+    })
+  }
+
+  // checks `num_repetitions` random linear combinations of the final checks of the arguments,
+  // each with weights of `challenge_bits` bits
+  fn verify_many_inner(
+    vk: &VerifierKey<E>,
+    instances: &[(
+      Commitment<E>,
+      Vec<E::Scalar>,
+      E::Scalar,
+      InnerProductArgument<E>,
+    )],
+    transcript: &mut E::TE,
+    challenge_bits: u32,
+    num_repetitions: usize,
+  ) -> Result<(), NovaError> {
+    let checks = instances
+      .iter()
+      .map(|(comm, point, eval, arg)| Self::verify_deferred(vk, transcript, comm, point, eval, arg))
+      .collect::<Result<Vec<DeferredCheck<E>>, NovaError>>()?;
+
+    for _ in 0..num_repetitions {
+      let weights = (0..checks.len())
+        .map(|_| Ok(truncate(&transcript.squeeze(b"w")?, challenge_bits)))
+        .collect::<Result<Vec<E::Scalar>, NovaError>>()?;
+
+      DeferredCheck::aggregate_with_key(vk, &checks, &weights)?.check()?;
+    }
+
+    Ok(())
+  }
+
+  /// Verifies many independent evaluation arguments with a single MSM. Unlike `verify_many`,
+  /// each argument comes with its own transcript, in the state in which its prover started, so
+  /// the arguments need not have been produced on a shared transcript. The final checks of all
+  /// the arguments are combined with random weights sampled here, after all the checks are
+  /// fixed, so the combination passes with negligible probability if any argument is invalid.
+  /// The points may have different lengths, and an empty batch is accepted.
+  pub fn verify_independent(
+    vk: &VerifierKey<E>,
+    transcripts: &mut [E::TE],
+    comms: &[Commitment<E>],
+    points: &[Vec<E::Scalar>],
+    evals: &[E::Scalar],
+    args: &[InnerProductArgument<E>],
+  ) -> Result<(), NovaError> {
+    let n = transcripts.len();
+    if comms.len() != n || points.len() != n || evals.len() != n || args.len() != n {
+      return Err(NovaError::InvalidInputLength);
+    }
+    if n == 0 {
+      return Ok(());
+    }
+
+    let checks = transcripts
+      .iter_mut()
+      .zip(comms.iter())
+      .zip(points.iter())
+      .zip(evals.iter())
+      .zip(args.iter())
+      .map(|((((transcript, comm), point), eval), arg)| {
+        Self::verify_deferred(vk, transcript, comm, point, eval, arg)
+      })
+      .collect::<Result<Vec<DeferredCheck<E>>, NovaError>>()?;
+
+    let weights = (0..n)
+      .map(|_| E::Scalar::random(OsRng))
+      .collect::<Vec<E::Scalar>>();
+    DeferredCheck::aggregate_with_key(vk, &checks, &weights)?.check()
+  }
+
+  /// Verifies the arguments that `f(point) = eval_f` and `g(point) = eval_g`, where `f` and `g`
+  /// are committed in `comm_f` and `comm_g`, and checks that `eval_f - eval_g = delta` for a
+  /// public `delta`. The arguments must have been produced one after another on the same
+  /// transcript, as for `verify_many`, and their final checks are combined with random weights
+  /// into a single MSM. Returns `NovaError::EvaluationRelationFailed` if the evaluations do not
+  /// differ by `delta`, before the transcript is touched.
+  pub fn verify_with_difference(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm_f: &Commitment<E>,
+    comm_g: &Commitment<E>,
+    point: &[E::Scalar],
+    eval_f: &E::Scalar,
+    eval_g: &E::Scalar,
+    delta: &E::Scalar,
+    arg_f: &InnerProductArgument<E>,
+    arg_g: &InnerProductArgument<E>,
+  ) -> Result<(), NovaError> {
+    if *eval_f - *eval_g != *delta {
+      return Err(NovaError::EvaluationRelationFailed);
+    }
+
+    let checks = [
+      Self::verify_deferred(vk, transcript, comm_f, point, eval_f, arg_f)?,
+      Self::verify_deferred(vk, transcript, comm_g, point, eval_g, arg_g)?,
+    ];
+    let weights = [transcript.squeeze(b"w")?, transcript.squeeze(b"w")?];
+
+    DeferredCheck::aggregate_with_key(vk, &checks, &weights)?.check()
+  }
+
+  /// Proves an evaluation like `prove`, after absorbing a `nonce` supplied by the caller in
+  /// the transcript, e.g., the identifier of a request to a proving service. Two proofs of the
+  /// same statement under different nonces are then distinct, and each only verifies with
+  /// `verify_with_nonce` under its own nonce, which the verifier supplies from its side.
+  pub fn prove_with_nonce(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    nonce: &[u8; 32],
+    comm: &Commitment<E>,
+    poly: &[E::Scalar],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+  ) -> Result<InnerProductArgument<E>, NovaError> {
+    transcript.absorb(b"nonce", &TranscriptDigest(*nonce));
+    <Self as EvaluationEngineTrait<E>>::prove(ck, pk, transcript, comm, poly, point, eval)
+  }
+
+  /// Verifies an argument produced by `prove_with_nonce` under the expected `nonce`; an
+  /// argument produced under another nonce fails the final check
+  pub fn verify_with_nonce(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    nonce: &[u8; 32],
+    comm: &Commitment<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+  ) -> Result<(), NovaError> {
+    transcript.absorb(b"nonce", &TranscriptDigest(*nonce));
+    <Self as EvaluationEngineTrait<E>>::verify(vk, transcript, comm, point, eval, arg)
+  }
+
+  /// Folds many evaluation arguments against the commitment key of `vk` into a single
+  /// accumulator, following the accumulation of Halo: the verifier replays the rounds of each
+  /// argument, but defers its final check, including the expensive MSM that computes the folded
+  /// generator, to `verify_accumulated`. As with `verify_many`, the arguments must have been
+  /// produced one after another on the same transcript, from which the random weights of the
+  /// combination are then sampled. Returns an error if an argument is malformed.
+  pub fn aggregate(
+    vk: &VerifierKey<E>,
+    instances: &[(
+      Commitment<E>,
+      Vec<E::Scalar>,
+      E::Scalar,
+      InnerProductArgument<E>,
+    )],
+    transcript: &mut E::TE,
+  ) -> Result<AccumulatedInstance<E>, NovaError> {
+    let checks = instances
+      .iter()
+      .map(|(comm, point, eval, arg)| Self::verify_deferred(vk, transcript, comm, point, eval, arg))
+      .collect::<Result<Vec<DeferredCheck<E>>, NovaError>>()?;
+
+    let weights = (0..checks.len())
+      .map(|_| transcript.squeeze(b"w"))
+      .collect::<Result<Vec<E::Scalar>, NovaError>>()?;
+
+    Ok(AccumulatedInstance {
+      check: DeferredCheck::aggregate_with_key(vk, &checks, &weights)?,
+      num_arguments: checks.len(),
+    })
+  }
+
+  /// Checks an accumulator produced by `aggregate` with a single MSM, which passes iff all the
+  /// aggregated arguments are valid, except with negligible probability
+  pub fn verify_accumulated(acc: &AccumulatedInstance<E>) -> Result<(), NovaError> {
+    acc.check.check()
+  }
+
+  /// Verifies an evaluation argument like `verify`, but dispatches the multi-scalar
+  /// multiplications to `service`: the one that computes the folded generator `<s, ck>`, of
+  /// size `2^m`, and the final one, of size `2m + 3`, which must yield the identity. Use
+  /// `LocalMsmService` to compute them in process.
+  pub fn verify_with_msm_service<M: MsmService<E>>(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+    service: &M,
+  ) -> Result<(), NovaError> {
+    let n = Self::check_claim(vk, comm, point, eval, arg)?;
+    let u = InnerProductInstance::new_eq(comm, point, eval);
+    let challenges = arg.challenges(vk, n, &u, transcript)?;
+    arg.verify_with_msm_service(&vk.ck_v, &vk.ck_s, n, &u, &challenges, service)
+  }
+
+  /// Computes the generator that the verifier derives by folding the first `2^m` generators of
+  /// `ck` with the `m` folding challenges, as a recursive verifier must compute in-circuit. We
+  /// use the closed-form tensor structure of the folding: the result is the MSM of the
+  /// generators with the vector whose `i`-th entry is the product over the rounds `j` of
+  /// `r_j` if the `j`-th most significant bit of `i` is set, and of `r_j^{-1}` otherwise.
+  pub fn folded_generator(
+    ck: &CommitmentKey<E>,
+    challenges: &InnerProductChallenges<E>,
+  ) -> Result<Commitment<E>, NovaError> {
+    let r = &challenges.r_vec;
+    if r.len() >= 32 || ck.length() < 1 << r.len() {
+      return Err(NovaError::InvalidCommitmentKeyLength);
+    }
+
+    let r_square = r.iter().map(|r_i| r_i.square()).collect::<Vec<E::Scalar>>();
+    let s = tensor_vector(&r_square, &batch_invert(r)?);
+    Ok(CE::<E>::commit(ck, &s))
+  }
+
+  /// Computes the final folded witness `a_hat` of an argument for the polynomial `poly` from
+  /// its folding challenges in closed form, as `<poly, s>`, where `s_i` is the product over
+  /// the rounds `j` of `r_j^{-1}` if the `j`-th most significant bit of `i` is set, and of
+  /// `r_j` otherwise. This lets a test or an audit with access to `poly` check the folding of
+  /// the prover.
+  #[cfg(any(test, feature = "testing"))]
+  pub fn expected_a_hat(
+    poly: &[E::Scalar],
+    challenges: &InnerProductChallenges<E>,
+  ) -> Result<E::Scalar, NovaError> {
+    let r = &challenges.r_vec;
+    if r.len() >= 32 || poly.len() != 1 << r.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let r_inverse = batch_invert(r)?;
+    let r_inverse_square = r_inverse
+      .iter()
+      .map(|r_i| r_i.square())
+      .collect::<Vec<E::Scalar>>();
+    let s = tensor_vector(&r_inverse_square, r);
+    Ok(inner_product(poly, &s))
+  }
+
+  /// Proves the evaluation `eval = f(zeta)` of a univariate polynomial `f`, whose coefficients
+  /// `coeffs` (in order of increasing degree) are committed in `comm`. The number of
+  /// coefficients must be a power of two.
+  pub fn prove_univariate(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    coeffs: &[E::Scalar],
+    zeta: &E::Scalar,
+    eval: &E::Scalar,
+  ) -> Result<InnerProductArgument<E>, NovaError> {
+    let comm = TaggedCommitment::new(*comm, Basis::Coefficients);
+    Self::prove_univariate_tagged(ck, pk, transcript, &comm, coeffs, zeta, eval)
+  }
+
+  /// Verifies an evaluation `eval = f(zeta)` of a univariate polynomial with `n` coefficients
+  /// committed in `comm`, as produced by `prove_univariate`
+  pub fn verify_univariate(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    n: usize,
+    zeta: &E::Scalar,
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+  ) -> Result<(), NovaError> {
+    let comm = TaggedCommitment::new(*comm, Basis::Coefficients);
+    Self::verify_univariate_tagged(vk, transcript, &comm, n, zeta, eval, arg)
+  }
+
+  /// Proves the evaluation `eval = f(zeta)` of a univariate polynomial `f` of degree at most
+  /// `n - 2` from a commitment `comm` to its shift `x * f(x)`. The shift convention is that the
+  /// `n` committed coefficients `shifted_coeffs` (in order of increasing degree) are
+  /// `(0, f_0, f_1, ..., f_{n-2})`, where `n` must be a power of two. The argument opens the
+  /// committed vector against `(0, 1, zeta, ..., zeta^{n-2})`, which yields `f(zeta)` directly,
+  /// also for `zeta = 0`. The leading zero is not checked, since it does not contribute.
+  pub fn prove_shifted(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    shifted_coeffs: &[E::Scalar],
+    zeta: &E::Scalar,
+    eval: &E::Scalar,
+  ) -> Result<InnerProductArgument<E>, NovaError> {
+    if !shifted_coeffs.len().is_power_of_two() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let u = InnerProductInstance::new(comm, &shifted_powers(zeta, shifted_coeffs.len()), eval)
+      .in_basis(Basis::Coefficients);
+    let w = InnerProductWitness::new(shifted_coeffs);
+
+    InnerProductArgument::prove(ck, &pk.ck_s, &u, &w, transcript)
+  }
+
+  /// Verifies an evaluation `eval = f(zeta)` from a commitment `comm` to the `n` coefficients
+  /// of the shift `x * f(x)`, as produced by `prove_shifted`
+  pub fn verify_shifted(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    n: usize,
+    zeta: &E::Scalar,
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+  ) -> Result<(), NovaError> {
+    if !n.is_power_of_two() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let u =
+      InnerProductInstance::new(comm, &shifted_powers(zeta, n), eval).in_basis(Basis::Coefficients);
+
+    arg.verify(vk, n, &u, transcript)
+  }
+
+  /// Proves the evaluation `eval = f(point) + g(point)` of the sum of a committed polynomial
+  /// `f`, given by `poly`, and a public polynomial `g`, given by `public_poly`, with `comm` a
+  /// commitment to `f` alone. The verifier computes `g(point)` itself, so `g` is never
+  /// committed. Both polynomials are given by their evaluations over the boolean hypercube.
+  pub fn prove_with_public_addend(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    poly: &[E::Scalar],
+    public_poly: &[E::Scalar],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+  ) -> Result<InnerProductArgument<E>, NovaError> {
+    let public_eval = Self::public_addend_eval(public_poly, point)?;
+    Self::prove(
+      ck,
+      pk,
+      transcript,
+      comm,
+      poly,
+      point,
+      &(*eval - public_eval),
+    )
+  }
+
+  /// Verifies an evaluation `eval = f(point) + g(point)` from a commitment `comm` to `f` and the
+  /// public polynomial `g`, as produced by `prove_with_public_addend`
+  pub fn verify_with_public_addend(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    public_poly: &[E::Scalar],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+  ) -> Result<(), NovaError> {
+    let public_eval = Self::public_addend_eval(public_poly, point)?;
+    Self::verify(vk, transcript, comm, point, &(*eval - public_eval), arg)
+  }
+
+  // evaluates the public addend at `point`, which must have one coordinate per variable
+  fn public_addend_eval(
+    public_poly: &[E::Scalar],
+    point: &[E::Scalar],
+  ) -> Result<E::Scalar, NovaError> {
+    if point.len() >= usize::BITS as usize || public_poly.len() != 1 << point.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+    Ok(MultilinearPolynomial::evaluate_with(public_poly, point))
+  }
+
+  /// Proves the evaluation `eval = h(point)` of the entrywise product `h = f ∘ g` of a
+  /// committed polynomial `f`, given by `poly`, and a public polynomial `g`, given by
+  /// `public_poly`, with `comm` a commitment to `f` alone. The evaluation is the sum over the
+  /// boolean hypercube of `eq(point, x) f(x) g(x)`, i.e., the inner product of `f` with the
+  /// public vector `eq(point) ∘ g`, which the argument proves directly: since `g` is public,
+  /// the verifier computes that vector itself and no sum-check is needed.
+  pub fn prove_public_product(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    poly: &[E::Scalar],
+    public_poly: &[E::Scalar],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+  ) -> Result<InnerProductArgument<E>, NovaError> {
+    check_num_vars(point, pk.max_num_vars)?;
+    let b_vec = Self::public_product_claim(transcript, public_poly, point)?;
+    if poly.len() != b_vec.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let u = InnerProductInstance::new(comm, &b_vec, eval);
+    let w = InnerProductWitness::new(poly);
+
+    InnerProductArgument::prove_owned(ck, pk, u, w, transcript)
+  }
+
+  /// Verifies an evaluation `eval = h(point)` of the entrywise product `h = f ∘ g` from a
+  /// commitment `comm` to `f` and the public polynomial `g`, as produced by
+  /// `prove_public_product`
+  pub fn verify_public_product(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    public_poly: &[E::Scalar],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+  ) -> Result<(), NovaError> {
+    Self::check_claim(vk, comm, point, eval, arg)?;
+    let b_vec = Self::public_product_claim(transcript, public_poly, point)?;
+
+    let u = InnerProductInstance::new(comm, &b_vec, eval);
+    arg.verify(vk, b_vec.len(), &u, transcript)
+  }
+
+  // binds the point and the public polynomial, and computes the public vector
+  // `eq(point) ∘ public_poly`, whose inner product with a polynomial `f` is the evaluation at
+  // `point` of the entrywise product of `f` and `public_poly`
+  fn public_product_claim(
+    transcript: &mut E::TE,
+    public_poly: &[E::Scalar],
+    point: &[E::Scalar],
+  ) -> Result<Vec<E::Scalar>, NovaError> {
+    if point.len() >= usize::BITS as usize || public_poly.len() != 1 << point.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+    transcript.absorb(b"p", &point);
+    transcript.absorb(b"g", &public_poly);
+
+    let mut b_vec = EqPolynomial::new(point.to_vec()).evals();
+    b_vec
+      .par_iter_mut()
+      .zip(public_poly.par_iter())
+      .for_each(|(b, g)| *b *= *g);
+    Ok(b_vec)
+  }
+
+  /// Proves the evaluation `eval` at `point` of a target polynomial that differs from the
+  /// committed polynomial `base_poly` by a sparse correction, so that the target need not be
+  /// committed. Each `(i, delta)` in `corrections` adds `delta` to the `i`-th evaluation of the
+  /// base. The correction contributes `delta * eq(point, i)` to the evaluation, which the
+  /// verifier computes itself, and the argument opens `base_comm` to the remainder.
+  pub fn prove_with_sparse_correction(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    base_comm: &Commitment<E>,
+    base_poly: &[E::Scalar],
+    corrections: &[(usize, E::Scalar)],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+  ) -> Result<InnerProductArgument<E>, NovaError> {
+    let correction = Self::sparse_correction_eval(corrections, point)?;
+    Self::prove(
+      ck,
+      pk,
+      transcript,
+      base_comm,
+      base_poly,
+      point,
+      &(*eval - correction),
+    )
+  }
+
+  /// Verifies the evaluation `eval` of the committed polynomial `base_comm` corrected by
+  /// `corrections`, as produced by `prove_with_sparse_correction`
+  pub fn verify_with_sparse_correction(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    base_comm: &Commitment<E>,
+    corrections: &[(usize, E::Scalar)],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+  ) -> Result<(), NovaError> {
+    let correction = Self::sparse_correction_eval(corrections, point)?;
+    Self::verify(vk, transcript, base_comm, point, &(*eval - correction), arg)
+  }
+
+  // evaluates a sparse correction at `point`, where the `i`-th evaluation over the hypercube
+  // is weighted by `eq(point, i)`, and the first coordinate of `point` is the most significant
+  // bit of `i`
+  fn sparse_correction_eval(
+    corrections: &[(usize, E::Scalar)],
+    point: &[E::Scalar],
+  ) -> Result<E::Scalar, NovaError> {
+    let m = point.len();
+    if m < usize::BITS as usize && corrections.iter().any(|(i, _)| *i >> m != 0) {
+      return Err(NovaError::InvalidIndex);
+    }
+
+    Ok(
+      corrections
+        .iter()
+        .map(|(i, delta)| eq_at_index(point, *i) * delta)
+        .sum(),
+    )
+  }
+
+  /// Proves an evaluation like `prove` for a commitment tagged with its basis, which must be
+  /// `Basis::Evaluations`; returns `NovaError::BasisMismatch` otherwise
+  pub fn prove_tagged(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &TaggedCommitment<E>,
+    poly: &[E::Scalar],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+  ) -> Result<InnerProductArgument<E>, NovaError> {
+    check_num_vars(point, pk.max_num_vars)?;
+
+    // the basis is checked before the evaluations of `eq` are computed, in `prove_owned`
+    let u = InnerProductInstance::new_eq(&comm.comm, point, eval);
+    u.check_basis(comm)?;
+    let w = InnerProductWitness::new(poly);
+
+    InnerProductArgument::prove_owned(ck, pk, u, w, transcript)
+  }
+
+  /// Verifies an evaluation argument produced by `prove_tagged`
+  pub fn verify_tagged(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm: &TaggedCommitment<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+  ) -> Result<(), NovaError> {
+    let n = Self::check_claim(vk, &comm.comm, point, eval, arg)?;
+    let u = InnerProductInstance::new_eq(&comm.comm, point, eval);
+    u.check_basis(comm)?;
+
+    arg.verify(vk, n, &u, transcript)
+  }
+
+  /// Proves an evaluation like `prove_univariate` for a commitment tagged with its basis, which
+  /// must be `Basis::Coefficients`; returns `NovaError::BasisMismatch` otherwise
+  pub fn prove_univariate_tagged(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &TaggedCommitment<E>,
+    coeffs: &[E::Scalar],
+    zeta: &E::Scalar,
+    eval: &E::Scalar,
+  ) -> Result<InnerProductArgument<E>, NovaError> {
+    if !coeffs.len().is_power_of_two() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let u = InnerProductInstance::new(&comm.comm, &powers(zeta, coeffs.len()), eval)
+      .in_basis(Basis::Coefficients);
+    u.check_basis(comm)?;
+    let w = InnerProductWitness::new(coeffs);
+
+    InnerProductArgument::prove(ck, &pk.ck_s, &u, &w, transcript)
+  }
+
+  /// Verifies an evaluation argument produced by `prove_univariate_tagged`
+  pub fn verify_univariate_tagged(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm: &TaggedCommitment<E>,
+    n: usize,
+    zeta: &E::Scalar,
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+  ) -> Result<(), NovaError> {
+    if !n.is_power_of_two() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let u =
+      InnerProductInstance::new(&comm.comm, &powers(zeta, n), eval).in_basis(Basis::Coefficients);
+    u.check_basis(comm)?;
+
+    arg.verify(vk, n, &u, transcript)
+  }
+
+  /// Proves that the multilinear polynomials `f` and `g`, committed as `comm_f` and `comm_g`,
+  /// are equal. The prover opens `f - g` at a point sampled from the transcript and shows that
+  /// the evaluation is zero. By the Schwartz-Zippel lemma, two distinct multilinear polynomials
+  /// in `m` variables agree at a random point with probability at most `m / |F|`.
+  pub fn prove_poly_equal(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm_f: &Commitment<E>,
+    comm_g: &Commitment<E>,
+    f: &[E::Scalar],
+    g: &[E::Scalar],
+  ) -> Result<InnerProductArgument<E>, NovaError> {
+    if f.len() != g.len() || !f.len().is_power_of_two() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let point = Self::poly_equal_point(transcript, comm_f, comm_g, f.len().ilog2() as usize)?;
+    let comm = *comm_f - *comm_g;
+    let diff = f
+      .par_iter()
+      .zip(g.par_iter())
+      .map(|(f_i, g_i)| *f_i - g_i)
+      .collect::<Vec<E::Scalar>>();
+
+    <Self as EvaluationEngineTrait<E>>::prove(
+      ck,
+      pk,
+      transcript,
+      &comm,
+      &diff,
+      &point,
+      &E::Scalar::ZERO,
+    )
+  }
+
+  /// Verifies that the multilinear polynomials in `num_vars` variables committed as `comm_f`
+  /// and `comm_g` are equal, as shown by `prove_poly_equal`
+  pub fn verify_poly_equal(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm_f: &Commitment<E>,
+    comm_g: &Commitment<E>,
+    num_vars: usize,
+    arg: &InnerProductArgument<E>,
+  ) -> Result<(), NovaError> {
+    let point = Self::poly_equal_point(transcript, comm_f, comm_g, num_vars)?;
+    let comm = *comm_f - *comm_g;
+
+    <Self as EvaluationEngineTrait<E>>::verify(vk, transcript, &comm, &point, &E::Scalar::ZERO, arg)
+  }
+
+  // samples the point at which `f - g` is opened, after binding both commitments
+  fn poly_equal_point(
+    transcript: &mut E::TE,
+    comm_f: &Commitment<E>,
+    comm_g: &Commitment<E>,
+    num_vars: usize,
+  ) -> Result<Vec<E::Scalar>, NovaError> {
+    transcript.absorb(b"f", comm_f);
+    transcript.absorb(b"g", comm_g);
+    (0..num_vars)
+      .map(|_| transcript.squeeze(b"t"))
+      .collect::<Result<Vec<E::Scalar>, NovaError>>()
+  }
+
+  /// Proves the evaluations of a batch of multilinear polynomials, committed in `comms`, which
+  /// may have different numbers of variables, with a single argument. A polynomial in `k`
+  /// variables, for `k` at most `m = point.len()`, is opened at the last `k` coordinates of
+  /// `point`, so `evals[i]` is its evaluation there. Its zero-extension to `m` variables has
+  /// the same commitment, and its evaluation at `point` is `evals[i]` times the product of
+  /// `1 - point_j` over the first `m - k` coordinates. The argument opens a random linear
+  /// combination of the zero-extensions at `point`.
+  pub fn prove_batch(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comms: &[Commitment<E>],
+    polys: &[&[E::Scalar]],
+    point: &[E::Scalar],
+    evals: &[E::Scalar],
+  ) -> Result<InnerProductArgument<E>, NovaError> {
+    check_num_vars(point, pk.max_num_vars)?;
+
+    let num_vars = polys
+      .iter()
+      .map(|poly| {
+        if poly.len().is_power_of_two() {
+          Ok(poly.len().ilog2() as usize)
+        } else {
+          Err(NovaError::InvalidInputLength)
+        }
+      })
+      .collect::<Result<Vec<usize>, NovaError>>()?;
+    let (weights, comm, eval) = Self::batch_claim(transcript, comms, &num_vars, point, evals)?;
+
+    let mut poly = vec![E::Scalar::ZERO; 1 << point.len()];
+    for (p, w) in polys.iter().zip(weights.iter()) {
+      poly
+        .par_iter_mut()
+        .zip(p.par_iter())
+        .for_each(|(acc, p_i)| *acc += *p_i * w);
+    }
+
+    <Self as EvaluationEngineTrait<E>>::prove(ck, pk, transcript, &comm, &poly, point, &eval)
+  }
+
+  /// Verifies the evaluations of a batch of polynomials with `num_vars[i]` variables, committed
+  /// in `comms`, as produced by `prove_batch`
+  pub fn verify_batch(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comms: &[Commitment<E>],
+    num_vars: &[usize],
+    point: &[E::Scalar],
+    evals: &[E::Scalar],
+    arg: &InnerProductArgument<E>,
+  ) -> Result<(), NovaError> {
+    vk.check_point(point)?;
+
+    let (_, comm, eval) = Self::batch_claim(transcript, comms, num_vars, point, evals)?;
+
+    <Self as EvaluationEngineTrait<E>>::verify(vk, transcript, &comm, point, &eval, arg)
+  }
+
+  /// Proves that the linear combination `h = sum_i coeffs[i] * f_i` of the polynomials `f_i`
+  /// in `polys`, each given with its commitment, evaluates to `eval` at `point`. Unlike
+  /// `prove_batch`, whose weights are squeezed from the transcript, the coefficients are chosen
+  /// by the caller, e.g., public constants or challenges of an outer protocol. Only the
+  /// combination of the commitments is opened. The polynomials must have `point.len()`
+  /// variables.
+  pub fn prove_linear_combination(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    polys: &[(Commitment<E>, &[E::Scalar])],
+    coeffs: &[E::Scalar],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+  ) -> Result<InnerProductArgument<E>, NovaError> {
+    let n = 1 << point.len();
+    if polys.iter().any(|(_, poly)| poly.len() != n) {
+      return Err(NovaError::InvalidInputLength);
+    }
+    let comms = polys.iter().map(|(comm, _)| *comm).collect::<Vec<_>>();
+    let comm = Self::linear_combination_claim(transcript, &comms, coeffs)?;
+
+    let mut poly = vec![E::Scalar::ZERO; n];
+    for ((_, p), c) in polys.iter().zip(coeffs.iter()) {
+      poly
+        .par_iter_mut()
+        .zip(p.par_iter())
+        .for_each(|(acc, p_i)| *acc += *p_i * c);
+    }
+
+    <Self as EvaluationEngineTrait<E>>::prove(ck, pk, transcript, &comm, &poly, point, eval)
+  }
+
+  /// Verifies an argument produced by `prove_linear_combination`, which checks that the linear
+  /// combination with coefficients `coeffs` of the polynomials committed in `comms` evaluates
+  /// to `eval` at `point`
+  pub fn verify_linear_combination(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comms: &[Commitment<E>],
+    coeffs: &[E::Scalar],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+  ) -> Result<(), NovaError> {
+    let comm = Self::linear_combination_claim(transcript, comms, coeffs)?;
+
+    <Self as EvaluationEngineTrait<E>>::verify(vk, transcript, &comm, point, eval, arg)
+  }
+
+  // binds the commitments and the coefficients of a linear combination, and returns the
+  // commitment to the combination
+  fn linear_combination_claim(
+    transcript: &mut E::TE,
+    comms: &[Commitment<E>],
+    coeffs: &[E::Scalar],
+  ) -> Result<Commitment<E>, NovaError> {
+    if comms.is_empty() || comms.len() != coeffs.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    transcript.absorb(b"c", &comms);
+    transcript.absorb(b"a", &coeffs);
+
+    Ok(
+      comms
+        .iter()
+        .zip(coeffs.iter())
+        .fold(Commitment::<E>::default(), |acc, (comm, c)| {
+          acc + *comm * *c
+        }),
+    )
+  }
+
+  // binds the claims of a batch and combines them with random weights into a claim about the
+  // zero-extensions of the polynomials at `point`; returns the weights, the combined
+  // commitment, and the combined evaluation
+  fn batch_claim(
+    transcript: &mut E::TE,
+    comms: &[Commitment<E>],
+    num_vars: &[usize],
+    point: &[E::Scalar],
+    evals: &[E::Scalar],
+  ) -> Result<(Vec<E::Scalar>, Commitment<E>, E::Scalar), NovaError> {
+    if comms.is_empty()
+      || comms.len() != num_vars.len()
+      || comms.len() != evals.len()
+      || num_vars.iter().any(|k| *k > point.len())
+    {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let num_vars_repr = num_vars
+      .iter()
+      .map(|k| E::Scalar::from(*k as u64))
+      .collect::<Vec<E::Scalar>>();
+    transcript.absorb(b"c", &comms);
+    transcript.absorb(b"k", &num_vars_repr.as_slice());
+    transcript.absorb(b"e", &evals);
+    let rho = transcript.squeeze(b"rho")?;
+    let weights = powers(&rho, comms.len());
+
+    let comm = comms
+      .iter()
+      .zip(weights.iter())
+      .fold(Commitment::<E>::default(), |acc, (comm, w)| {
+        acc + *comm * *w
+      });
+
+    // the evaluation of a zero-extension is scaled by the `eq` factor of the extra variables
+    let eval = num_vars
+      .iter()
+      .zip(evals.iter().zip(weights.iter()))
+      .map(|(k, (eval, w))| {
+        let scale = point[..point.len() - k]
+          .iter()
+          .fold(E::Scalar::ONE, |acc, p| acc * (E::Scalar::ONE - p));
+        *eval * scale * w
+      })
+      .sum();
+
+    Ok((weights, comm, eval))
+  }
+
+  /// Proves the evaluations `evals[i]` of the polynomial `poly`, committed in `comm`, at each of
+  /// the points `points[i]`, with a single argument. The `eq` vectors of the points are
+  /// combined with the powers of a challenge `rho` into one vector `b`, and the argument shows
+  /// that `<poly, b>` is the same combination of the evaluations. Points may repeat: the
+  /// combination is then still a polynomial in `rho` of degree less than `points.len()`, which
+  /// vanishes at `rho` with negligible probability for inconsistent evaluations.
+  pub fn prove_multi_point(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    poly: &[E::Scalar],
+    points: &[Vec<E::Scalar>],
+    evals: &[E::Scalar],
+  ) -> Result<InnerProductArgument<E>, NovaError> {
+    for point in points {
+      check_num_vars(point, pk.max_num_vars)?;
+    }
+
+    let (b_vec, eval) = Self::multi_point_claim(transcript, comm, points, evals)?;
+    if poly.len() != b_vec.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let u = InnerProductInstance::new(comm, &b_vec, &eval);
+    let w = InnerProductWitness::new(poly);
+    InnerProductArgument::prove(ck, &pk.ck_s, &u, &w, transcript)
+  }
+
+  /// Verifies the evaluations of a polynomial at several points, as produced by
+  /// `prove_multi_point`
+  pub fn verify_multi_point(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    points: &[Vec<E::Scalar>],
+    evals: &[E::Scalar],
+    arg: &InnerProductArgument<E>,
+  ) -> Result<(), NovaError> {
+    if points.len() != evals.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+    for (point, eval) in points.iter().zip(evals.iter()) {
+      Self::check_claim(vk, comm, point, eval, arg)?;
+    }
+
+    let (b_vec, eval) = Self::multi_point_claim(transcript, comm, points, evals)?;
+    let u = InnerProductInstance::new(comm, &b_vec, &eval);
+    arg.verify(vk, b_vec.len(), &u, transcript)
+  }
+
+  // binds the claims at several points and combines them with the powers of a random `rho`;
+  // returns the combination of the `eq` vectors of the points, and that of the evaluations
+  fn multi_point_claim(
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    points: &[Vec<E::Scalar>],
+    evals: &[E::Scalar],
+  ) -> Result<(Vec<E::Scalar>, E::Scalar), NovaError> {
+    if points.is_empty()
+      || points.len() != evals.len()
+      || points.iter().any(|p| p.len() != points[0].len())
+    {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    transcript.absorb(b"c", comm);
+    for point in points {
+      transcript.absorb(b"p", &point.as_slice());
+    }
+    transcript.absorb(b"e", &evals);
+    let rho = transcript.squeeze(b"rho")?;
+    let weights = powers(&rho, points.len());
+
+    let mut b_vec = vec![E::Scalar::ZERO; 1 << points[0].len()];
+    for (point, w) in points.iter().zip(weights.iter()) {
+      let eq = EqPolynomial::evals_from_points(point);
+      b_vec
+        .par_iter_mut()
+        .zip(eq.par_iter())
+        .for_each(|(acc, eq_i)| *acc += *eq_i * w);
+    }
+    let eval = inner_product(evals, &weights);
+
+    Ok((b_vec, eval))
+  }
+
+  /// Proves that committed vectors satisfy `c = a ∘ b`, the entrywise product, for vectors of
+  /// a length `n` that is a power of two. For a random `tau`, the check is reduced to the random
+  /// inner product `sum_x eq(tau, x) (a(x) b(x) - c(x)) = 0` over the boolean hypercube, which is
+  /// zero for all `tau` only if `c = a ∘ b`. A sum-check reduces the inner product to the
+  /// evaluations of `a`, `b`, `c` at a random point, which are opened with a single argument.
+  pub fn prove_hadamard(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm_a: &Commitment<E>,
+    comm_b: &Commitment<E>,
+    comm_c: &Commitment<E>,
+    a: &[E::Scalar],
+    b: &[E::Scalar],
+    c: &[E::Scalar],
+  ) -> Result<HadamardArgument<E>, NovaError> {
+    if b.len() != a.len() || c.len() != a.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+    let comms = [*comm_a, *comm_b, *comm_c];
+    let tau = Self::hadamard_challenge(transcript, &comms, a.len())?;
+
+    let comb_func = |tau: &E::Scalar, a: &E::Scalar, b: &E::Scalar, c: &E::Scalar| -> E::Scalar {
+      *tau * (*a * *b - *c)
+    };
+    let (sc_proof, r, claims) = SumcheckProof::prove_cubic_with_additive_term(
+      &E::Scalar::ZERO,
+      a.len().ilog2() as usize,
+      &mut MultilinearPolynomial::new(tau.evals()),
+      &mut MultilinearPolynomial::new(a.to_vec()),
+      &mut MultilinearPolynomial::new(b.to_vec()),
+      &mut MultilinearPolynomial::new(c.to_vec()),
+      comb_func,
+      transcript,
+    )?;
+
+    let evals = [claims[1], claims[2], claims[3]];
+    let arg = Self::prove_batch(ck, pk, transcript, &comms, &[a, b, c], &r, &evals)?;
+
+    Ok(HadamardArgument {
+      sc_proof,
+      evals,
+      arg,
+    })
+  }
+
+  /// Verifies that committed vectors of length `n` satisfy `c = a ∘ b`, as proven by
+  /// `prove_hadamard`
+  pub fn verify_hadamard(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm_a: &Commitment<E>,
+    comm_b: &Commitment<E>,
+    comm_c: &Commitment<E>,
+    n: usize,
+    arg: &HadamardArgument<E>,
+  ) -> Result<(), NovaError> {
+    let comms = [*comm_a, *comm_b, *comm_c];
+    let tau = Self::hadamard_challenge(transcript, &comms, n)?;
+    let num_vars = n.ilog2() as usize;
+
+    let (claim, r) = arg
+      .sc_proof
+      .verify(E::Scalar::ZERO, num_vars, 3, transcript)?;
+    let [eval_a, eval_b, eval_c] = arg.evals;
+    if claim != tau.evaluate(&r) * (eval_a * eval_b - eval_c) {
+      return Err(NovaError::InvalidSumcheckProof);
+    }
+
+    Self::verify_batch(
+      vk,
+      transcript,
+      &comms,
+      &[num_vars; 3],
+      &r,
+      &arg.evals,
+      &arg.arg,
+    )
+  }
+
+  // binds the commitments of a Hadamard relation over vectors of length `n`, and returns the
+  // `eq` polynomial at the challenge `tau`
+  fn hadamard_challenge(
+    transcript: &mut E::TE,
+    comms: &[Commitment<E>],
+    n: usize,
+  ) -> Result<EqPolynomial<E::Scalar>, NovaError> {
+    if !n.is_power_of_two() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    transcript.absorb(b"h", &comms);
+    (0..n.ilog2())
+      .map(|_| transcript.squeeze(b"t"))
+      .collect::<Result<EqPolynomial<_>, NovaError>>()
+  }
+
+  /// Returns the digest of a commitment that is checked by `verify_with_commitment_digest`, so
+  /// that a client can hold only the digest of a commitment that is published separately
+  pub fn commitment_digest(comm: &Commitment<E>) -> [u8; 32] {
+    Sha3_256::digest(comm.to_transcript_bytes()).into()
+  }
+
+  /// Verifies an evaluation argument like `verify`, after checking that the supplied
+  /// commitment matches the digest `comm_digest` held by the client. This binds the proof to
+  /// the expected commitment: returns `NovaError::InvalidCommitmentDigest` if they differ.
+  pub fn verify_with_commitment_digest(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm_digest: &[u8; 32],
+    comm: &Commitment<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+  ) -> Result<(), NovaError> {
+    if Self::commitment_digest(comm) != *comm_digest {
+      return Err(NovaError::InvalidCommitmentDigest);
+    }
+
+    <Self as EvaluationEngineTrait<E>>::verify(vk, transcript, comm, point, eval, arg)
+  }
+
+  /// Returns the root of a Merkle tree over the evaluations `poly`, which commits to the
+  /// polynomial with a hash function, for a verifier that does not use group elements
+  pub fn merkle_root(poly: &[E::Scalar]) -> Result<[u8; 32], NovaError> {
+    Ok(MerkleTree::new(poly)?.root())
+  }
+
+  /// Proves an evaluation like `prove` for a polynomial that is committed both as `comm` and
+  /// with the Merkle root `root` (see `merkle_root`), and shows that the two commitments are
+  /// consistent. After binding both commitments, `num_queries` distinct indices are squeezed
+  /// from the transcript, and the prover opens the evaluation at each index `i` with a Merkle
+  /// path and with an IPA at the corresponding vertex of the hypercube, where the `eq`
+  /// polynomial is the `i`-th unit vector.
+  ///
+  /// The queries are spot checks: if the two commitments differ in a fraction `d` of the
+  /// positions, verification fails with probability at least `1 - (1 - d)^num_queries`, so
+  /// that about `λ / d` queries bring the soundness error below `2^-λ`. Returns
+  /// `NovaError::InvalidInputLength` unless `num_queries` is between 1 and `poly.len()`.
+  pub fn prove_hybrid(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    root: &[u8; 32],
+    poly: &[E::Scalar],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    num_queries: usize,
+  ) -> Result<HybridEvaluationArgument<E>, NovaError> {
+    if poly.len() != 1 << point.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+    let tree = MerkleTree::new(poly)?;
+    if tree.root() != *root {
+      return Err(NovaError::InvalidMerklePath);
+    }
+
+    let indices = Self::hybrid_queries(transcript, comm, root, point.len(), num_queries)?;
+    let mut query_evals = Vec::with_capacity(num_queries);
+    let mut query_paths = Vec::with_capacity(num_queries);
+    let mut query_args = Vec::with_capacity(num_queries);
+    for index in indices {
+      query_args.push(<Self as EvaluationEngineTrait<E>>::prove(
+        ck,
+        pk,
+        transcript,
+        comm,
+        poly,
+        &Self::vertex(point.len(), index),
+        &poly[index],
+      )?);
+      query_evals.push(poly[index]);
+      query_paths.push(tree.path(index));
+    }
+    let arg =
+      <Self as EvaluationEngineTrait<E>>::prove(ck, pk, transcript, comm, poly, point, eval)?;
+
+    Ok(HybridEvaluationArgument {
+      arg,
+      query_evals,
+      query_paths,
+      query_args,
+    })
+  }
+
+  /// Verifies an argument produced by `prove_hybrid` with `num_queries` queries
+  pub fn verify_hybrid(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    root: &[u8; 32],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &HybridEvaluationArgument<E>,
+    num_queries: usize,
+  ) -> Result<(), NovaError> {
+    if arg.query_evals.len() != num_queries
+      || arg.query_paths.len() != num_queries
+      || arg.query_args.len() != num_queries
+    {
+      return Err(NovaError::ProofLengthMismatch);
+    }
+
+    let indices = Self::hybrid_queries(transcript, comm, root, point.len(), num_queries)?;
+    for (((index, query_eval), query_path), query_arg) in indices
+      .into_iter()
+      .zip(arg.query_evals.iter())
+      .zip(arg.query_paths.iter())
+      .zip(arg.query_args.iter())
+    {
+      query_path.verify(root, 1 << point.len(), index, query_eval)?;
+      <Self as EvaluationEngineTrait<E>>::verify(
+        vk,
+        transcript,
+        comm,
+        &Self::vertex(point.len(), index),
+        query_eval,
+        query_arg,
+      )?;
+    }
+    <Self as EvaluationEngineTrait<E>>::verify(vk, transcript, comm, point, eval, &arg.arg)
+  }
+
+  /// Proves an evaluation like `prove`, and additionally opens the entries of `poly` at the
+  /// positions `known`, which the verifier knows, such as public inputs embedded in a witness.
+  /// The known entries are combined with random weights squeezed after they are bound, and
+  /// their combination is opened with an auxiliary inner product argument against the
+  /// correspondingly weighted unit vectors. Returns `NovaError::InvalidIndex` if a position is
+  /// out of range.
+  pub fn prove_with_known_coeffs(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    poly: &[E::Scalar],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    known: &[usize],
+  ) -> Result<KnownCoeffsArgument<E>, NovaError> {
+    let known = known
+      .iter()
+      .map(|i| {
+        poly
+          .get(*i)
+          .map(|v| (*i, *v))
+          .ok_or(NovaError::InvalidIndex)
+      })
+      .collect::<Result<Vec<_>, NovaError>>()?;
+
+    let arg =
+      <Self as EvaluationEngineTrait<E>>::prove(ck, pk, transcript, comm, poly, point, eval)?;
+
+    let (b_vec, c) = Self::known_coeffs_claim(transcript, poly.len(), &known)?;
+    let u = InnerProductInstance::new(comm, &b_vec, &c);
+    let w = InnerProductWitness::new(poly);
+    let known_arg = InnerProductArgument::prove_owned(ck, pk, u, w, transcript)?;
+
+    Ok(KnownCoeffsArgument { arg, known_arg })
+  }
+
+  /// Verifies an argument produced by `prove_with_known_coeffs`, which checks that the
+  /// committed polynomial has the entry `v` at position `i` for each `(i, v)` in `known`
+  pub fn verify_with_known_coeffs(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    known: &[(usize, E::Scalar)],
+    arg: &KnownCoeffsArgument<E>,
+  ) -> Result<(), NovaError> {
+    <Self as EvaluationEngineTrait<E>>::verify(vk, transcript, comm, point, eval, &arg.arg)?;
+
+    let n = 1 << point.len();
+    let (b_vec, c) = Self::known_coeffs_claim(transcript, n, known)?;
+    let u = InnerProductInstance::new(comm, &b_vec, &c);
+    arg.known_arg.verify(vk, n, &u, transcript)
+  }
+
+  // binds the known entries of a vector of length `n`, and combines them with random weights
+  // into the claim `<poly, b_vec> = c`
+  fn known_coeffs_claim(
+    transcript: &mut E::TE,
+    n: usize,
+    known: &[(usize, E::Scalar)],
+  ) -> Result<(Vec<E::Scalar>, E::Scalar), NovaError> {
+    if known.iter().any(|(i, _)| *i >= n) {
+      return Err(NovaError::InvalidIndex);
+    }
+
+    let (indices, values): (Vec<E::Scalar>, Vec<E::Scalar>) = known
+      .iter()
+      .map(|(i, v)| (E::Scalar::from(*i as u64), *v))
+      .unzip();
+    transcript.absorb(b"k", &indices.as_slice());
+    transcript.absorb(b"v", &values.as_slice());
+    let rho = transcript.squeeze(b"rho")?;
+    let weights = powers(&rho, known.len());
+
+    let mut b_vec = vec![E::Scalar::ZERO; n];
+    for ((i, _), w) in known.iter().zip(weights.iter()) {
+      b_vec[*i] += w;
+    }
+    let c = inner_product(&values, &weights);
+
+    Ok((b_vec, c))
+  }
+
+  /// Verifies an evaluation argument like `verify` for a commitment that is first shown to be
+  /// a member of an accumulator of commitments, given by its Merkle root (see
+  /// [`CommitmentTree`]). Returns `NovaError::InvalidMerklePath` if `path` does not lead from
+  /// `comm` to `root`, without checking the argument.
+  pub fn verify_with_membership(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    root: &[u8; 32],
+    path: &MembershipPath,
+    comm: &Commitment<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+  ) -> Result<(), NovaError> {
+    path.verify::<E>(root, comm)?;
+    <Self as EvaluationEngineTrait<E>>::verify(vk, transcript, comm, point, eval, arg)
+  }
+
+  // samples `num_queries` distinct indices at which the two commitments are compared, by
+  // squeezing indices from the transcript and skipping the ones already sampled
+  fn hybrid_queries(
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    root: &[u8; 32],
+    num_vars: usize,
+    num_queries: usize,
+  ) -> Result<Vec<usize>, NovaError> {
+    if num_vars >= usize::BITS as usize || num_vars > E::Scalar::CAPACITY as usize {
+      return Err(NovaError::InvalidInputLength);
+    }
+    if num_queries == 0 || num_queries > 1 << num_vars {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    transcript.absorb(b"C", comm);
+    transcript.absorb(b"M", &MerkleRoot(*root));
+    transcript.absorb(b"q", &E::Scalar::from(num_queries as u64));
+    let mut sampled = BTreeSet::new();
+    let mut indices = Vec::with_capacity(num_queries);
+    while indices.len() < num_queries {
+      let bits = transcript.squeeze(b"i")?.to_le_bits();
+      let index = (0..num_vars).fold(0, |acc, j| acc | (usize::from(bits[j]) << j));
+      if sampled.insert(index) {
+        indices.push(index);
+      }
+    }
+
+    Ok(indices)
+  }
+
+  /// Proves the evaluation of the multilinear polynomial `poly` committed as `comm` at a point
+  /// that is squeezed from the transcript, after binding the commitment, rather than supplied
+  /// by the caller. Returns the point, the evaluation and the argument; the verifier derives
+  /// the same point with `verify_at_challenge_point`.
+  pub fn prove_at_challenge_point(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    poly: &[E::Scalar],
+    num_vars: usize,
+  ) -> Result<(Vec<E::Scalar>, E::Scalar, InnerProductArgument<E>), NovaError> {
+    if poly.len() != 1 << num_vars {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let point = Self::challenge_point(transcript, comm, num_vars)?;
+    let eval = MultilinearPolynomial::evaluate_with(poly, &point);
+    let arg =
+      <Self as EvaluationEngineTrait<E>>::prove(ck, pk, transcript, comm, poly, &point, &eval)?;
+
+    Ok((point, eval, arg))
+  }
+
+  /// Verifies an argument produced by `prove_at_challenge_point` for a polynomial in
+  /// `num_vars` variables, and returns the point at which it was opened
+  pub fn verify_at_challenge_point(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    num_vars: usize,
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+  ) -> Result<Vec<E::Scalar>, NovaError> {
+    let point = Self::challenge_point(transcript, comm, num_vars)?;
+    <Self as EvaluationEngineTrait<E>>::verify(vk, transcript, comm, &point, eval, arg)?;
+
+    Ok(point)
+  }
+
+  // samples the point at which a polynomial is opened, after binding its commitment
+  fn challenge_point(
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    num_vars: usize,
+  ) -> Result<Vec<E::Scalar>, NovaError> {
+    transcript.absorb(b"C", comm);
+    (0..num_vars)
+      .map(|_| transcript.squeeze(b"p"))
+      .collect::<Result<Vec<E::Scalar>, NovaError>>()
+  }
+
+  /// Derives the Fiat-Shamir challenges that `verify` would use under `vk` for an evaluation
+  /// argument, advancing the transcript in the same way
+  pub fn challenges(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+  ) -> Result<InnerProductChallenges<E>, NovaError> {
+    let n = Self::check_shape(vk, point, arg)?;
+    let u = InnerProductInstance::new_eq(comm, point, eval);
+
+    arg.challenges(vk, n, &u, transcript)
+  }
+
+  /// Verifies an evaluation argument using the supplied challenges instead of deriving them
+  /// with Fiat-Shamir, e.g. because an outer protocol has already derived them.
+  ///
+  /// The caller is responsible for the soundness of the challenges: they must be sampled
+  /// uniformly at random after the commitment, point, evaluation and each round's `L` and `R`
+  /// in `arg` are fixed, exactly as [`EvaluationEngine::challenges`] does.
+  pub fn verify_with_challenges(
+    vk: &VerifierKey<E>,
+    challenges: &InnerProductChallenges<E>,
+    comm: &Commitment<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+  ) -> Result<(), NovaError> {
+    let n = Self::check_claim(vk, comm, point, eval, arg)?;
+    let u = InnerProductInstance::new_eq(comm, point, eval);
+
+    arg
+      .verify_deferred_with_challenges(&vk.ck_v, &vk.ck_s, n, &u, challenges)?
+      .check()
+  }
+
+  // checks that `arg` has one round per variable of `point`, which bounds the length
+  // `2^point.len()` of the polynomial that is returned, and that `vk` accepts `point`. This
+  // runs before anything is absorbed in the transcript.
+  fn check_shape(
+    vk: &VerifierKey<E>,
+    point: &[E::Scalar],
+    arg: &InnerProductArgument<E>,
+  ) -> Result<usize, NovaError> {
+    arg.check_num_rounds(point.len())?;
+    vk.check_point(point)?;
+    Ok(1 << point.len())
+  }
+
+  // the checks with which each verifier of the claim that `comm` evaluates to `eval` at
+  // `point` starts: those of `check_shape`, and that the claim is not trivially false
+  fn check_claim(
+    vk: &VerifierKey<E>,
+    comm: &Commitment<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+  ) -> Result<usize, NovaError> {
+    let n = Self::check_shape(vk, point, arg)?;
+
+    // the identity commits to the zero polynomial, which evaluates to zero everywhere; the
+    // comparisons are in constant time, for `verify_constant_time`
+    if bool::from(comm.is_identity() & !eval.is_zero()) {
+      return Err(NovaError::TrivialCommitment);
+    }
+
+    Ok(n)
+  }
+}
+
+impl<E> EvaluationEngine<E>
+where
+  E: Engine<CE = CommitmentEngine<E>>,
+  E::GE: DlogGroup,
+{
+  /// Proves an evaluation like `prove` for a sparse polynomial, given by its nonzero entries:
+  /// `entries` holds `(i, v)` for each position `i` where the polynomial takes the value `v`.
+  /// While at most one in eight entries of the folded polynomial is nonzero, the rounds only
+  /// commit to and fold the nonzero entries, so that for a very sparse polynomial the first
+  /// rounds, which are the most expensive, cost time in the number of nonzero entries rather
+  /// than in the length of the polynomial. The `eq` polynomial and the generators are folded
+  /// as usual. The argument is the same as the one output by `prove` for the dense polynomial.
+  /// Returns `NovaError::InvalidIndex` if a position is out of range or appears twice.
+  pub fn prove_sparse(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    entries: &[(usize, E::Scalar)],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+  ) -> Result<InnerProductArgument<E>, NovaError> {
+    check_num_vars(point, pk.max_num_vars)?;
+
+    let w = SparseInnerProductWitness::new(entries, 1 << point.len())?;
+    let u = InnerProductInstance::new_eq(comm, point, eval);
+
+    InnerProductArgument::prove_sparse(ck, &pk.ck_s, u, w, transcript)
+  }
+
+  /// Proves an evaluation like `prove`, after absorbing the digest of `ck` in the transcript,
+  /// so that the challenges of the argument depend on the commitment key. The digest is also
+  /// included in the argument, so that `verify_key_bound` rejects an argument produced under
+  /// another key before running the verifier.
+  pub fn prove_key_bound(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    poly: &[E::Scalar],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+  ) -> Result<KeyBoundArgument<E>, NovaError> {
+    let key_digest = ck.digest();
+    transcript.absorb(b"ck", &TranscriptDigest(key_digest));
+
+    let arg =
+      <Self as EvaluationEngineTrait<E>>::prove(ck, pk, transcript, comm, poly, point, eval)?;
+    Ok(KeyBoundArgument { key_digest, arg })
+  }
+
+  /// Verifies an argument produced by `prove_key_bound`. Returns
+  /// `NovaError::CommitmentKeyMismatch` if the argument was produced under a commitment key
+  /// other than `vk`'s.
+  pub fn verify_key_bound(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &KeyBoundArgument<E>,
+  ) -> Result<(), NovaError> {
+    let key_digest = vk.ck_v.digest();
+    if arg.key_digest != key_digest {
+      return Err(NovaError::CommitmentKeyMismatch);
+    }
+    transcript.absorb(b"ck", &TranscriptDigest(key_digest));
+
+    <Self as EvaluationEngineTrait<E>>::verify(vk, transcript, comm, point, eval, &arg.arg)
+  }
+
+  /// Proves an evaluation like `prove`, folding every vector in place: the polynomial is taken
+  /// by value and folded into its own first half each round, and the generators are copied once
+  /// and folded in place likewise. `prove` instead copies the polynomial, and in each round
+  /// copies the two halves of the generators and allocates the folded ones, so that it holds up
+  /// to `2.5 n` generators at once for a polynomial of size `n`, while this holds `n`
+  /// generators. The argument is the same as the one output by `prove`.
+  pub fn prove_in_place(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    poly: Vec<E::Scalar>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+  ) -> Result<InnerProductArgument<E>, NovaError> {
+    check_num_vars(point, pk.max_num_vars)?;
+
+    let u = InnerProductInstance::new_eq(comm, point, eval);
+
+    InnerProductArgument::prove_in_place(ck, &pk.ck_s, u, poly, transcript)
+  }
+
+  /// Proves an evaluation like `prove`, using a commitment key with a precomputed table of
+  /// multiples of the generators, which can be shared by many proofs (possibly running in
+  /// parallel). The argument is the same as the one output by `prove` with `ck.ck()`.
+  pub fn prove_prepared(
+    ck: &PreparedCommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    poly: &[E::Scalar],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+  ) -> Result<InnerProductArgument<E>, NovaError> {
+    check_num_vars(point, pk.max_num_vars)?;
+
+    let u = InnerProductInstance::new_eq(comm, point, eval);
+    let w = InnerProductWitness::new(poly);
+
+    InnerProductArgument::prove_prepared(ck, &pk.ck_s, &u, &w, transcript)
+  }
+
+  /// Proves an evaluation like `prove` for a polynomial that is not held in memory, but read
+  /// in chunks with `get_chunk(range)`, which returns the entries of the polynomial at the
+  /// positions `range`, e.g., from disk. `max_len` is a power of two that bounds the length of
+  /// the chunks. While the folded polynomial is longer than `max_len`, each round streams the
+  /// whole polynomial once, and computes the chunks of the folded polynomial it needs from the
+  /// folding challenges so far. Once the folded polynomial fits in `max_len` entries, it is
+  /// materialized and the remaining rounds run in memory. The `eq` polynomial is computed in
+  /// chunks from its closed form, while the commitment key is held in memory as usual. The
+  /// argument is the same as the one output by `prove`.
+  pub fn prove_streaming<F>(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    max_len: usize,
+    get_chunk: F,
+  ) -> Result<InnerProductArgument<E>, NovaError>
+  where
+    F: FnMut(Range<usize>) -> Vec<E::Scalar>,
+  {
+    check_num_vars(point, pk.max_num_vars)?;
+
+    let u = InnerProductInstance::new_eq(comm, point, eval);
+    InnerProductArgument::prove_streaming(ck, &pk.ck_s, &u, point, max_len, get_chunk, transcript)
+  }
+
+  /// Verifies an evaluation argument like `verify`, with memory that does not grow with the
+  /// length `n` of the polynomial beyond the verifier key. `verify` materializes the vector `s`
+  /// of `n` scalars with which the generators are folded, and checks it with one MSM of size
+  /// `n`. Here, `s` is computed in blocks of `block_len` entries from its tensor structure,
+  /// which `block_len`, a power of two, bounds along with `n / block_len`, and each block is
+  /// folded into the generator `<s, ck>` as soon as it is computed. Since the blocks are cheap
+  /// to recompute from the challenges, none is spilled to disk. The accept/reject decision is
+  /// the same as with `verify`, at the cost of `n / block_len` smaller MSMs in place of one.
+  pub fn verify_streaming(
+    vk: &VerifierKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &InnerProductArgument<E>,
+    block_len: usize,
+  ) -> Result<(), NovaError> {
+    let n = Self::check_claim(vk, comm, point, eval, arg)?;
+    let u = InnerProductInstance::new_eq(comm, point, eval);
+    let challenges = arg.challenges(vk, n, &u, transcript)?;
+    arg.verify_streaming(&vk.ck_v, &vk.ck_s, n, &u, &challenges, block_len)
+  }
+}
+
+// absorbs the commitments `L` and `R` of each round in the order `round_order`, and derives
+// the challenge of the round after them
+pub(crate) fn round_challenges<E: Engine>(
+  transcript: &mut E::TE,
+  L_vec: &[Commitment<E>],
+  R_vec: &[Commitment<E>],
+  round_order: RoundOrder,
+) -> Result<Vec<E::Scalar>, NovaError> {
+  L_vec
+    .iter()
+    .zip(R_vec.iter())
+    .map(|(L, R)| {
+      match round_order {
+        RoundOrder::LeftFirst => {
+          transcript.absorb(b"L", L);
+          transcript.absorb(b"R", R);
+        }
+        RoundOrder::RightFirst => {
+          transcript.absorb(b"R", R);
+          transcript.absorb(b"L", L);
+        }
+      }
+      transcript.squeeze(b"r")
+    })
+    .collect()
+}
+
+pub(crate) fn batch_invert<T: Field>(v: &[T]) -> Result<Vec<T>, NovaError> {
+  let mut products = vec![T::ZERO; v.len()];
+  let mut acc = T::ONE;
+
+  for i in 0..v.len() {
+    products[i] = acc;
+    acc *= v[i];
+  }
+
+  // the product is zero iff one of the entries, which are challenges, is zero
+  acc = match Option::from(acc.invert()) {
+    Some(inv) => inv,
+    None => return Err(NovaError::DegenerateChallenge),
+  };
+
+  // compute the inverse once for all entries
+  let mut inv = vec![T::ZERO; v.len()];
+  for i in (0..v.len()).rev() {
+    let tmp = acc * v[i];
+    inv[i] = products[i] * acc;
+    acc = tmp;
+  }
+
+  Ok(inv)
+}
+
+// computes the vector `s` of length `2^m` for the folding challenges `r_1, ..., r_m`, given
+// their squares and inverses, so that folding the generators `ck` with the challenges as the
+// prover does yields `<s, ck>`: `s_i` is the product over `j` of `r_j` if the `j`-th most
+// significant bit of `i` is set, and of `r_j^{-1}` otherwise
+//
+// the entries with the `p`-th least significant bit set are the entries below `2^p` times
+// `r_square` of the corresponding round, so `s` is expanded by doubling its prefix in parallel
+pub(crate) fn tensor_vector<T: Field>(r_square: &[T], r_inverse: &[T]) -> Vec<T> {
+  let m = r_square.len();
+  let n = 1 << m;
+  let mut s = vec![T::ZERO; n];
+  s[0] = r_inverse
+    .iter()
+    .fold(T::ONE, |acc, r_inverse_i| acc * r_inverse_i);
+  for p in 0..m {
+    let (lo, hi) = s[..2 << p].split_at_mut(1 << p);
+    let r_sq = r_square[(m - 1) - p];
+    hi.par_iter_mut()
+      .zip(lo.par_iter())
+      .for_each(|(s_hi, s_lo)| *s_hi = *s_lo * r_sq);
+  }
+  s
+}
+
+// keeps the `num_bits` least significant bits of `x`
+fn truncate<T: PrimeFieldBits>(x: &T, num_bits: u32) -> T {
+  x.to_le_bits()
+    .iter()
+    .take(num_bits as usize)
+    .rev()
+    .fold(T::ZERO, |acc, bit| {
+      if *bit {
+        acc.double() + T::ONE
+      } else {
+        acc.double()
+      }
+    })
+}
+
+// returns `(1, x, x^2, ..., x^{n-1})`
+fn powers<T: Field>(x: &T, n: usize) -> Vec<T> {
+  iter::successors(Some(T::ONE), |p| Some(*p * x))
+    .take(n)
+    .collect()
+}
+
+// returns `(0, 1, x, ..., x^{n-2})`, the powers of `x` for the coefficients of a shift by `x`
+fn shifted_powers<T: Field>(x: &T, n: usize) -> Vec<T> {
+  iter::once(T::ZERO).chain(powers(x, n - 1)).collect()
+}
+
+pub(crate) fn inner_product<T: Field + Send + Sync>(a: &[T], b: &[T]) -> T {
+  assert_eq!(a.len(), b.len());
+  (0..a.len())
+    .into_par_iter()
+    .map(|i| a[i] * b[i])
+    .reduce(|| T::ZERO, |x, y| x + y)
+}
+
+// folds the right half of `v` into the left half, in place, as `w_L * v_L + w_R * v_R`. The
+// prover folds the witness vector with `(r, r^{-1})` and the public vector with `(r^{-1}, r)`,
+// so that `<a', b'> = <a, b> + r^2 <a_L, b_R> + r^{-2} <a_R, b_L>`.
+pub(crate) fn fold_halves<T: Field + Send + Sync>(v: &mut Vec<T>, w_L: &T, w_R: &T) {
+  let n = v.len();
+  let (v_L, v_R) = v.split_at_mut(n / 2);
+  v_L
+    .par_iter_mut()
+    .zip(v_R.par_iter())
+    .for_each(|(v_L, v_R)| *v_L = *v_L * w_L + *v_R * w_R);
+  v.truncate(n / 2);
+}
+
+// the public vector `b` of an inner product instance, which is either stored in full, or as
+// the point whose `eq` polynomial evaluates to `b` over the hypercube. The latter is
+// logarithmic in the length of `b`, which keeps the instance small for a verifier in a circuit.
+pub(crate) enum PublicVector<S> {
+  Full(Vec<S>),
+  Eq(Vec<S>),
+}
+
+impl<S: PrimeField> PublicVector<S> {
+  fn len(&self) -> usize {
+    match self {
+      PublicVector::Full(b_vec) => b_vec.len(),
+      PublicVector::Eq(point) => 1 << point.len(),
+    }
+  }
+
+  fn to_full(&self) -> Vec<S> {
+    match self {
+      PublicVector::Full(b_vec) => b_vec.clone(),
+      PublicVector::Eq(point) => EqPolynomial::evals_from_points(point),
+    }
+  }
+
+  fn into_full(self) -> Vec<S> {
+    match self {
+      PublicVector::Full(b_vec) => b_vec,
+      PublicVector::Eq(point) => EqPolynomial::evals_from_points(&point),
+    }
+  }
+
+  // a digest of the vector and of its length. An `eq` vector is digested through its point,
+  // so the two sides of an argument must represent `b` in the same way.
+  fn digest(&self) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"ipa-instance");
+    hasher.update((self.len() as u64).to_le_bytes());
+    match self {
+      PublicVector::Eq(point) => {
+        hasher.update([0]);
+        point.iter().for_each(|p| hasher.update(p.to_repr()));
+      }
+      PublicVector::Full(b_vec) => {
+        hasher.update([1]);
+        b_vec.iter().for_each(|b| hasher.update(b.to_repr()));
+      }
+    }
+    hasher.finalize().into()
+  }
+
+  // computes `<b, s>` for the vector `s` with the tensor structure of the folding challenges
+  // `r`. For `eq`, both vectors are tensor products over the rounds, so the inner product is
+  // the product over the rounds `j` of `(1 - point_j) * r_j^{-1} + point_j * r_j`.
+  pub(crate) fn inner_product_with_tensor(&self, s: &[S], r: &[S], r_inverse: &[S]) -> S {
+    match self {
+      // a vector shorter than `s` is implicitly padded with zeros
+      PublicVector::Full(b_vec) => inner_product(b_vec, &s[..b_vec.len()]),
+      PublicVector::Eq(point) => point
+        .iter()
+        .zip(r.iter().zip(r_inverse.iter()))
+        .fold(S::ONE, |acc, (p, (r, r_inv))| {
+          acc * ((S::ONE - p) * r_inv + *p * r)
+        }),
+    }
+  }
+}
+
+/// The basis of a committed vector that represents a polynomial. The same polynomial has
+/// different vectors in each basis, so a commitment must be opened in the basis it was made in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Basis {
+  /// The evaluations of a multilinear polynomial over the boolean hypercube, which are opened
+  /// at a point with the `eq` polynomial
+  Evaluations,
+  /// The coefficients of a univariate polynomial, in order of increasing degree, which are
+  /// opened at a point with its powers
+  Coefficients,
+}
+
+/// A commitment tagged with the basis of the committed vector, so that opening it in the other
+/// basis is rejected with `NovaError::BasisMismatch` instead of producing a wrong evaluation
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct TaggedCommitment<E: Engine> {
+  comm: Commitment<E>,
+  basis: Basis,
+}
+
+impl<E: Engine> TaggedCommitment<E> {
+  /// Tags the commitment `comm` to a vector in the basis `basis`
+  pub fn new(comm: Commitment<E>, basis: Basis) -> Self {
+    TaggedCommitment { comm, basis }
+  }
+
+  /// Returns the commitment
+  pub fn comm(&self) -> &Commitment<E> {
+    &self.comm
+  }
+
+  /// Returns the basis of the committed vector
+  pub fn basis(&self) -> Basis {
+    self.basis
+  }
+}
+
+/// An inner product instance consists of a commitment to a vector `a` and another vector `b`
+/// and the claim that c = <a, b>.
+pub struct InnerProductInstance<E: Engine> {
+  pub(crate) comm_a_vec: Commitment<E>,
+  pub(crate) b_vec: PublicVector<E::Scalar>,
+  pub(crate) c: E::Scalar,
+  // the basis in which `b` opens `a`
+  basis: Basis,
+  // whether the argument adds its protocol name as a domain separator to the transcript
+  dom_sep: bool,
+  // the digest of `b` and of its length, computed once so that provers may move `b` out
+  digest: [u8; 32],
+}
+
+impl<E> InnerProductInstance<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  pub(crate) fn new(comm_a_vec: &Commitment<E>, b_vec: &[E::Scalar], c: &E::Scalar) -> Self {
+    Self::with_public_vector(comm_a_vec, PublicVector::Full(b_vec.to_vec()), c)
+  }
+
+  // an instance whose vector `b` holds the evaluations of the `eq` polynomial at `point`,
+  // which is stored in place of the `2^|point|` evaluations
+  pub(crate) fn new_eq(comm_a_vec: &Commitment<E>, point: &[E::Scalar], c: &E::Scalar) -> Self {
+    Self::with_public_vector(comm_a_vec, PublicVector::Eq(point.to_vec()), c)
+  }
+
+  // like `new_eq`, for a prover that holds the evaluations `eq` of the `eq` polynomial at
+  // `point`, which are stored in full. The digest is that of `new_eq`, so that the instance
+  // binds as the one of a verifier that only has `point`.
+  fn new_eq_with_evals(
+    comm_a_vec: &Commitment<E>,
+    point: &[E::Scalar],
+    eq: &[E::Scalar],
+    c: &E::Scalar,
+  ) -> Self {
+    InnerProductInstance {
+      b_vec: PublicVector::Full(eq.to_vec()),
+      ..Self::new_eq(comm_a_vec, point, c)
+    }
+  }
+
+  fn with_public_vector(
+    comm_a_vec: &Commitment<E>,
+    b_vec: PublicVector<E::Scalar>,
+    c: &E::Scalar,
+  ) -> Self {
+    InnerProductInstance {
+      comm_a_vec: *comm_a_vec,
+      digest: b_vec.digest(),
+      b_vec,
+      c: *c,
+      basis: Basis::Evaluations,
+      dom_sep: true,
+    }
+  }
+
+  // absorbs the digest of the vector `b` and of its length in the transcript, which the absorb
+  // of the instance with the label `U` leaves out, and returns the digest
+  fn append_to_transcript(&self, transcript: &mut E::TE) -> [u8; 32] {
+    transcript.absorb(b"b", &TranscriptDigest(self.digest));
+    self.digest
+  }
+
+  // skips the domain separator of the argument, for protocols that already domain-separate
+  fn without_dom_sep(self) -> Self {
+    InnerProductInstance {
+      dom_sep: false,
+      ..self
+    }
+  }
+
+  // sets the basis in which `b` opens `a`, which is the basis of evaluations by default
+  fn in_basis(self, basis: Basis) -> Self {
+    InnerProductInstance { basis, ..self }
+  }
+
+  // checks that the instance opens `comm` in the basis it was made in
+  fn check_basis(&self, comm: &TaggedCommitment<E>) -> Result<(), NovaError> {
+    if self.basis != comm.basis {
+      return Err(NovaError::BasisMismatch);
+    }
+    Ok(())
+  }
+}
+
+impl<E: Engine> TranscriptReprTrait<E::GE> for InnerProductInstance<E> {
+  fn to_transcript_bytes(&self) -> Vec<u8> {
+    // we do not need to include self.b_vec as in our context it is produced from the transcript
+    [
+      self.comm_a_vec.to_transcript_bytes(),
+      self.c.to_transcript_bytes(),
+    ]
+    .concat()
+  }
+}
+
+struct InnerProductWitness<E: Engine> {
+  a_vec: Vec<E::Scalar>,
+}
+
+impl<E: Engine> InnerProductWitness<E> {
+  fn new(a_vec: &[E::Scalar]) -> Self {
+    InnerProductWitness {
+      a_vec: a_vec.to_vec(),
+    }
+  }
+}
+
+// a witness vector of length `len` given by its nonzero entries, sorted by position
+struct SparseInnerProductWitness<E: Engine> {
+  entries: Vec<(usize, E::Scalar)>,
+  len: usize,
+}
+
+impl<E: Engine> SparseInnerProductWitness<E> {
+  // returns `NovaError::InvalidIndex` if a position is out of range or appears twice
+  fn new(entries: &[(usize, E::Scalar)], len: usize) -> Result<Self, NovaError> {
+    let mut entries = entries.to_vec();
+    entries.sort_by_key(|(i, _)| *i);
+    if entries.iter().any(|(i, _)| *i >= len) || entries.windows(2).any(|w| w[0].0 == w[1].0) {
+      return Err(NovaError::InvalidIndex);
+    }
+    Ok(SparseInnerProductWitness { entries, len })
+  }
+}
+
+/// An inner product argument.
+///
+/// Deserialization rejects arguments whose parts have inconsistent lengths, such as truncated
+/// arguments, with `NovaError::MalformedProof`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "", try_from = "UncheckedInnerProductArgument<E>")]
+pub struct InnerProductArgument<E: Engine> {
+  pub(crate) L_vec: Vec<Commitment<E>>,
+  pub(crate) R_vec: Vec<Commitment<E>>,
+  pub(crate) a_hat: E::Scalar,
+  // the digest of the public vector of the instance, with which a verifier that disagrees on
+  // the instance is reported with `NovaError::InstanceMismatch`
+  pub(crate) instance_digest: [u8; 32],
+}
+
+// the serialized form of an inner product argument, before its structure is validated
+#[derive(Deserialize)]
+#[serde(bound = "")]
+pub(crate) struct UncheckedInnerProductArgument<E: Engine> {
+  pub(crate) L_vec: Vec<Commitment<E>>,
+  pub(crate) R_vec: Vec<Commitment<E>>,
+  pub(crate) a_hat: E::Scalar,
+  pub(crate) instance_digest: [u8; 32],
+}
+
+impl<E: Engine> TryFrom<UncheckedInnerProductArgument<E>> for InnerProductArgument<E> {
+  type Error = NovaError;
+
+  fn try_from(arg: UncheckedInnerProductArgument<E>) -> Result<Self, Self::Error> {
+    if arg.L_vec.len() != arg.R_vec.len() {
+      return Err(NovaError::MalformedProof);
+    }
+
+    Ok(InnerProductArgument {
+      L_vec: arg.L_vec,
+      R_vec: arg.R_vec,
+      a_hat: arg.a_hat,
+      instance_digest: arg.instance_digest,
+    })
+  }
+}
+
+/// A verification of an evaluation argument that awaits the commitment, as returned by
+/// `EvaluationEngine::verify_incremental`
+pub struct PendingVerification<'a, E: Engine> {
+  vk: &'a VerifierKey<E>,
+  arg: &'a InnerProductArgument<E>,
+  point: Vec<E::Scalar>,
+  eval: E::Scalar,
+}
+
+impl<E> PendingVerification<'_, E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+  CommitmentKey<E>: CommitmentKeyExtTrait<E>,
+{
+  /// Completes the verification with the commitment `comm`, deriving the challenges from
+  /// `transcript` in the same way as `verify`
+  pub fn finalize(self, transcript: &mut E::TE, comm: &Commitment<E>) -> Result<(), NovaError> {
+    EvaluationEngine::verify_deferred(self.vk, transcript, comm, &self.point, &self.eval, self.arg)?
+      .check()
+  }
+}
+
+/// The state of the prover of an inner product argument that stopped before the final rounds,
+/// as returned by `EvaluationEngine::prove_deferred`
+pub struct InnerProductProverState<E: Engine> {
+  L_vec: Vec<Commitment<E>>,
+  R_vec: Vec<Commitment<E>>,
+  a_vec: Vec<E::Scalar>,
+  b_vec: Vec<E::Scalar>,
+  ck: CommitmentKey<E>,
+  ck_c: CommitmentKey<E>,
+  // the challenge with which `ck_c` was scaled from the generator of the prover key, and the
+  // table of multiples of that generator, if the prover has one
+  r_c: E::Scalar,
+  c_table: Option<Arc<GeneratorTable<E>>>,
+  // the digest of the public vector, which the argument carries
+  instance_digest: [u8; 32],
+}
+
+impl<E> InnerProductProverState<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+  CommitmentKey<E>: CommitmentKeyExtTrait<E>,
+{
+  // the length of the folded vectors at which the prover stops
+  const DEFERRED_LEN: usize = 4;
+
+  /// Returns the folded witness vector, of length at most 4
+  pub fn a_vec(&self) -> &[E::Scalar] {
+    &self.a_vec
+  }
+
+  /// Returns the folded public vector, of the same length as the witness vector
+  pub fn b_vec(&self) -> &[E::Scalar] {
+    &self.b_vec
+  }
+
+  // commits to `x` with `ck_c`, using the table of multiples of the generator if there is one
+  fn commit_c(&self, x: &E::Scalar) -> Commitment<E> {
+    match &self.c_table {
+      Some(table) => table.commit(&(*x * self.r_c)),
+      None => CE::<E>::commit(&self.ck_c, &[*x]),
+    }
+  }
+
+  // executes a step of the recursive inner product argument
+  fn prove_round(&mut self, transcript: &mut E::TE) -> Result<(), NovaError> {
+    let n = self.a_vec.len();
+    let (ck_L, ck_R) = self.ck.split_at(n / 2);
+    self.prove_round_with(transcript, |a_L, a_R| {
+      (CE::<E>::commit(&ck_R, a_L), CE::<E>::commit(&ck_L, a_R))
+    })
+  }
+
+  // executes a step of the argument, where `commit_halves(a_L, a_R)` commits to the left half
+  // of the witness with the right half of the generators, and vice versa
+  fn prove_round_with<F>(
+    &mut self,
+    transcript: &mut E::TE,
+    commit_halves: F,
+  ) -> Result<(), NovaError>
+  where
+    F: FnOnce(&[E::Scalar], &[E::Scalar]) -> (Commitment<E>, Commitment<E>),
+  {
+    let (a_vec, b_vec) = (&self.a_vec, &self.b_vec);
+    let n = a_vec.len();
+
+    let c_L = inner_product(&a_vec[0..n / 2], &b_vec[n / 2..n]);
+    let c_R = inner_product(&a_vec[n / 2..n], &b_vec[0..n / 2]);
+
+    #[cfg(debug_assertions)]
+    let c = inner_product(a_vec, b_vec);
+
+    let (comm_a_L, comm_a_R) = commit_halves(&a_vec[0..n / 2], &a_vec[n / 2..n]);
+    let L = comm_a_L + self.commit_c(&c_L);
+    let R = comm_a_R + self.commit_c(&c_R);
+
+    let (r, r_inverse) = self.push_round(L, R, transcript)?;
+
+    fold_halves(&mut self.a_vec, &r, &r_inverse);
+
+    #[cfg(debug_assertions)]
+    self.self_check_round(c, c_L, c_R, &r);
+
+    Ok(())
+  }
+
+  // in debug builds, checks after a round that the folded vectors have the inner product
+  // `c + r^2 * c_L + r^{-2} * c_R` that the verifier expects from `L` and `R`, where `c` is the
+  // inner product before the round, and panics with the index of the round otherwise. A bug in
+  // the arithmetic of a new curve then points at the round that broke, instead of surfacing as
+  // a proof that fails to verify.
+  #[cfg(debug_assertions)]
+  fn self_check_round(&self, c: E::Scalar, c_L: E::Scalar, c_R: E::Scalar, r: &E::Scalar) {
+    self.self_check_folded(inner_product(&self.a_vec, &self.b_vec), c, c_L, c_R, r);
+  }
+
+  // like `self_check_round`, for a round whose folded witness is not held in `a_vec`, with the
+  // inner product `c_folded` of the folded vectors computed by the caller
+  #[cfg(debug_assertions)]
+  fn self_check_folded(
+    &self,
+    c_folded: E::Scalar,
+    c: E::Scalar,
+    c_L: E::Scalar,
+    c_R: E::Scalar,
+    r: &E::Scalar,
+  ) {
+    let round = self.L_vec.len() - 1;
+    let r_square = r.square();
+    let r_inverse_square = r_square.invert().unwrap();
+    assert!(
+      c_folded == c + r_square * c_L + r_inverse_square * c_R,
+      "IPA prover self-check failed at round {round}: the folded vectors are inconsistent with L and R"
+    );
+  }
+
+  // absorbs the commitments `L` and `R` of a round, and folds the public vector and the
+  // generators with the resulting challenge `r`, which is returned along with its inverse.
+  // Folding the witness vector is left to the caller.
+  fn push_round(
+    &mut self,
+    L: Commitment<E>,
+    R: Commitment<E>,
+    transcript: &mut E::TE,
+  ) -> Result<(E::Scalar, E::Scalar), NovaError> {
+    let (r, r_inverse) = self.absorb_round(L, R, transcript)?;
+    self.ck = self.ck.fold(&r_inverse, &r);
+
+    Ok((r, r_inverse))
+  }
+
+  // like `push_round`, but leaves the generators unfolded
+  fn absorb_round(
+    &mut self,
+    L: Commitment<E>,
+    R: Commitment<E>,
+    transcript: &mut E::TE,
+  ) -> Result<(E::Scalar, E::Scalar), NovaError> {
+    transcript.absorb(b"L", &L);
+    transcript.absorb(b"R", &R);
+
+    let r = transcript.squeeze(b"r")?;
+    let r_inverse = Option::from(r.invert()).ok_or(NovaError::DegenerateChallenge)?;
+    #[cfg(test)]
+    let r_inverse = tests::fault_r_inverse(r_inverse);
+
+    fold_halves(&mut self.b_vec, &r_inverse, &r);
+
+    self.L_vec.push(L);
+    self.R_vec.push(R);
+
+    Ok((r, r_inverse))
+  }
+
+  // runs the remaining rounds and outputs the argument
+  fn finish(self, transcript: &mut E::TE) -> Result<InnerProductArgument<E>, NovaError> {
+    Ok(self.finish_with_folded_ck(transcript)?.0)
+  }
+
+  // like `finish`, but also outputs the generators folded by all the rounds, of length 1
+  fn finish_with_folded_ck(
+    mut self,
+    transcript: &mut E::TE,
+  ) -> Result<(InnerProductArgument<E>, CommitmentKey<E>), NovaError> {
+    while self.a_vec.len() > 1 {
+      self.prove_round(transcript)?;
+    }
+
+    let arg = InnerProductArgument {
+      L_vec: self.L_vec,
+      R_vec: self.R_vec,
+      a_hat: self.a_vec[0],
+      instance_digest: self.instance_digest,
+    };
+    Ok((arg, self.ck))
+  }
+}
+
+impl<E> InnerProductProverState<E>
+where
+  E: Engine<CE = CommitmentEngine<E>>,
+  E::GE: DlogGroup,
+{
+  // the prover keeps the folded witness sparse while at most one in `SPARSE_RATIO` of its
+  // entries is nonzero
+  const SPARSE_RATIO: usize = 8;
+
+  // like `prove_round`, for the witness vector whose nonzero entries are `entries`, sorted by
+  // position; returns the entries of the folded witness, which has at most as many
+  fn prove_round_sparse(
+    &mut self,
+    entries: &[(usize, E::Scalar)],
+    transcript: &mut E::TE,
+  ) -> Result<Vec<(usize, E::Scalar)>, NovaError> {
+    let n = self.b_vec.len();
+    let split = entries.partition_point(|(i, _)| *i < n / 2);
+    let (a_L, a_R) = entries.split_at(split);
+
+    // the left half is committed with the generators of the right half, and vice versa
+    let a_L_shifted = a_L.iter().map(|(i, v)| (i + n / 2, *v)).collect::<Vec<_>>();
+    let a_R_shifted = a_R.iter().map(|(i, v)| (i - n / 2, *v)).collect::<Vec<_>>();
+
+    let c_L = a_L_shifted.iter().map(|(i, v)| *v * self.b_vec[*i]).sum();
+    let c_R = a_R_shifted.iter().map(|(i, v)| *v * self.b_vec[*i]).sum();
+
+    #[cfg(debug_assertions)]
+    let c = entries.iter().map(|(i, v)| *v * self.b_vec[*i]).sum();
+
+    let L = self.ck.commit_sparse(&a_L_shifted)? + self.commit_c(&c_L);
+    let R = self.ck.commit_sparse(&a_R_shifted)? + self.commit_c(&c_R);
+
+    let (r, r_inverse) = self.push_round(L, R, transcript)?;
+
+    // fold `r * a_L + r^{-1} * a_R`, merging the entries of both halves by position
+    let mut folded = Vec::with_capacity(entries.len());
+    let (mut left, mut right) = (a_L.iter().peekable(), a_R_shifted.iter().peekable());
+    loop {
+      let entry = match (left.peek(), right.peek()) {
+        (Some((i, v)), Some((j, w))) if i == j => {
+          let entry = (*i, *v * r + *w * r_inverse);
+          left.next();
+          right.next();
+          entry
+        }
+        (Some((i, v)), Some((j, _))) if i < j => {
+          let entry = (*i, *v * r);
+          left.next();
+          entry
+        }
+        (Some((i, v)), None) => {
+          let entry = (*i, *v * r);
+          left.next();
+          entry
+        }
+        (_, Some((j, w))) => {
+          let entry = (*j, *w * r_inverse);
+          right.next();
+          entry
+        }
+        (None, None) => break,
+      };
+      folded.push(entry);
+    }
+
+    #[cfg(debug_assertions)]
+    self.self_check_folded(
+      folded.iter().map(|(i, v)| *v * self.b_vec[*i]).sum(),
+      c,
+      c_L,
+      c_R,
+      &r,
+    );
+
+    Ok(folded)
+  }
+
+  // like `prove_round`, but commits with views of the two halves of the generators, and
+  // folds them into the left half
+  fn prove_round_in_place(&mut self, transcript: &mut E::TE) -> Result<(), NovaError> {
+    let n = self.a_vec.len();
+    let (a_L, a_R) = self.a_vec.split_at(n / 2);
+
+    let c_L = inner_product(a_L, &self.b_vec[n / 2..n]);
+    let c_R = inner_product(a_R, &self.b_vec[0..n / 2]);
+
+    #[cfg(debug_assertions)]
+    let c = inner_product(&self.a_vec, &self.b_vec);
+
+    let (ck_L, ck_R) = self.ck.as_view().split_at(n / 2);
+    let L = ck_R.commit(a_L) + self.commit_c(&c_L);
+    let R = ck_L.commit(a_R) + self.commit_c(&c_R);
+
+    let (r, r_inverse) = self.absorb_round(L, R, transcript)?;
+    self.ck.fold_in_place(&r_inverse, &r);
+    fold_halves(&mut self.a_vec, &r, &r_inverse);
+
+    #[cfg(debug_assertions)]
+    self.self_check_round(c, c_L, c_R, &r);
+
+    Ok(())
+  }
+}
+
+// the JSON encoding of an inner product argument
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InnerProductArgumentJson {
+  rounds: Vec<InnerProductRoundJson>,
+  a_hat: String,
+  instance_digest: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InnerProductRoundJson {
+  L: String,
+  R: String,
+}
+
+impl<E: Engine> InnerProductArgument<E> {
+  /// Returns the number of rounds of the argument, the logarithm of the length of the vectors
+  pub fn num_rounds(&self) -> usize {
+    self.L_vec.len()
+  }
+
+  /// Returns the size of the argument in bytes, with its `2 * num_rounds()` commitments in the
+  /// compressed encoding of `CommitmentTrait::to_compressed_bytes`, its single scalar and the
+  /// 32-byte digest of its instance, which is the length of `to_compact_bytes`
+  pub fn size_estimate(&self) -> usize {
+    let scalar_len = <E::Scalar as PrimeField>::Repr::default().as_ref().len();
+    (self.L_vec.len() + self.R_vec.len()) * Commitment::<E>::compressed_len()
+      + scalar_len
+      + self.instance_digest.len()
+  }
+
+  /// Returns the number of bytes that each part of the argument contributes to its compact
+  /// encoding (see `to_compact_bytes`): one entry per round, for its commitments `L` and `R`,
+  /// followed by one entry for `a_hat` and one for the digest of the instance. The entries sum
+  /// to the length of the encoding.
+  pub fn round_sizes(&self) -> Vec<usize> {
+    let scalar_len = <E::Scalar as PrimeField>::Repr::default().as_ref().len();
+    iter::repeat(2 * Commitment::<E>::compressed_len())
+      .take(self.L_vec.len())
+      .chain([scalar_len, self.instance_digest.len()])
+      .collect()
+  }
+
+  /// Serializes the argument with `bincode`
+  pub fn to_bytes(&self) -> Vec<u8> {
+    bincode::serialize(self).unwrap()
+  }
+
+  /// Deserializes an argument serialized with `to_bytes`. Returns `NovaError::MalformedProof`
+  /// if the bytes are truncated, have trailing data, or encode an argument whose parts have
+  /// inconsistent lengths.
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, NovaError> {
+    bincode::DefaultOptions::new()
+      .with_fixint_encoding()
+      .reject_trailing_bytes()
+      .deserialize(bytes)
+      .map_err(|_| NovaError::MalformedProof)
+  }
+
+  /// Encodes the argument in a compact format that does not depend on serde or on the layout
+  /// of the argument: the commitments of `L_vec`, then those of `R_vec`, each in the compressed
+  /// encoding of its curve, then `a_hat` as a big-endian integer, then the 32-byte digest of
+  /// the instance, without length prefixes. The number of rounds is implied by the length of
+  /// the encoding.
+  pub fn to_compact_bytes(&self) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(self.size_estimate());
+    for comm in self.L_vec.iter().chain(self.R_vec.iter()) {
+      bytes.extend(comm.to_compressed_bytes());
+    }
+    let mut a_hat = self.a_hat.to_repr().as_ref().to_vec();
+    a_hat.reverse();
+    bytes.extend(a_hat);
+    bytes.extend(self.instance_digest);
+    bytes
+  }
+
+  /// Decodes an argument encoded with `to_compact_bytes`. Returns `NovaError::MalformedProof`
+  /// if the length of the bytes does not match any number of rounds or `a_hat` is not
+  /// canonical, and `NovaError::PointNotOnCurve` if a commitment does not decode.
+  pub fn from_compact_bytes(bytes: &[u8]) -> Result<Self, NovaError> {
+    let point_len = Commitment::<E>::compressed_len();
+    let mut repr = <E::Scalar as PrimeField>::Repr::default();
+    let mut instance_digest = [0u8; 32];
+    let tail_len = repr.as_ref().len() + instance_digest.len();
+    if bytes.len() < tail_len || (bytes.len() - tail_len) % (2 * point_len) != 0 {
+      return Err(NovaError::MalformedProof);
+    }
+    let num_rounds = (bytes.len() - tail_len) / (2 * point_len);
+
+    let (points, tail) = bytes.split_at(bytes.len() - tail_len);
+    let (a_hat, digest) = tail.split_at(repr.as_ref().len());
+    instance_digest.copy_from_slice(digest);
+    let mut comms = points
+      .chunks(point_len)
+      .map(Commitment::<E>::from_compressed_bytes)
+      .collect::<Result<Vec<Commitment<E>>, NovaError>>()?;
+    let R_vec = comms.split_off(num_rounds);
+
+    repr.as_mut().copy_from_slice(a_hat);
+    repr.as_mut().reverse();
+    let a_hat = Option::from(E::Scalar::from_repr(repr)).ok_or(NovaError::MalformedProof)?;
+
+    Ok(InnerProductArgument {
+      L_vec: comms,
+      R_vec,
+      a_hat,
+      instance_digest,
+    })
+  }
+
+  /// Encodes the argument as calldata for a Solidity verifier, as tightly packed 32-byte
+  /// big-endian words without length prefixes. For an argument with `m` rounds, the layout is
+  ///
+  /// | words            | contents                                      |
+  /// |------------------|-----------------------------------------------|
+  /// | `4i` to `4i + 3` | `L_i.x`, `L_i.y`, `R_i.x`, `R_i.y` of round `i` |
+  /// | `4m`             | `a_hat`                                       |
+  /// | `4m + 1`         | the digest of the instance                    |
+  ///
+  /// so the calldata has `32 (4m + 2)` bytes. The point at infinity is encoded with both
+  /// coordinates zero, as by the EVM precompiles for BN254.
+  pub fn to_evm_calldata(&self) -> Vec<u8> {
+    fn word<F: PrimeField>(f: &F) -> Vec<u8> {
+      let mut bytes = f.to_repr().as_ref().to_vec();
+      bytes.reverse();
+      bytes
+    }
+
+    let mut calldata = Vec::with_capacity(32 * (4 * self.L_vec.len() + 2));
+    for (L, R) in self.L_vec.iter().zip(self.R_vec.iter()) {
+      for comm in [L, R] {
+        let (x, y, is_infinity) = comm.to_coordinates();
+        let (x, y) = if is_infinity {
+          (E::Base::ZERO, E::Base::ZERO)
+        } else {
+          (x, y)
+        };
+        calldata.extend(word(&x));
+        calldata.extend(word(&y));
+      }
+    }
+    calldata.extend(word(&self.a_hat));
+    calldata.extend(self.instance_digest);
+    calldata
+  }
+
+  /// Encodes the argument as a readable JSON object, for debugging and for authoring test
+  /// vectors: `rounds` holds the commitments `L` and `R` of each round, `a_hat` the final
+  /// folded witness, and `instance_digest` the digest of the instance, all as hex strings
+  pub fn to_json(&self) -> String {
+    let to_hex = |comm: &Commitment<E>| hex::encode(comm.to_compressed_bytes());
+    let json = InnerProductArgumentJson {
+      rounds: self
+        .L_vec
+        .iter()
+        .zip(self.R_vec.iter())
+        .map(|(L, R)| InnerProductRoundJson {
+          L: to_hex(L),
+          R: to_hex(R),
+        })
+        .collect(),
+      a_hat: hex::encode(self.a_hat.to_repr()),
+      instance_digest: hex::encode(self.instance_digest),
+    };
+    serde_json::to_string_pretty(&json).unwrap()
+  }
+
+  /// Decodes an argument encoded with `to_json`. Returns `NovaError::InvalidJson` if the input
+  /// is not a JSON object of the expected shape with hex strings, `NovaError::MalformedProof`
+  /// if a commitment, `a_hat` or the digest has the wrong length or `a_hat` is not canonical, and
+  /// `NovaError::PointNotOnCurve` if a commitment does not decode to a point of the curve.
+  pub fn from_json(json: &str) -> Result<Self, NovaError> {
+    let invalid = |reason: String| NovaError::InvalidJson { reason };
+    let json: InnerProductArgumentJson =
+      serde_json::from_str(json).map_err(|e| invalid(e.to_string()))?;
+
+    let from_hex = |s: &str| -> Result<Commitment<E>, NovaError> {
+      let bytes = hex::decode(s).map_err(|e| invalid(e.to_string()))?;
+      Commitment::<E>::from_compressed_bytes(&bytes).map_err(|e| match e {
+        NovaError::InvalidInputLength => NovaError::MalformedProof,
+        e => e,
+      })
+    };
+    let (L_vec, R_vec) = json
+      .rounds
+      .iter()
+      .map(|round| Ok((from_hex(&round.L)?, from_hex(&round.R)?)))
+      .collect::<Result<Vec<_>, NovaError>>()?
+      .into_iter()
+      .unzip();
+
+    let mut repr = <E::Scalar as PrimeField>::Repr::default();
+    let bytes = hex::decode(&json.a_hat).map_err(|e| invalid(e.to_string()))?;
+    if bytes.len() != repr.as_ref().len() {
+      return Err(NovaError::MalformedProof);
+    }
+    repr.as_mut().copy_from_slice(&bytes);
+    let a_hat = Option::from(E::Scalar::from_repr(repr)).ok_or(NovaError::MalformedProof)?;
+
+    let instance_digest = hex::decode(&json.instance_digest)
+      .map_err(|e| invalid(e.to_string()))?
+      .try_into()
+      .map_err(|_| NovaError::MalformedProof)?;
+
+    Ok(InnerProductArgument {
+      L_vec,
+      R_vec,
+      a_hat,
+      instance_digest,
+    })
+  }
+}
+
+/// An evaluation argument for a polynomial committed both with Pedersen and with a Merkle
+/// tree, as produced by `EvaluationEngine::prove_hybrid`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct HybridEvaluationArgument<E: Engine> {
+  arg: InnerProductArgument<E>,
+  query_evals: Vec<E::Scalar>,
+  query_paths: Vec<MerklePath>,
+  query_args: Vec<InnerProductArgument<E>>,
+}
+
+/// An evaluation argument together with an opening of known entries of the polynomial, as
+/// produced by `EvaluationEngine::prove_with_known_coeffs`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct KnownCoeffsArgument<E: Engine> {
+  arg: InnerProductArgument<E>,
+  known_arg: InnerProductArgument<E>,
+}
+
+/// An argument that committed vectors satisfy `c = a ∘ b`, as produced by
+/// `EvaluationEngine::prove_hadamard`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct HadamardArgument<E: Engine> {
+  sc_proof: SumcheckProof<E>,
+  evals: [E::Scalar; 3],
+  arg: InnerProductArgument<E>,
+}
+
+/// An evaluation argument together with the digest of the commitment key it was produced
+/// under, as produced by `EvaluationEngine::prove_key_bound`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct KeyBoundArgument<E: Engine> {
+  key_digest: [u8; 32],
+  arg: InnerProductArgument<E>,
+}
+
+// a digest, e.g., of a commitment key or of an instance, which is absorbed in the transcript
+struct TranscriptDigest([u8; 32]);
+
+impl<G: Group> TranscriptReprTrait<G> for TranscriptDigest {
+  fn to_transcript_bytes(&self) -> Vec<u8> {
+    self.0.to_vec()
+  }
+}
+
+/// Describes the random linear combinations checked by `verify_many_with_soundness`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SoundnessReport {
+  /// The number of bits of each random weight
+  pub challenge_bits: u32,
+  /// The number of independent combinations that were checked
+  pub num_repetitions: usize,
+  /// An invalid batch passes all the combinations with probability at most `2^-soundness_bits`
+  pub soundness_bits: u32,
+}
+
+/// The Fiat-Shamir challenges of an inner product argument
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InnerProductChallenges<E: Engine> {
+  /// The challenge that randomizes the base used to commit to the inner product
+  pub r_c: E::Scalar,
+  /// The challenges used to fold the vectors, one per round
+  pub r_vec: Vec<E::Scalar>,
+}
+
+impl<E> InnerProductArgument<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+  CommitmentKey<E>: CommitmentKeyExtTrait<E>,
+{
+  // the version suffix separates the transcripts of arguments that bind the digest of the
+  // instance from those of earlier versions, which do not
+  const fn protocol_name() -> &'static [u8] {
+    b"IPA-v2"
+  }
+
+  // if proving fails, the transcript is rolled back to where it was before the argument, so
+  // that the caller can retry from there
+  fn prove(
+    ck: &CommitmentKey<E>,
+    ck_c: &CommitmentKey<E>,
+    U: &InnerProductInstance<E>,
+    W: &InnerProductWitness<E>,
+    transcript: &mut E::TE,
+  ) -> Result<Self, NovaError> {
+    let checkpoint = transcript.checkpoint();
+    let arg =
+      Self::prove_deferred(ck, ck_c, U, W, transcript).and_then(|state| state.finish(transcript));
+    if arg.is_err() {
+      transcript.restore(checkpoint);
+    }
+    arg
+  }
+
+  // like `prove` with the key `pk.ck_s`, but consumes the instance and the witness and folds
+  // their vectors in place, which avoids copying them, and commits to the inner products of the
+  // rounds with the table of multiples cached in `pk`
+  fn prove_owned(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    mut U: InnerProductInstance<E>,
+    W: InnerProductWitness<E>,
+    transcript: &mut E::TE,
+  ) -> Result<Self, NovaError> {
+    let b_vec = mem::replace(&mut U.b_vec, PublicVector::Full(Vec::new())).into_full();
+    let checkpoint = transcript.checkpoint();
+    let arg = Self::prove_start(ck, &pk.ck_s, &U, W.a_vec, b_vec, transcript).and_then(|state| {
+      InnerProductProverState {
+        c_table: Some(pk.ck_s_table()),
+        ..state
+      }
+      .finish(transcript)
+    });
+    if arg.is_err() {
+      transcript.restore(checkpoint);
+    }
+    arg
+  }
+
+  // runs the rounds of the argument until the folded vectors have length at most
+  // `InnerProductProverState::DEFERRED_LEN`
+  fn prove_deferred(
+    ck: &CommitmentKey<E>,
+    ck_c: &CommitmentKey<E>,
+    U: &InnerProductInstance<E>,
+    W: &InnerProductWitness<E>,
+    transcript: &mut E::TE,
+  ) -> Result<InnerProductProverState<E>, NovaError> {
+    // we create mutable copies of vectors
+    Self::prove_deferred_with(ck, ck_c, U, W.a_vec.to_vec(), U.b_vec.to_full(), transcript)
+  }
+
+  // `prove_deferred` for the instance `U`, whose vector `b_vec` is supplied separately, and
+  // the witness vector `a_vec`
+  fn prove_deferred_with(
+    ck: &CommitmentKey<E>,
+    ck_c: &CommitmentKey<E>,
+    U: &InnerProductInstance<E>,
+    a_vec: Vec<E::Scalar>,
+    b_vec: Vec<E::Scalar>,
+    transcript: &mut E::TE,
+  ) -> Result<InnerProductProverState<E>, NovaError> {
+    let mut state = Self::prove_start(ck, ck_c, U, a_vec, b_vec, transcript)?;
+    while state.a_vec.len() > InnerProductProverState::<E>::DEFERRED_LEN {
+      state.prove_round(transcript)?;
+    }
+
+    Ok(state)
+  }
+
+  // binds the instance and returns the state of the prover before the first round. Vectors
+  // whose length is not a power of two are padded with zeros to the next power of two, which
+  // does not change the commitment to `a_vec` nor the inner product.
+  fn prove_start(
+    ck: &CommitmentKey<E>,
+    ck_c: &CommitmentKey<E>,
+    U: &InnerProductInstance<E>,
+    mut a_vec: Vec<E::Scalar>,
+    mut b_vec: Vec<E::Scalar>,
+    transcript: &mut E::TE,
+  ) -> Result<InnerProductProverState<E>, NovaError> {
+    if b_vec.len() != a_vec.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let n = b_vec.len().next_power_of_two();
+    a_vec.resize(n, E::Scalar::ZERO);
+    b_vec.resize(n, E::Scalar::ZERO);
+
+    let mut state = Self::bind_instance(ck, ck_c, U, n, transcript)?;
+    state.a_vec = a_vec;
+    state.b_vec = b_vec;
+    Ok(state)
+  }
+
+  // absorbs the instance and returns the state of the prover before the first round for
+  // vectors of length `n`, without the witness and public vectors, which are set by the caller
+  fn bind_instance(
+    ck: &CommitmentKey<E>,
+    ck_c: &CommitmentKey<E>,
+    U: &InnerProductInstance<E>,
+    n: usize,
+    transcript: &mut E::TE,
+  ) -> Result<InnerProductProverState<E>, NovaError> {
+    Self::check_keys(ck, ck_c, n)?;
+
+    if U.dom_sep {
+      transcript.dom_sep(Self::protocol_name());
+    }
+    let instance_digest = U.append_to_transcript(transcript);
+
+    let (ck, _) = ck.split_at(n);
+
+    // absorb the instance in the transcript
+    transcript.absorb(b"U", U);
+
+    // sample a random base for committing to the inner product
+    let r = transcript.squeeze(b"r")?;
+    let ck_c = ck_c.scale(&r);
+
+    Ok(InnerProductProverState {
+      L_vec: Vec::new(),
+      R_vec: Vec::new(),
+      a_vec: Vec::new(),
+      b_vec: Vec::new(),
+      ck,
+      ck_c,
+      r_c: r,
+      c_table: None,
+      instance_digest,
+    })
+  }
+
+  // verifies the argument against the generators of `vk`, deriving the challenges as in
+  // `challenges`
+  fn verify(
+    &self,
+    vk: &VerifierKey<E>,
+    n: usize,
+    U: &InnerProductInstance<E>,
+    transcript: &mut E::TE,
+  ) -> Result<(), NovaError> {
+    self.verify_deferred(vk, n, U, transcript)?.check()
+  }
+
+  fn verify_deferred(
+    &self,
+    vk: &VerifierKey<E>,
+    n: usize,
+    U: &InnerProductInstance<E>,
+    transcript: &mut E::TE,
+  ) -> Result<DeferredCheck<E>, NovaError> {
+    let challenges = self.challenges(vk, n, U, transcript)?;
+    self.verify_deferred_with_challenges(&vk.ck_v, &vk.ck_s, n, U, &challenges)
+  }
+
+  // like `verify_deferred_with_challenges` followed by the check, except that the folded
+  // generator `<s, ck>` and the final MSM are computed by `service`
+  fn verify_with_msm_service<M: MsmService<E>>(
+    &self,
+    ck: &CommitmentKey<E>,
+    ck_c: &CommitmentKey<E>,
+    n: usize,
+    U: &InnerProductInstance<E>,
+    challenges: &InnerProductChallenges<E>,
+    service: &M,
+  ) -> Result<(), NovaError> {
+    let check = self.final_check(ck, ck_c, n, U, challenges, |ck, n, r_square, r_inverse| {
+      let (ck, _) = ck.split_at(n);
+      let s = tensor_vector(r_square, r_inverse);
+      Ok((Some(service.msm(&ck, &s)?), s))
+    })?;
+
+    if service.msm(&check.bases, &check.scalars)? == Commitment::<E>::default() {
+      Ok(())
+    } else {
+      Err(NovaError::FinalCheckFailed)
+    }
+  }
+
+  // checks that the argument is well-formed for a vector of length `n`, which the prover pads
+  // to the next power of two
+  fn check_lengths(&self, n: usize, b_len: usize) -> Result<(), NovaError> {
+    if self.L_vec.len() >= 32 || self.R_vec.len() >= 32 {
+      return Err(NovaError::RoundCountExceeded);
+    }
+    if b_len != n
+      || n.next_power_of_two() != (1 << self.L_vec.len())
+      || self.L_vec.len() != self.R_vec.len()
+    {
+      return Err(NovaError::ProofLengthMismatch);
+    }
+    Ok(())
+  }
+
+  // like `check_lengths`, for a polynomial in `num_vars` variables; this only compares the
+  // number of rounds, so it is cheap even when `2^num_vars` is large
+  fn check_num_rounds(&self, num_vars: usize) -> Result<(), NovaError> {
+    if self.L_vec.len() >= 32 || self.R_vec.len() >= 32 {
+      return Err(NovaError::RoundCountExceeded);
+    }
+    if self.L_vec.len() != num_vars || self.R_vec.len() != num_vars {
+      return Err(NovaError::ProofLengthMismatch);
+    }
+    Ok(())
+  }
+
+  // derives the challenges of the argument, absorbing the commitments of each round in the
+  // order of `vk`; each verifier with a verifier key derives its challenges here
+  fn challenges(
+    &self,
+    vk: &VerifierKey<E>,
+    n: usize,
+    U: &InnerProductInstance<E>,
+    transcript: &mut E::TE,
+  ) -> Result<InnerProductChallenges<E>, NovaError> {
+    if U.dom_sep {
+      transcript.dom_sep(Self::protocol_name());
+    }
+    self.check_lengths(n, U.b_vec.len())?;
+    if U.append_to_transcript(transcript) != self.instance_digest {
+      return Err(NovaError::InstanceMismatch);
+    }
+
+    // absorb the instance in the transcript
+    transcript.absorb(b"U", U);
+
+    // sample a random base for committing to the inner product
+    let r_c = transcript.squeeze(b"r")?;
+
+    // compute a vector of public coins using self.L_vec and self.R_vec
+    let r_vec = round_challenges::<E>(transcript, &self.L_vec, &self.R_vec, vk.round_order)?;
+
+    Ok(InnerProductChallenges { r_c, r_vec })
+  }
+
+  // checks that `ck` has a generator for each of the `n` entries of the vectors, and that
+  // `ck_c` has the generator for the inner product
+  fn check_keys(ck: &CommitmentKey<E>, ck_c: &CommitmentKey<E>, n: usize) -> Result<(), NovaError> {
+    if ck.length() < n || ck_c.length() < 1 {
+      return Err(NovaError::InvalidCommitmentKeyLength);
+    }
+    Ok(())
+  }
+
+  fn verify_deferred_with_challenges(
+    &self,
+    ck: &CommitmentKey<E>,
+    ck_c: &CommitmentKey<E>,
+    n: usize,
+    U: &InnerProductInstance<E>,
+    challenges: &InnerProductChallenges<E>,
+  ) -> Result<DeferredCheck<E>, NovaError> {
+    // the generators stay in the check, weighted by the vector with the tensor structure
+    self.final_check(ck, ck_c, n, U, challenges, |_, _, r_square, r_inverse| {
+      Ok((None, tensor_vector(r_square, r_inverse)))
+    })
+  }
+
+  // assembles the final check of the argument for the challenges, where `fold` is given the
+  // generators, the padded length `n`, and the squares of the challenges and their inverses,
+  // and returns the vector `s` with which the generators are folded, along with `<s, ck>` if
+  // it computes the folded generator itself. Otherwise, the first `n` generators are bases of
+  // the check, weighted by `s`. The vector `s` may be empty if the folded generator is
+  // computed and the inner product of the public vector with `s` has a closed form.
+  fn final_check<F>(
+    &self,
+    ck: &CommitmentKey<E>,
+    ck_c: &CommitmentKey<E>,
+    n: usize,
+    U: &InnerProductInstance<E>,
+    challenges: &InnerProductChallenges<E>,
+    fold: F,
+  ) -> Result<DeferredCheck<E>, NovaError>
+  where
+    F: FnOnce(
+      &CommitmentKey<E>,
+      usize,
+      &[E::Scalar],
+      &[E::Scalar],
+    ) -> Result<(Option<Commitment<E>>, Vec<E::Scalar>), NovaError>,
+  {
+    self.check_lengths(n, U.b_vec.len())?;
+    if challenges.r_vec.len() != self.L_vec.len() {
+      return Err(NovaError::ProofLengthMismatch);
+    }
+    let n = n.next_power_of_two();
+    Self::check_keys(ck, ck_c, n)?;
+
+    let (ck_c, _) = ck_c.split_at(1);
+    let r_c = challenges.r_c;
+    let r = &challenges.r_vec;
+
+    // precompute scalars necessary for verification; there is one per round, so these are
+    // computed sequentially
+    let r_square = r.iter().map(|r_i| r_i.square()).collect::<Vec<E::Scalar>>();
+    let r_inverse = batch_invert(r)?;
+    let r_inverse_square = r_inverse
+      .iter()
+      .map(|r_i| r_i.square())
+      .collect::<Vec<E::Scalar>>();
+
+    let (ck_hat, s) = fold(ck, n, &r_square, &r_inverse)?;
+    let b_hat = U.b_vec.inner_product_with_tensor(&s, r, &r_inverse);
+
+    // the argument verifies iff P_hat = a_hat * <s, ck> + a_hat * b_hat * r_c * ck_c, where
+    // P_hat = <r_square, L_vec> + <r_inverse_square, R_vec> + comm_a_vec + c * r_c * ck_c;
+    // we move all terms to one side so that the check becomes a single MSM against zero
+    // a folded generator computed by `fold` is not a generator of the key
+    let (ck_hat, s_scaled, num_key_bases) = match ck_hat {
+      Some(ck_hat) => (
+        CommitmentKey::<E>::reinterpret_commitments_as_ck(&[ck_hat])?,
+        vec![-self.a_hat],
+        1,
+      ),
+      None => {
+        let s_scaled = s
+          .par_iter()
+          .map(|s_i| -(self.a_hat * s_i))
+          .collect::<Vec<E::Scalar>>();
+        (ck.split_at(n).0, s_scaled, n + 1)
+      }
+    };
+
+    let bases = {
+      let ck_L = CommitmentKey::<E>::reinterpret_commitments_as_ck(&self.L_vec)?;
+      let ck_R = CommitmentKey::<E>::reinterpret_commitments_as_ck(&self.R_vec)?;
+      let ck_P = CommitmentKey::<E>::reinterpret_commitments_as_ck(&[U.comm_a_vec])?;
+      ck_c
+        .combine(&ck_hat)
+        .combine(&ck_L)
+        .combine(&ck_R)
+        .combine(&ck_P)
+    };
+    let scalars = iter::once(r_c * (U.c - self.a_hat * b_hat))
+      .chain(s_scaled)
+      .chain(r_square)
+      .chain(r_inverse_square)
+      .chain(iter::once(E::Scalar::ONE))
+      .collect::<Vec<E::Scalar>>();
+
+    Ok(DeferredCheck {
+      bases,
+      scalars,
+      num_key_bases,
+    })
+  }
+}
+
+impl<E> InnerProductArgument<E>
+where
+  E: Engine<CE = CommitmentEngine<E>>,
+  E::GE: DlogGroup,
+{
+  // like `prove_owned`, for a sparse witness vector. The rounds commit to the nonzero entries
+  // only, until the folded witness is dense enough that the remaining rounds run as usual.
+  fn prove_sparse(
+    ck: &CommitmentKey<E>,
+    ck_c: &CommitmentKey<E>,
+    mut U: InnerProductInstance<E>,
+    W: SparseInnerProductWitness<E>,
+    transcript: &mut E::TE,
+  ) -> Result<Self, NovaError> {
+    let mut b_vec = mem::replace(&mut U.b_vec, PublicVector::Full(Vec::new())).into_full();
+    if b_vec.len() != W.len {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let n = b_vec.len().next_power_of_two();
+    b_vec.resize(n, E::Scalar::ZERO);
+    let mut state = Self::bind_instance(ck, ck_c, &U, n, transcript)?;
+    state.b_vec = b_vec;
+
+    let mut entries = W.entries;
+    while state.b_vec.len() > 1
+      && entries.len() * InnerProductProverState::<E>::SPARSE_RATIO <= state.b_vec.len()
+    {
+      entries = state.prove_round_sparse(&entries, transcript)?;
+    }
+
+    state.a_vec = vec![E::Scalar::ZERO; state.b_vec.len()];
+    for (i, v) in entries {
+      state.a_vec[i] = v;
+    }
+    state.finish(transcript)
+  }
+
+  // like `prove_owned`, but commits to the halves of the witness with views of the generators
+  // rather than copies of them, and folds the generators in place
+  fn prove_in_place(
+    ck: &CommitmentKey<E>,
+    ck_c: &CommitmentKey<E>,
+    mut U: InnerProductInstance<E>,
+    a_vec: Vec<E::Scalar>,
+    transcript: &mut E::TE,
+  ) -> Result<Self, NovaError> {
+    let b_vec = mem::replace(&mut U.b_vec, PublicVector::Full(Vec::new())).into_full();
+    let mut state = Self::prove_start(ck, ck_c, &U, a_vec, b_vec, transcript)?;
+    while state.a_vec.len() > 1 {
+      state.prove_round_in_place(transcript)?;
+    }
+    state.finish(transcript)
+  }
+
+  // like `prove`, but uses the precomputed table of `ck` for the commitments of the first
+  // round, which are the only ones against the original generators
+  fn prove_prepared(
+    ck: &PreparedCommitmentKey<E>,
+    ck_c: &CommitmentKey<E>,
+    U: &InnerProductInstance<E>,
+    W: &InnerProductWitness<E>,
+    transcript: &mut E::TE,
+  ) -> Result<Self, NovaError> {
+    let mut state = Self::prove_start(
+      ck.ck(),
+      ck_c,
+      U,
+      W.a_vec.to_vec(),
+      U.b_vec.to_full(),
+      transcript,
+    )?;
+    let n = state.a_vec.len();
+    if n > 1 {
+      state.prove_round_with(transcript, |a_L, a_R| {
+        (ck.commit_at(n / 2, a_L), ck.commit_at(0, a_R))
+      })?;
+    }
+    state.finish(transcript)
+  }
+
+  // like `prove`, for a witness vector given as shards. The first round combines the
+  // contributions of the shards, and the remaining rounds run on the folded vector.
+  pub(crate) fn prove_sharded(
+    ck: &CommitmentKey<E>,
+    ck_c: &CommitmentKey<E>,
+    U: &InnerProductInstance<E>,
+    shards: &[Shard<E>],
+    transcript: &mut E::TE,
+  ) -> Result<Self, NovaError> {
+    let n = U.b_vec.len();
+    let mut state = Self::bind_instance(ck, ck_c, U, n, transcript)?;
+    state.b_vec = U.b_vec.to_full();
+    if n == 1 {
+      state.a_vec = shards[0].a_vec.clone();
+      return state.finish(transcript);
+    }
+
+    let contributions = shards
+      .par_iter()
+      .map(|shard| shard.first_round(ck, &state.b_vec))
+      .collect::<Result<Vec<_>, _>>()?;
+    let mut parts_L = Vec::new();
+    let mut parts_R = Vec::new();
+    let (mut c_L, mut c_R) = (E::Scalar::ZERO, E::Scalar::ZERO);
+    for (parts, c_L_shard, c_R_shard) in contributions {
+      // the entries in the left half are committed with the generators of the right half,
+      // and contribute to `L`
+      for part in parts {
+        if part.range().start >= n / 2 {
+          parts_L.push(part);
+        } else {
+          parts_R.push(part);
+        }
+      }
+      c_L += c_L_shard;
+      c_R += c_R_shard;
+    }
+
+    #[cfg(debug_assertions)]
+    let c = shards
+      .iter()
+      .map(|shard| inner_product(&shard.a_vec, &state.b_vec[shard.range.clone()]))
+      .sum();
+
+    let L = PartialCommitment::combine(&parts_L)? + state.commit_c(&c_L);
+    let R = PartialCommitment::combine(&parts_R)? + state.commit_c(&c_R);
+    let (r, r_inverse) = state.push_round(L, R, transcript)?;
+
+    let mut a_folded = vec![E::Scalar::ZERO; n / 2];
+    for shard in shards {
+      shard.fold_into(&mut a_folded, &r, &r_inverse);
+    }
+    state.a_vec = a_folded;
+
+    #[cfg(debug_assertions)]
+    state.self_check_round(c, c_L, c_R, &r);
+
+    state.finish(transcript)
+  }
+
+  // like `prove`, for an instance whose public vector is the evaluations of `eq` at `point`,
+  // and a witness vector of length `2^{point.len()}` that is read in chunks with `get_chunk`,
+  // as described in `EvaluationEngine::prove_streaming`. The rounds that read the witness in
+  // chunks are not self-checked in debug builds, since checking them would read the witness
+  // once more per round; only the rounds that `finish` runs on the vectors in memory are.
+  fn prove_streaming<F>(
+    ck: &CommitmentKey<E>,
+    ck_c: &CommitmentKey<E>,
+    U: &InnerProductInstance<E>,
+    point: &[E::Scalar],
+    max_len: usize,
+    mut get_chunk: F,
+    transcript: &mut E::TE,
+  ) -> Result<Self, NovaError>
+  where
+    F: FnMut(Range<usize>) -> Vec<E::Scalar>,
+  {
+    let n = 1 << point.len();
+    if !max_len.is_power_of_two() {
+      return Err(NovaError::InvalidInputLength);
+    }
+    if ck.length() < n {
+      return Err(NovaError::InvalidCommitmentKeyLength);
+    }
+
+    let mut state = Self::bind_instance(ck, ck_c, U, n, transcript)?;
+
+    // after `k` rounds, the public vector is `scale * eq(point[k..])`
+    let mut r_vec = Vec::new();
+    let mut scale = E::Scalar::ONE;
+    while n >> r_vec.len() > max_len {
+      let k = r_vec.len();
+      let half = n >> (k + 1);
+      let weights = fold_weights(&r_vec)?;
+      let (ck_L, ck_R) = state.ck.as_view().split_at(half);
+
+      let mut comm_L = Commitment::<E>::default();
+      let mut comm_R = Commitment::<E>::default();
+      let (mut c_L, mut c_R) = (E::Scalar::ZERO, E::Scalar::ZERO);
+      for start in (0..half).step_by(max_len) {
+        let end = start + max_len;
+        let a_L = folded_chunk(&mut get_chunk, n, &weights, start..end)?;
+        let a_R = folded_chunk(&mut get_chunk, n, &weights, start + half..end + half)?;
+        let b_L = eq_chunk(&point[k..], start, max_len);
+        let b_R = eq_chunk(&point[k..], start + half, max_len);
+
+        comm_L = comm_L + ck_R.split_at(start).1.split_at(max_len).0.commit(&a_L);
+        comm_R = comm_R + ck_L.split_at(start).1.split_at(max_len).0.commit(&a_R);
+        c_L += inner_product(&a_L, &b_R);
+        c_R += inner_product(&a_R, &b_L);
+      }
+
+      let L = comm_L + state.commit_c(&(c_L * scale));
+      let R = comm_R + state.commit_c(&(c_R * scale));
+      let (r, r_inverse) = state.push_round(L, R, transcript)?;
+      scale *= (E::Scalar::ONE - point[k]) * r_inverse + point[k] * r;
+      r_vec.push(r);
+    }
+
+    // the folded vectors fit in memory
+    let k = r_vec.len();
+    state.a_vec = folded_chunk(&mut get_chunk, n, &fold_weights(&r_vec)?, 0..n >> k)?;
+    state.b_vec = EqPolynomial::evals_from_points(&point[k..])
+      .into_iter()
+      .map(|b| b * scale)
+      .collect();
+    state.finish(transcript)
+  }
+
+  // like `verify_deferred_with_challenges` followed by the check, for an instance whose vector
+  // `b` holds the evaluations of `eq`, but computes `<s, ck>` in blocks of `block_len` entries:
+  // with `s = s_hi ⊗ s_lo`, the block `i` contributes `s_hi[i] * <s_lo, ck_i>`, so that only
+  // `s_lo` and `s_hi`, of `block_len` and `n / block_len` entries, are held in memory
+  fn verify_streaming(
+    &self,
+    ck: &CommitmentKey<E>,
+    ck_c: &CommitmentKey<E>,
+    n: usize,
+    U: &InnerProductInstance<E>,
+    challenges: &InnerProductChallenges<E>,
+    block_len: usize,
+  ) -> Result<(), NovaError> {
+    if !block_len.is_power_of_two() || !matches!(U.b_vec, PublicVector::Eq(_)) {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    self
+      .final_check(ck, ck_c, n, U, challenges, |ck, _, r_square, r_inverse| {
+        // the first challenges select the block, and the last `k` the entry within the block
+        let m = r_square.len();
+        let k = (block_len.ilog2() as usize).min(m);
+        let s_hi = tensor_vector(&r_square[..m - k], &r_inverse[..m - k]);
+        let s_lo = tensor_vector(&r_square[m - k..], &r_inverse[m - k..]);
+        let ck = ck.as_view();
+        let ck_hat = s_hi
+          .iter()
+          .enumerate()
+          .fold(Commitment::<E>::default(), |acc, (i, s_i)| {
+            let (_, ck_i) = ck.split_at(i << k);
+            let (ck_i, _) = ck_i.split_at(1 << k);
+            acc + ck_i.commit(&s_lo) * *s_i
+          });
+
+        // the inner product of `eq` with `s` has a closed form, which does not read `s`
+        Ok((Some(ck_hat), Vec::new()))
+      })?
+      .check()
+  }
+}
+
+// the weights with which the entries of a witness vector are combined into an entry of the
+// vector folded with the challenges `r_vec`: the `t`-th weight is the product over the rounds
+// `j` of `r_j^{-1}` if the `j`-th most significant bit of `t` is set, and of `r_j` otherwise
+fn fold_weights<T: Field>(r_vec: &[T]) -> Result<Vec<T>, NovaError> {
+  let r_inverse = batch_invert(r_vec)?;
+  let r_inverse_square = r_inverse.iter().map(|r| r.square()).collect::<Vec<T>>();
+  Ok(tensor_vector(&r_inverse_square, r_vec))
+}
+
+// computes the entries at the positions `range` of the witness vector of length `n` folded
+// with the weights of `fold_weights`, reading the chunks of the witness that fold into them
+fn folded_chunk<T, F>(
+  get_chunk: &mut F,
+  n: usize,
+  weights: &[T],
+  range: Range<usize>,
+) -> Result<Vec<T>, NovaError>
+where
+  T: Field,
+  F: FnMut(Range<usize>) -> Vec<T>,
+{
+  let n_folded = n / weights.len();
+  let mut folded = vec![T::ZERO; range.len()];
+  for (t, w) in weights.iter().enumerate() {
+    let chunk = get_chunk(t * n_folded + range.start..t * n_folded + range.end);
+    if chunk.len() != range.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+    folded
+      .par_iter_mut()
+      .zip(chunk.par_iter())
+      .for_each(|(f, a)| *f += *a * w);
+  }
+  Ok(folded)
+}
+
+// returns the `j`-th of the `num_vars` bits of `index`, with the most significant bit first;
+// the bits beyond the width of `usize` are zero
+fn index_bit(index: usize, num_vars: usize, j: usize) -> bool {
+  let shift = num_vars - 1 - j;
+  shift < usize::BITS as usize && (index >> shift) & 1 == 1
+}
+
+// evaluates `eq(point, x)` at the vertex `x` of the hypercube whose coordinates are the bits of
+// `index`, with the most significant bit first
+fn eq_at_index<T: PrimeField>(point: &[T], index: usize) -> T {
+  point.iter().enumerate().fold(T::ONE, |acc, (j, p)| {
+    if index_bit(index, point.len(), j) {
+      acc * p
+    } else {
+      acc * (T::ONE - p)
+    }
+  })
+}
+
+// computes the `len` evaluations of `eq(point)` starting at `start`, where `len` is a power
+// of two that divides `start`: the evaluations share the factor of the most significant
+// coordinates, which are fixed by `start`
+fn eq_chunk<T: PrimeField>(point: &[T], start: usize, len: usize) -> Vec<T> {
+  let (hi, lo) = point.split_at(point.len() - len.ilog2() as usize);
+  let factor = eq_at_index(hi, start >> lo.len());
+  EqPolynomial::evals_from_points(lo)
+    .into_iter()
+    .map(|e| e * factor)
+    .collect()
+}
+
+/// An inner product argument whose final check is a proof of knowledge of `a_hat`, as produced
+/// by `EvaluationEngine::prove_final_knowledge`, in place of `a_hat` itself
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct FinalKnowledgeArgument<E: Engine> {
+  L_vec: Vec<Commitment<E>>,
+  R_vec: Vec<Commitment<E>>,
+  // the commitment of the final step to the random mask of `a_hat`
+  T: Commitment<E>,
+  z: E::Scalar,
+  instance_digest: [u8; 32],
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+  use super::*;
+  use crate::{
+    provider::{keccak::Keccak256Transcript, PallasEngine},
+    spartan::polys::multilinear::MultilinearPolynomial,
+  };
+  use rand::SeedableRng;
+
+  type E = PallasEngine;
+  type Fr = <E as Engine>::Scalar;
+
+  thread_local! {
+    // whether the prover running on this thread perturbs the inverse of each round challenge
+    static FAULTY_R_INVERSE: core::cell::Cell<bool> = const { core::cell::Cell::new(false) };
+  }
+
+  // the fault hook of the prover: returns `r_inverse`, plus one if the test running on this
+  // thread injected a fault with `FAULTY_R_INVERSE`
+  pub(super) fn fault_r_inverse<F: Field>(r_inverse: F) -> F {
+    if FAULTY_R_INVERSE.with(|faulty| faulty.get()) {
+      r_inverse + F::ONE
     } else {
-      Err(NovaError::InvalidPCS)
+      r_inverse
+    }
+  }
+
+  // samples a random polynomial over `num_vars` variables, a point, and the evaluation there
+  pub(crate) fn random_instance(
+    num_vars: usize,
+    rng: &mut rand::rngs::StdRng,
+  ) -> (Vec<Fr>, Vec<Fr>, Fr) {
+    let poly = (0..(1 << num_vars))
+      .map(|_| Fr::random(&mut *rng))
+      .collect::<Vec<Fr>>();
+    let point = (0..num_vars)
+      .map(|_| Fr::random(&mut *rng))
+      .collect::<Vec<Fr>>();
+    let eval = MultilinearPolynomial::evaluate_with(&poly, &point);
+    (poly, point, eval)
+  }
+
+  // the keys, the instance, the commitment, and the argument of a proven random evaluation
+  type ProvenInstance = (
+    CommitmentKey<E>,
+    ProverKey<E>,
+    VerifierKey<E>,
+    Vec<Fr>,
+    Vec<Fr>,
+    Fr,
+    Commitment<E>,
+    InnerProductArgument<E>,
+  );
+
+  // sets up the keys for polynomials over `num_vars` variables, and proves the evaluation of a
+  // random polynomial at a random point with `EvaluationEngine::prove`
+  fn prove_random_instance(num_vars: usize, rng: &mut rand::rngs::StdRng) -> ProvenInstance {
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let (poly, point, eval) = random_instance(num_vars, rng);
+    let comm = CE::<E>::commit(&ck, &poly);
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg =
+      EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+    (ck, pk, vk, poly, point, eval, comm, arg)
+  }
+
+  #[test]
+  fn test_ipa_verify_deferred_aggregate() {
+    let num_vars = 4;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+
+    let mut checks = (0..3)
+      .map(|_| {
+        let (poly, point, eval) = random_instance(num_vars, &mut rng);
+        let comm = CE::<E>::commit(&ck, &poly);
+
+        let mut transcript = Keccak256Transcript::new(b"TestEval");
+        let arg =
+          EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+
+        let mut transcript = Keccak256Transcript::new(b"TestEval");
+        let check =
+          EvaluationEngine::verify_deferred(&vk, &mut transcript, &comm, &point, &eval, &arg)
+            .unwrap();
+        assert_eq!(check.bases().length(), check.scalars().len());
+        assert!(check.check().is_ok());
+        check
+      })
+      .collect::<Vec<_>>();
+
+    // the deferred checks of all proofs are verified with a single MSM
+    let weights = (0..checks.len())
+      .map(|_| Fr::random(&mut rng))
+      .collect::<Vec<Fr>>();
+    let aggregate = DeferredCheck::aggregate(&checks, &weights).unwrap();
+    assert!(aggregate.check().is_ok());
+
+    // the aggregate fails if any one of the checks fails
+    checks[1].scalars[0] += Fr::ONE;
+    let aggregate = DeferredCheck::aggregate(&checks, &weights).unwrap();
+    assert_eq!(aggregate.check(), Err(NovaError::FinalCheckFailed));
+
+    assert_eq!(
+      DeferredCheck::aggregate(&checks, &weights[1..]).err(),
+      Some(NovaError::InvalidInputLength)
+    );
+  }
+
+  #[test]
+  fn test_ipa_deferred_batch_matches_verify() {
+    let num_vars = 3;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(73);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+
+    let instances = (0..3)
+      .map(|_| {
+        let (poly, point, eval) = random_instance(num_vars, &mut rng);
+        let comm = CE::<E>::commit(&ck, &poly);
+        let mut transcript = Keccak256Transcript::new(b"TestEval");
+        let arg =
+          EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+        (comm, point, eval, arg)
+      })
+      .collect::<Vec<_>>();
+    let weights = (0..instances.len())
+      .map(|_| Fr::random(&mut rng))
+      .collect::<Vec<Fr>>();
+
+    // for every subset of claims with a wrong evaluation, the batch of deferred checks is
+    // accepted exactly when each argument is accepted by `verify`
+    for mask in 0..1 << instances.len() {
+      let mut all_valid = true;
+      let checks = instances
+        .iter()
+        .enumerate()
+        .map(|(i, (comm, point, eval, arg))| {
+          let eval = if mask >> i & 1 == 1 {
+            *eval + Fr::ONE
+          } else {
+            *eval
+          };
+
+          let mut transcript = Keccak256Transcript::new(b"TestEval");
+          let valid =
+            EvaluationEngine::verify(&vk, &mut transcript, comm, point, &eval, arg).is_ok();
+          all_valid &= valid;
+
+          let mut transcript = Keccak256Transcript::new(b"TestEval");
+          let check =
+            EvaluationEngine::verify_deferred(&vk, &mut transcript, comm, point, &eval, arg)
+              .unwrap();
+          assert_eq!(check.check().is_ok(), valid);
+          check
+        })
+        .collect::<Vec<_>>();
+
+      assert_eq!(all_valid, mask == 0);
+      let aggregate = DeferredCheck::aggregate(&checks, &weights).unwrap();
+      assert_eq!(aggregate.check().is_ok(), all_valid);
+    }
+  }
+
+  #[test]
+  fn test_ipa_verify_and_accumulate() {
+    let num_vars = 3;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(25);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+
+    let mut acc = CheckAccumulator::new();
+    let mut proofs = Vec::new();
+    for _ in 0..3 {
+      let (poly, point, eval) = random_instance(num_vars, &mut rng);
+      let comm = CE::<E>::commit(&ck, &poly);
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let arg =
+        EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert!(EvaluationEngine::verify_and_accumulate(
+        &vk,
+        &mut transcript,
+        &comm,
+        &point,
+        &eval,
+        &arg,
+        &mut acc
+      )
+      .is_ok());
+      proofs.push((comm, point, eval, arg));
+    }
+    assert_eq!(acc.len(), 3);
+    assert!(acc.check().is_ok());
+
+    // a tampered proof fails individually and is not accumulated
+    let (comm, point, eval, mut arg) = proofs.pop().unwrap();
+    arg.a_hat += Fr::ONE;
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_and_accumulate(
+        &vk,
+        &mut transcript,
+        &comm,
+        &point,
+        &eval,
+        &arg,
+        &mut acc
+      ),
+      Err(NovaError::FinalCheckFailed)
+    );
+    assert_eq!(acc.len(), 3);
+    assert!(acc.check().is_ok());
+
+    // and its final check makes the batch fail
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    acc.push(
+      EvaluationEngine::verify_deferred(&vk, &mut transcript, &comm, &point, &eval, &arg).unwrap(),
+    );
+    assert_eq!(acc.check(), Err(NovaError::FinalCheckFailed));
+  }
+
+  #[test]
+  fn test_ipa_verify_many() {
+    let num_proofs = 16;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(5);
+    let ck = CE::<E>::setup(b"test", 1 << 5);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+
+    // unrelated proofs of different sizes, produced one after another on a single transcript
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let mut instances = (0..num_proofs)
+      .map(|i| {
+        let (poly, point, eval) = random_instance(1 + i % 5, &mut rng);
+        let comm = CE::<E>::commit(&ck, &poly);
+        let arg =
+          EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+        (comm, point, eval, arg)
+      })
+      .collect::<Vec<_>>();
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify_many(&vk, &instances, &mut transcript).is_ok());
+
+    // a single invalid evaluation makes the batch fail
+    instances[7].2 += Fr::ONE;
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_many(&vk, &instances, &mut transcript),
+      Err(NovaError::FinalCheckFailed)
+    );
+  }
+
+  #[test]
+  fn test_ipa_verify_independent() {
+    let num_proofs = 6;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(74);
+    let ck = CE::<E>::setup(b"test", 1 << 4);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+
+    // unrelated proofs of different sizes, each produced on its own transcript
+    let (mut comms, mut points, mut evals, mut args) = (vec![], vec![], vec![], vec![]);
+    for i in 0..num_proofs {
+      let (poly, point, eval) = random_instance(1 + i % 4, &mut rng);
+      let comm = CE::<E>::commit(&ck, &poly);
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      transcript.absorb(b"i", &Fr::from(i as u64));
+      let arg =
+        EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+      comms.push(comm);
+      points.push(point);
+      evals.push(eval);
+      args.push(arg);
+    }
+    let transcripts = || {
+      (0..num_proofs)
+        .map(|i| {
+          let mut transcript = Keccak256Transcript::new(b"TestEval");
+          transcript.absorb(b"i", &Fr::from(i as u64));
+          transcript
+        })
+        .collect::<Vec<_>>()
+    };
+
+    assert!(EvaluationEngine::verify_independent(
+      &vk,
+      &mut transcripts(),
+      &comms,
+      &points,
+      &evals,
+      &args
+    )
+    .is_ok());
+
+    // the batch fails if exactly one of its arguments is invalid
+    for i in 0..num_proofs {
+      let mut tampered = evals.clone();
+      tampered[i] += Fr::ONE;
+      assert_eq!(
+        EvaluationEngine::verify_independent(
+          &vk,
+          &mut transcripts(),
+          &comms,
+          &points,
+          &tampered,
+          &args
+        ),
+        Err(NovaError::FinalCheckFailed)
+      );
+    }
+
+    // each argument is bound to its own transcript
+    let mut swapped = transcripts();
+    swapped.swap(0, 4);
+    assert!(EvaluationEngine::verify_independent(
+      &vk,
+      &mut swapped,
+      &comms,
+      &points,
+      &evals,
+      &args
+    )
+    .is_err());
+
+    assert!(EvaluationEngine::<E>::verify_independent(&vk, &mut [], &[], &[], &[], &[]).is_ok());
+    assert_eq!(
+      EvaluationEngine::verify_independent(
+        &vk,
+        &mut transcripts(),
+        &comms,
+        &points,
+        &evals[1..],
+        &args
+      ),
+      Err(NovaError::InvalidInputLength)
+    );
+  }
+
+  #[test]
+  fn test_ipa_aggregate() {
+    let num_proofs = 8;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(59);
+    let ck = CE::<E>::setup(b"test", 1 << 4);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let instances = (0..num_proofs)
+      .map(|i| {
+        let (poly, point, eval) = random_instance(1 + i % 4, &mut rng);
+        let comm = CE::<E>::commit(&ck, &poly);
+        let arg =
+          EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+        (comm, point, eval, arg)
+      })
+      .collect::<Vec<_>>();
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let acc = EvaluationEngine::aggregate(&vk, &instances, &mut transcript).unwrap();
+    assert_eq!(acc.len(), num_proofs);
+    assert!(EvaluationEngine::verify_accumulated(&acc).is_ok());
+
+    // the accumulator is rejected if any one of the arguments is invalid
+    for i in 0..num_proofs {
+      let mut tampered = instances.clone();
+      tampered[i].2 += Fr::ONE;
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let acc = EvaluationEngine::aggregate(&vk, &tampered, &mut transcript).unwrap();
+      assert_eq!(
+        EvaluationEngine::verify_accumulated(&acc),
+        Err(NovaError::FinalCheckFailed)
+      );
+    }
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::aggregate(&vk, &[], &mut transcript).is_err());
+  }
+
+  #[test]
+  fn test_ipa_verify_many_with_soundness() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(6);
+    let ck = CE::<E>::setup(b"test", 1 << 3);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let mut instances = (0..4)
+      .map(|_| {
+        let (poly, point, eval) = random_instance(3, &mut rng);
+        let comm = CE::<E>::commit(&ck, &poly);
+        let arg =
+          EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+        (comm, point, eval, arg)
+      })
+      .collect::<Vec<_>>();
+
+    let verify = |instances: &[_], target_bits| {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      EvaluationEngine::verify_many_with_soundness(&vk, instances, &mut transcript, target_bits)
+    };
+
+    // tighter bounds use wider challenges, and then more combinations, and still verify
+    let mut challenge_bits = 0;
+    for target_bits in [32, 64, 128, Fr::CAPACITY] {
+      let report = verify(&instances, target_bits).unwrap();
+      assert_eq!(report.num_repetitions, 1);
+      assert!(report.soundness_bits >= target_bits);
+      assert!(report.challenge_bits > challenge_bits);
+      challenge_bits = report.challenge_bits;
+    }
+    let report = verify(&instances, Fr::CAPACITY + 1).unwrap();
+    assert_eq!(report.num_repetitions, 2);
+    assert!(report.soundness_bits > Fr::CAPACITY);
+
+    assert_eq!(verify(&instances, 0), Err(NovaError::InvalidInputLength));
+
+    instances[2].2 += Fr::ONE;
+    assert_eq!(verify(&instances, 64), Err(NovaError::FinalCheckFailed));
+  }
+
+  #[test]
+  fn test_truncate() {
+    let x = Fr::from(0b1011_0110);
+    assert_eq!(truncate(&x, 4), Fr::from(0b0110));
+    assert_eq!(truncate(&x, 64), x);
+    assert_eq!(truncate(&-Fr::ONE, Fr::NUM_BITS), -Fr::ONE);
+  }
+
+  #[test]
+  fn test_ipa_without_dom_sep() {
+    let num_vars = 3;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(8);
+    let (ck, pk, vk, poly, point, eval, comm, arg) = prove_random_instance(num_vars, &mut rng);
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg_without_dom_sep = EvaluationEngine::prove_without_dom_sep(
+      &ck,
+      &pk,
+      &mut transcript,
+      &comm,
+      &poly,
+      &point,
+      &eval,
+    )
+    .unwrap();
+
+    // both modes round-trip
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg).is_ok());
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify_without_dom_sep(
+      &vk,
+      &mut transcript,
+      &comm,
+      &point,
+      &eval,
+      &arg_without_dom_sep
+    )
+    .is_ok());
+
+    // the modes produce different transcripts, so they do not verify each other's arguments
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify(
+      &vk,
+      &mut transcript,
+      &comm,
+      &point,
+      &eval,
+      &arg_without_dom_sep
+    )
+    .is_err());
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify_without_dom_sep(
+      &vk,
+      &mut transcript,
+      &comm,
+      &point,
+      &eval,
+      &arg
+    )
+    .is_err());
+  }
+
+  #[test]
+  fn test_ipa_verify_with_challenges() {
+    let num_vars = 3;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+    let (ck, pk, vk, _, point, eval, comm, arg) = prove_random_instance(num_vars, &mut rng);
+
+    // an outer protocol derives the challenges once and shares them between the IPA and a
+    // second sub-argument: here, an opening of another polynomial at the folding challenges
+    let (other_poly, _, _) = random_instance(num_vars, &mut rng);
+    let other_comm = CE::<E>::commit(&ck, &other_poly);
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let challenges =
+      EvaluationEngine::challenges(&vk, &mut transcript, &comm, &point, &eval, &arg).unwrap();
+    let other_eval = MultilinearPolynomial::evaluate_with(&other_poly, &challenges.r_vec);
+    let other_arg = EvaluationEngine::prove(
+      &ck,
+      &pk,
+      &mut transcript,
+      &other_comm,
+      &other_poly,
+      &challenges.r_vec,
+      &other_eval,
+    )
+    .unwrap();
+
+    let verify_composed = |eval: &Fr, other_eval: &Fr| {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let challenges =
+        EvaluationEngine::challenges(&vk, &mut transcript, &comm, &point, eval, &arg)?;
+      EvaluationEngine::verify_with_challenges(&vk, &challenges, &comm, &point, eval, &arg)?;
+      EvaluationEngine::verify(
+        &vk,
+        &mut transcript,
+        &other_comm,
+        &challenges.r_vec,
+        other_eval,
+        &other_arg,
+      )
+    };
+    assert!(verify_composed(&eval, &other_eval).is_ok());
+
+    // the composed check fails if either sub-argument is tampered with
+    assert!(verify_composed(&(eval + Fr::ONE), &other_eval).is_err());
+    assert!(verify_composed(&eval, &(other_eval + Fr::ONE)).is_err());
+
+    // the challenges match the ones derived by `verify`, so both see the same transcript
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg).is_ok());
+
+    // the argument does not verify with other challenges
+    let mut bad_challenges = challenges.clone();
+    bad_challenges.r_vec[0] += Fr::ONE;
+    assert_eq!(
+      EvaluationEngine::verify_with_challenges(&vk, &bad_challenges, &comm, &point, &eval, &arg),
+      Err(NovaError::FinalCheckFailed)
+    );
+
+    bad_challenges.r_vec.pop();
+    assert_eq!(
+      EvaluationEngine::verify_with_challenges(&vk, &bad_challenges, &comm, &point, &eval, &arg),
+      Err(NovaError::ProofLengthMismatch)
+    );
+  }
+
+  #[test]
+  fn test_ipa_degenerate_challenge() {
+    let num_vars = 3;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+    let (_, _, vk, _, point, eval, comm, arg) = prove_random_instance(num_vars, &mut rng);
+
+    // a rigged transcript that squeezes a zero folding challenge
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let mut challenges =
+      EvaluationEngine::challenges(&vk, &mut transcript, &comm, &point, &eval, &arg).unwrap();
+    challenges.r_vec[1] = Fr::ZERO;
+    assert_eq!(
+      EvaluationEngine::verify_with_challenges(&vk, &challenges, &comm, &point, &eval, &arg),
+      Err(NovaError::DegenerateChallenge)
+    );
+
+    assert_eq!(
+      batch_invert(&[Fr::ONE, Fr::ZERO]),
+      Err(NovaError::DegenerateChallenge)
+    );
+  }
+
+  #[test]
+  fn test_inner_product() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(52);
+    for n in [0u64, 1, 7, 64] {
+      // `<(1, ..., n), (1, ..., n)>` is the sum of the first `n` squares
+      let a = (1..=n).map(Fr::from).collect::<Vec<Fr>>();
+      assert_eq!(
+        inner_product(&a, &a),
+        Fr::from(n * (n + 1) * (2 * n + 1) / 6)
+      );
+
+      // the inner product with a random vector is linear in each side
+      let b = (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>();
+      let c = (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>();
+      let b_plus_c = b
+        .iter()
+        .zip(c.iter())
+        .map(|(b, c)| *b + c)
+        .collect::<Vec<Fr>>();
+      assert_eq!(
+        inner_product(&a, &b_plus_c),
+        inner_product(&a, &b) + inner_product(&a, &c)
+      );
+    }
+  }
+
+  #[test]
+  fn test_tensor_vector() {
+    // the sequential recurrence that `tensor_vector` expands in parallel
+    fn tensor_vector_sequential(r_square: &[Fr], r_inverse: &[Fr]) -> Vec<Fr> {
+      let m = r_square.len();
+      let n = 1 << m;
+      let mut s = vec![Fr::ZERO; n];
+      s[0] = r_inverse.iter().product();
+      for i in 1..n {
+        let pos_in_r = (31 - (i as u32).leading_zeros()) as usize;
+        s[i] = s[i - (1 << pos_in_r)] * r_square[(m - 1) - pos_in_r];
+      }
+      s
+    }
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(44);
+    for m in [0, 1, 2, 5, 16] {
+      let r = (0..m).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>();
+      let r_square = r.iter().map(|r_i| r_i.square()).collect::<Vec<Fr>>();
+      let r_inverse = batch_invert(&r).unwrap();
+      assert_eq!(
+        tensor_vector(&r_square, &r_inverse),
+        tensor_vector_sequential(&r_square, &r_inverse)
+      );
+    }
+  }
+
+  #[test]
+  fn test_ipa_size_estimate() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(43);
+    for num_vars in [0, 2, 4, 8] {
+      let ck = CE::<E>::setup(b"test", 1 << num_vars);
+      let (pk, _vk) = EvaluationEngine::<E>::setup(&ck);
+      let (poly, point, eval) = random_instance(num_vars, &mut rng);
+      let comm = CE::<E>::commit(&ck, &poly);
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let arg =
+        EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+
+      // the size is logarithmic in the length of the polynomial
+      assert_eq!(arg.num_rounds(), num_vars);
+      assert_eq!(arg.size_estimate(), 32 * (2 * num_vars + 2));
+      assert_eq!(arg.size_estimate(), arg.to_compact_bytes().len());
+    }
+
+    // the compressed points of secp256k1 take one more byte than an element of its base field
+    type E2 = crate::provider::Secp256k1Engine;
+    let ck = CE::<E2>::setup(b"test", 4);
+    let (pk, _vk) = EvaluationEngine::<E2>::setup(&ck);
+    let poly = (0..4).map(|i| (i as u64).into()).collect::<Vec<_>>();
+    let point = vec![2.into(), 3.into()];
+    let eval = MultilinearPolynomial::evaluate_with(&poly, &point);
+    let comm = CE::<E2>::commit(&ck, &poly);
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg =
+      EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+    assert_eq!(arg.size_estimate(), 4 * 33 + 32 + 32);
+    assert_eq!(arg.size_estimate(), arg.to_compact_bytes().len());
+  }
+
+  #[test]
+  fn test_ipa_evm_calldata() {
+    type E = crate::provider::Bn256EngineIPA;
+    type Fr = <E as Engine>::Scalar;
+
+    let ck = CE::<E>::setup(b"test", 2);
+    let (pk, _vk) = EvaluationEngine::<E>::setup(&ck);
+    let poly = [Fr::from(1), Fr::from(2)];
+    let point = [Fr::from(3)];
+    let eval = MultilinearPolynomial::evaluate_with(&poly, &point);
+    let comm = CE::<E>::commit(&ck, &poly);
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg =
+      EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+
+    let calldata = arg.to_evm_calldata();
+    assert_eq!(calldata.len(), 32 * 6);
+
+    // the words are the big-endian coordinates of `L` and `R`, followed by `a_hat` and the
+    // digest of the instance
+    let (x, y, _) = arg.L_vec[0].to_coordinates();
+    let mut x_be = x.to_repr().as_ref().to_vec();
+    x_be.reverse();
+    let mut y_be = y.to_repr().as_ref().to_vec();
+    y_be.reverse();
+    assert_eq!(&calldata[..32], x_be.as_slice());
+    assert_eq!(&calldata[32..64], y_be.as_slice());
+    let mut a_hat_be = arg.a_hat.to_repr().as_ref().to_vec();
+    a_hat_be.reverse();
+    assert_eq!(&calldata[128..160], a_hat_be.as_slice());
+    assert_eq!(&calldata[160..], arg.instance_digest.as_slice());
+
+    // a fixed vector, which the Solidity verifier is tested against
+    assert_eq!(hex::encode(&calldata), EVM_CALLDATA);
+  }
+
+  // the calldata of the argument in `test_ipa_evm_calldata`
+  const EVM_CALLDATA: &str = concat!(
+    "201f26846a430cd01f5970e260f71084a9653fe6b35eadd171cb0042118c278e", // L.x
+    "1972828dc4cf427c0f5fec051c4c0061ce9a50b34c4da308afc5ebf263ca14f5", // L.y
+    "112eb310dee5d7bda55400234a070325930cdfb1e1c768a329ebfff26a4f66e3", // R.x
+    "0e893643a16964f7d427f9c8b74ff5699cbbb29e4336372d94dd9d59a71df957", // R.y
+    "1fe8cd638d38603abdabd1ec53c7ce36db088cee5c9486e784eb7ec10694cf07", // a_hat
+    "1135b70dc3d1df598c1cac07f6f9c2aaee980c1af87dadddaae9890d1ced1ed8", // instance digest
+  );
+
+  #[test]
+  fn test_ipa_univariate() {
+    // a polynomial of degree 7 has 8 coefficients
+    let n = 8;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(2);
+    let ck = CE::<E>::setup(b"test", n);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+
+    let coeffs = (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>();
+    let comm = CE::<E>::commit(&ck, &coeffs);
+    let zeta = Fr::random(&mut rng);
+    let eval = coeffs.iter().rev().fold(Fr::ZERO, |acc, c| acc * zeta + c);
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg =
+      EvaluationEngine::prove_univariate(&ck, &pk, &mut transcript, &comm, &coeffs, &zeta, &eval)
+        .unwrap();
+
+    let verify = |zeta: &Fr, eval: &Fr| {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      EvaluationEngine::verify_univariate(&vk, &mut transcript, &comm, n, zeta, eval, &arg)
+    };
+    assert!(verify(&zeta, &eval).is_ok());
+    assert_eq!(
+      verify(&zeta, &(eval + Fr::ONE)),
+      Err(NovaError::FinalCheckFailed)
+    );
+    assert_eq!(
+      verify(&(zeta + Fr::ONE), &eval),
+      Err(NovaError::InstanceMismatch)
+    );
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::prove_univariate(
+        &ck,
+        &pk,
+        &mut transcript,
+        &comm,
+        &coeffs[..n - 1],
+        &zeta,
+        &eval
+      )
+      .err(),
+      Some(NovaError::InvalidInputLength)
+    );
+  }
+
+  #[test]
+  fn test_ipa_public_addend() {
+    let num_vars = 3;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(33);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+
+    let poly = (0..1 << num_vars)
+      .map(|_| Fr::random(&mut rng))
+      .collect::<Vec<Fr>>();
+    let public_poly = (0..1 << num_vars)
+      .map(|_| Fr::random(&mut rng))
+      .collect::<Vec<Fr>>();
+    let point = (0..num_vars)
+      .map(|_| Fr::random(&mut rng))
+      .collect::<Vec<Fr>>();
+    let comm = CE::<E>::commit(&ck, &poly);
+
+    // the claimed evaluation is that of the sum, which is shifted by the public addend
+    let private_eval = MultilinearPolynomial::evaluate_with(&poly, &point);
+    let eval = private_eval + MultilinearPolynomial::evaluate_with(&public_poly, &point);
+    let sum = poly
+      .iter()
+      .zip(public_poly.iter())
+      .map(|(f, g)| *f + g)
+      .collect::<Vec<Fr>>();
+    assert_eq!(eval, MultilinearPolynomial::evaluate_with(&sum, &point));
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg = EvaluationEngine::prove_with_public_addend(
+      &ck,
+      &pk,
+      &mut transcript,
+      &comm,
+      &poly,
+      &public_poly,
+      &point,
+      &eval,
+    )
+    .unwrap();
+
+    let verify = |public_poly: &[Fr], eval: &Fr| {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      EvaluationEngine::verify_with_public_addend(
+        &vk,
+        &mut transcript,
+        &comm,
+        public_poly,
+        &point,
+        eval,
+        &arg,
+      )
+    };
+    assert!(verify(&public_poly, &eval).is_ok());
+    assert_eq!(
+      verify(&public_poly, &private_eval),
+      Err(NovaError::FinalCheckFailed)
+    );
+    assert_eq!(verify(&poly, &eval), Err(NovaError::FinalCheckFailed));
+    assert_eq!(
+      verify(&public_poly[..4], &eval),
+      Err(NovaError::InvalidInputLength)
+    );
+
+    // the argument is an ordinary evaluation argument for the private polynomial
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(
+      EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &private_eval, &arg).is_ok()
+    );
+  }
+
+  #[test]
+  fn test_ipa_shifted() {
+    let n = 8;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(19);
+    let ck = CE::<E>::setup(b"test", n);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+
+    // `f` has degree at most `n - 2`, and we commit to the coefficients of `x * f(x)`
+    let coeffs = (0..n - 1)
+      .map(|_| Fr::random(&mut rng))
+      .collect::<Vec<Fr>>();
+    let shifted_coeffs = iter::once(Fr::ZERO)
+      .chain(coeffs.iter().copied())
+      .collect::<Vec<Fr>>();
+    let comm = CE::<E>::commit(&ck, &shifted_coeffs);
+
+    for zeta in [Fr::random(&mut rng), Fr::ZERO] {
+      let eval = coeffs.iter().rev().fold(Fr::ZERO, |acc, c| acc * zeta + c);
+
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let arg = EvaluationEngine::prove_shifted(
+        &ck,
+        &pk,
+        &mut transcript,
+        &comm,
+        &shifted_coeffs,
+        &zeta,
+        &eval,
+      )
+      .unwrap();
+
+      let verify = |eval: &Fr| {
+        let mut transcript = Keccak256Transcript::new(b"TestEval");
+        EvaluationEngine::verify_shifted(&vk, &mut transcript, &comm, n, &zeta, eval, &arg)
+      };
+      assert!(verify(&eval).is_ok());
+      assert_eq!(verify(&(eval + Fr::ONE)), Err(NovaError::FinalCheckFailed));
+
+      // the evaluation of the shift itself is `zeta * f(zeta)`
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert!(EvaluationEngine::verify_univariate(
+        &vk,
+        &mut transcript,
+        &comm,
+        n,
+        &zeta,
+        &(zeta * eval),
+        &EvaluationEngine::prove_univariate(
+          &ck,
+          &pk,
+          &mut Keccak256Transcript::new(b"TestEval"),
+          &comm,
+          &shifted_coeffs,
+          &zeta,
+          &(zeta * eval),
+        )
+        .unwrap(),
+      )
+      .is_ok());
+    }
+  }
+
+  #[test]
+  fn test_ipa_hybrid() {
+    let num_vars = 5;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(20);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let (poly, point, eval) = random_instance(num_vars, &mut rng);
+    let comm = CE::<E>::commit(&ck, &poly);
+    let root = EvaluationEngine::<E>::merkle_root(&poly).unwrap();
+    let num_queries = 8;
+
+    let prove = |num_queries: usize| {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      EvaluationEngine::prove_hybrid(
+        &ck,
+        &pk,
+        &mut transcript,
+        &comm,
+        &root,
+        &poly,
+        &point,
+        &eval,
+        num_queries,
+      )
+    };
+    let arg = prove(num_queries).unwrap();
+
+    let verify = |comm: &Commitment<E>, root: &[u8; 32], eval: &Fr| {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      EvaluationEngine::verify_hybrid(
+        &vk,
+        &mut transcript,
+        comm,
+        root,
+        &point,
+        eval,
+        &arg,
+        num_queries,
+      )
+    };
+    assert!(verify(&comm, &root, &eval).is_ok());
+    assert!(verify(&comm, &root, &(eval + Fr::ONE)).is_err());
+
+    // the queries are at distinct indices
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let indices =
+      EvaluationEngine::<E>::hybrid_queries(&mut transcript, &comm, &root, num_vars, 1 << num_vars)
+        .unwrap();
+    assert_eq!(indices.iter().collect::<BTreeSet<_>>().len(), 1 << num_vars);
+
+    // the number of queries is bound to the argument
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_hybrid(
+        &vk,
+        &mut transcript,
+        &comm,
+        &root,
+        &point,
+        &eval,
+        &arg,
+        num_queries - 1,
+      ),
+      Err(NovaError::ProofLengthMismatch)
+    );
+    let arg_one = prove(1).unwrap();
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify_hybrid(
+      &vk,
+      &mut transcript,
+      &comm,
+      &root,
+      &point,
+      &eval,
+      &arg_one,
+      1,
+    )
+    .is_ok());
+    for num_queries in [0, (1 << num_vars) + 1] {
+      assert_eq!(
+        prove(num_queries).err(),
+        Some(NovaError::InvalidInputLength)
+      );
+    }
+
+    // both commitments are bound to the proof
+    let (poly_other, _, _) = random_instance(num_vars, &mut rng);
+    let root_other = EvaluationEngine::<E>::merkle_root(&poly_other).unwrap();
+    assert!(verify(&comm, &root_other, &eval).is_err());
+    assert!(verify(&CE::<E>::commit(&ck, &poly_other), &root, &eval).is_err());
+
+    // the prover must use the polynomial of the Merkle root
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::prove_hybrid(
+        &ck,
+        &pk,
+        &mut transcript,
+        &comm,
+        &root_other,
+        &poly,
+        &point,
+        &eval,
+        num_queries,
+      )
+      .err(),
+      Some(NovaError::InvalidMerklePath)
+    );
+  }
+
+  #[test]
+  fn test_ipa_poly_equal() {
+    let num_vars = 4;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(4);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+
+    let test_inner = |f: &[Fr], g: &[Fr]| -> Result<(), NovaError> {
+      let comm_f = CE::<E>::commit(&ck, f);
+      let comm_g = CE::<E>::commit(&ck, g);
+
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let arg =
+        EvaluationEngine::prove_poly_equal(&ck, &pk, &mut transcript, &comm_f, &comm_g, f, g)?;
+
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      EvaluationEngine::verify_poly_equal(&vk, &mut transcript, &comm_f, &comm_g, num_vars, &arg)
+    };
+
+    let f = (0..(1 << num_vars))
+      .map(|_| Fr::random(&mut rng))
+      .collect::<Vec<Fr>>();
+    assert!(test_inner(&f, &f.clone()).is_ok());
+
+    // polynomials that differ in a single coefficient are caught
+    for i in [0, 5, (1 << num_vars) - 1] {
+      let mut g = f.clone();
+      g[i] += Fr::ONE;
+      assert_eq!(test_inner(&f, &g), Err(NovaError::FinalCheckFailed));
+    }
+
+    assert_eq!(
+      test_inner(&f, &f[..f.len() / 2]),
+      Err(NovaError::InvalidInputLength)
+    );
+  }
+
+  #[test]
+  fn test_ipa_open_matrix_columns() {
+    // the matrix holds the evaluations of a multilinear polynomial in row-major order, so the
+    // first variables select the row and the last ones select the column
+    let (num_row_vars, num_col_vars) = (3, 2);
+    let (rows, cols) = (1 << num_row_vars, 1 << num_col_vars);
+    let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+    let ck = CE::<E>::setup(b"test", rows);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+
+    let (data, point, eval) = random_instance(num_row_vars + num_col_vars, &mut rng);
+    let comms = ck.commit_matrix(rows, cols, &data).unwrap();
+    let (r_row, r_col) = point.split_at(num_row_vars);
+
+    // combining the column commitments with eq(r_col) commits to the matrix times eq(r_col),
+    // whose evaluation at r_row is the evaluation of the polynomial at the point
+    let eq_col = EqPolynomial::new(r_col.to_vec()).evals();
+    let comm = comms
+      .iter()
+      .zip(eq_col.iter())
+      .fold(Commitment::<E>::default(), |acc, (c, e)| acc + *c * *e);
+    let poly = (0..rows)
+      .map(|i| (0..cols).map(|j| data[i * cols + j] * eq_col[j]).sum())
+      .collect::<Vec<Fr>>();
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg =
+      EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, r_row, &eval).unwrap();
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify(&vk, &mut transcript, &comm, r_row, &eval, &arg).is_ok());
+  }
+
+  #[test]
+  fn test_ipa_prove_deferred() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(11);
+    for num_vars in [0, 1, 2, 5] {
+      let ck = CE::<E>::setup(b"test", 1 << num_vars);
+      let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+      let (poly, point, eval) = random_instance(num_vars, &mut rng);
+      let comm = CE::<E>::commit(&ck, &poly);
+
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let arg =
+        EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+
+      let mut transcript_deferred = Keccak256Transcript::new(b"TestEval");
+      let state = EvaluationEngine::prove_deferred(
+        &ck,
+        &pk,
+        &mut transcript_deferred,
+        &comm,
+        &poly,
+        &point,
+        &eval,
+      )
+      .unwrap();
+      assert_eq!(state.a_vec().len(), (1 << num_vars).min(4));
+      assert_eq!(state.b_vec().len(), state.a_vec().len());
+      let arg_deferred =
+        EvaluationEngine::finish_deferred(state, &mut transcript_deferred).unwrap();
+
+      assert_eq!(
+        bincode::serialize(&arg).unwrap(),
+        bincode::serialize(&arg_deferred).unwrap()
+      );
+      assert_eq!(
+        transcript.squeeze(b"c").unwrap(),
+        transcript_deferred.squeeze(b"c").unwrap()
+      );
+
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert!(
+        EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg_deferred).is_ok()
+      );
+    }
+  }
+
+  #[test]
+  fn test_ipa_at_challenge_point() {
+    let num_vars = 5;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(12);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let (poly, _, _) = random_instance(num_vars, &mut rng);
+    let comm = CE::<E>::commit(&ck, &poly);
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let (point, eval, arg) =
+      EvaluationEngine::prove_at_challenge_point(&ck, &pk, &mut transcript, &comm, &poly, num_vars)
+        .unwrap();
+    assert_eq!(eval, MultilinearPolynomial::evaluate_with(&poly, &point));
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let point_verifier = EvaluationEngine::verify_at_challenge_point(
+      &vk,
+      &mut transcript,
+      &comm,
+      num_vars,
+      &eval,
+      &arg,
+    )
+    .unwrap();
+    assert_eq!(point, point_verifier);
+
+    // the point is bound to the commitment
+    let comm_other = CE::<E>::commit(&ck, &random_instance(num_vars, &mut rng).0);
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify_at_challenge_point(
+      &vk,
+      &mut transcript,
+      &comm_other,
+      num_vars,
+      &eval,
+      &arg,
+    )
+    .is_err());
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::prove_at_challenge_point(
+        &ck,
+        &pk,
+        &mut transcript,
+        &comm,
+        &poly,
+        num_vars - 1
+      )
+      .err(),
+      Some(NovaError::InvalidInputLength)
+    );
+  }
+
+  #[test]
+  fn test_ipa_verify_with_commitment_digest() {
+    let num_vars = 3;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(13);
+    let (ck, _, vk, _, point, eval, comm, arg) = prove_random_instance(num_vars, &mut rng);
+
+    let verify = |comm_digest: &[u8; 32]| {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      EvaluationEngine::verify_with_commitment_digest(
+        &vk,
+        &mut transcript,
+        comm_digest,
+        &comm,
+        &point,
+        &eval,
+        &arg,
+      )
+    };
+
+    let comm_digest = EvaluationEngine::<E>::commitment_digest(&comm);
+    assert!(verify(&comm_digest).is_ok());
+
+    let comm_other = CE::<E>::commit(&ck, &random_instance(num_vars, &mut rng).0);
+    assert_eq!(
+      verify(&EvaluationEngine::<E>::commitment_digest(&comm_other)),
+      Err(NovaError::InvalidCommitmentDigest)
+    );
+    let mut comm_digest_tampered = comm_digest;
+    comm_digest_tampered[0] ^= 1;
+    assert_eq!(
+      verify(&comm_digest_tampered),
+      Err(NovaError::InvalidCommitmentDigest)
+    );
+  }
+
+  #[test]
+  fn test_ipa_prove_owned() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(14);
+    for num_vars in [0, 1, 4] {
+      let ck = CE::<E>::setup(b"test", 1 << num_vars);
+      let (pk, _vk) = EvaluationEngine::<E>::setup(&ck);
+      let (poly, point, eval) = random_instance(num_vars, &mut rng);
+      let comm = CE::<E>::commit(&ck, &poly);
+      let b_vec = EqPolynomial::new(point).evals();
+
+      let u = InnerProductInstance::<E>::new(&comm, &b_vec, &eval);
+      let w = InnerProductWitness::new(&poly);
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let arg = InnerProductArgument::prove(&ck, &pk.ck_s, &u, &w, &mut transcript).unwrap();
+
+      let mut transcript_owned = Keccak256Transcript::new(b"TestEval");
+      let arg_owned =
+        InnerProductArgument::prove_owned(&ck, &pk, u, w, &mut transcript_owned).unwrap();
+
+      assert_eq!(
+        bincode::serialize(&arg).unwrap(),
+        bincode::serialize(&arg_owned).unwrap()
+      );
+      assert_eq!(
+        transcript.squeeze(b"c").unwrap(),
+        transcript_owned.squeeze(b"c").unwrap()
+      );
+    }
+  }
+
+  #[test]
+  fn test_ipa_non_power_of_two() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(37);
+    for n in [3, 6, 12] {
+      let ck = CE::<E>::setup(b"test", n);
+      let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+      let a_vec = (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>();
+      let b_vec = (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>();
+      let c = inner_product(&a_vec, &b_vec);
+      let comm = CE::<E>::commit(&ck, &a_vec);
+
+      let u = InnerProductInstance::<E>::new(&comm, &b_vec, &c);
+      let w = InnerProductWitness::new(&a_vec);
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let arg = InnerProductArgument::prove(&ck, &pk.ck_s, &u, &w, &mut transcript).unwrap();
+
+      // the vectors are padded to the next power of two
+      assert_eq!(1 << arg.L_vec.len(), n.next_power_of_two());
+
+      let verify = |c: &Fr, n: usize| {
+        let u = InnerProductInstance::<E>::new(&comm, &b_vec[..n], c);
+        let mut transcript = Keccak256Transcript::new(b"TestEval");
+        arg.verify(&vk, n, &u, &mut transcript)
+      };
+      assert!(verify(&c, n).is_ok());
+      assert_eq!(verify(&(c + Fr::ONE), n), Err(NovaError::FinalCheckFailed));
+      assert!(verify(&c, n - 1).is_err());
+    }
+  }
+
+  #[test]
+  fn test_ipa_undersized_keys() {
+    let n = 8;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(49);
+    let ck = CE::<E>::setup(b"test", n);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let a_vec = (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>();
+    let b_vec = (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>();
+    let c = inner_product(&a_vec, &b_vec);
+    let comm = CE::<E>::commit(&ck, &a_vec);
+    let u = InnerProductInstance::<E>::new(&comm, &b_vec, &c);
+    let w = InnerProductWitness::new(&a_vec);
+
+    let (ck_short, _) = ck.split_at(n / 2);
+    let (ck_c_empty, _) = pk.ck_s.split_at(0);
+
+    for (ck, ck_c) in [(&ck_short, &pk.ck_s), (&ck, &ck_c_empty)] {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert_eq!(
+        InnerProductArgument::prove(ck, ck_c, &u, &w, &mut transcript).err(),
+        Some(NovaError::InvalidCommitmentKeyLength)
+      );
+    }
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg = InnerProductArgument::prove(&ck, &pk.ck_s, &u, &w, &mut transcript).unwrap();
+    for (ck_v, ck_s) in [(&ck_short, &vk.ck_s), (&vk.ck_v, &ck_c_empty)] {
+      let vk = VerifierKey {
+        ck_v: ck_v.clone(),
+        ck_s: ck_s.clone(),
+        ..vk.clone()
+      };
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert_eq!(
+        arg.verify(&vk, n, &u, &mut transcript),
+        Err(NovaError::InvalidCommitmentKeyLength)
+      );
+    }
+  }
+
+  #[test]
+  fn test_ipa_compressed_instance() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(22);
+    for num_vars in [0, 1, 5] {
+      let ck = CE::<E>::setup(b"test", 1 << num_vars);
+      let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+      let (poly, point, eval) = random_instance(num_vars, &mut rng);
+      let comm = CE::<E>::commit(&ck, &poly);
+      let b_vec = EqPolynomial::new(point.clone()).evals();
+
+      let u = InnerProductInstance::<E>::new_eq_with_evals(&comm, &point, &b_vec, &eval);
+      let w = InnerProductWitness::new(&poly);
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let arg = InnerProductArgument::prove(&ck, &pk.ck_s, &u, &w, &mut transcript).unwrap();
+
+      for c in [eval, eval + Fr::ONE] {
+        let u_full = InnerProductInstance::<E>::new_eq_with_evals(&comm, &point, &b_vec, &c);
+        let u_eq = InnerProductInstance::<E>::new_eq(&comm, &point, &c);
+        assert_eq!(u_eq.b_vec.len(), b_vec.len());
+
+        let mut transcript_full = Keccak256Transcript::new(b"TestEval");
+        let res_full = arg.verify(&vk, 1 << num_vars, &u_full, &mut transcript_full);
+        let mut transcript_eq = Keccak256Transcript::new(b"TestEval");
+        let res_eq = arg.verify(&vk, 1 << num_vars, &u_eq, &mut transcript_eq);
+
+        assert_eq!(res_full, res_eq);
+        assert_eq!(res_full.is_ok(), c == eval);
+        assert_eq!(
+          transcript_full.squeeze(b"c").unwrap(),
+          transcript_eq.squeeze(b"c").unwrap()
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn test_ipa_trivial_commitment() {
+    let num_vars = 3;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(23);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let poly = vec![Fr::ZERO; 1 << num_vars];
+    let point = (0..num_vars)
+      .map(|_| Fr::random(&mut rng))
+      .collect::<Vec<_>>();
+    let comm = CE::<E>::commit(&ck, &poly);
+    assert_eq!(comm, Commitment::<E>::default());
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg =
+      EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &Fr::ZERO).unwrap();
+
+    // the evaluation of the zero polynomial is accepted
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &Fr::ZERO, &arg).is_ok());
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &Fr::ONE, &arg),
+      Err(NovaError::TrivialCommitment)
+    );
+
+    // the other verifiers start with the same checks
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_deferred(&vk, &mut transcript, &comm, &point, &Fr::ONE, &arg).err(),
+      Some(NovaError::TrivialCommitment)
+    );
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_constant_time(&vk, &mut transcript, &comm, &point, &Fr::ONE, &arg),
+      Err(NovaError::TrivialCommitment)
+    );
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_without_dom_sep(&vk, &mut transcript, &comm, &point, &Fr::ONE, &arg),
+      Err(NovaError::TrivialCommitment)
+    );
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_streaming(&vk, &mut transcript, &comm, &point, &Fr::ONE, &arg, 2),
+      Err(NovaError::TrivialCommitment)
+    );
+    let pending = EvaluationEngine::verify_incremental(&vk, &point, &Fr::ONE, &arg).unwrap();
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      pending.finalize(&mut transcript, &comm),
+      Err(NovaError::TrivialCommitment)
+    );
+  }
+
+  #[test]
+  fn test_ipa_long_point() {
+    // a point whose length overflows `2^point.len()` is rejected by the shape of the argument
+    let num_vars = 2;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(77);
+    let (_, _, vk, _, _, eval, comm, arg) = prove_random_instance(num_vars, &mut rng);
+
+    let vk = vk.with_max_num_vars(usize::MAX);
+    let long_point = vec![Fr::ONE; 64];
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_deferred(&vk, &mut transcript, &comm, &long_point, &eval, &arg)
+        .err(),
+      Some(NovaError::ProofLengthMismatch)
+    );
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_without_dom_sep(
+        &vk,
+        &mut transcript,
+        &comm,
+        &long_point,
+        &eval,
+        &arg
+      ),
+      Err(NovaError::ProofLengthMismatch)
+    );
+    assert_eq!(
+      EvaluationEngine::verify_incremental(&vk, &long_point, &eval, &arg).err(),
+      Some(NovaError::ProofLengthMismatch)
+    );
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::challenges(&vk, &mut transcript, &comm, &long_point, &eval, &arg).err(),
+      Some(NovaError::ProofLengthMismatch)
+    );
+  }
+
+  #[test]
+  fn test_ipa_max_num_vars() {
+    let num_vars = 3;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(26);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let (poly, point, eval) = random_instance(num_vars, &mut rng);
+    let comm = CE::<E>::commit(&ck, &poly);
+
+    // a point with 40 coordinates is rejected before allocating `2^40` evaluations
+    let huge_point = vec![Fr::ONE; 40];
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &huge_point, &eval).err(),
+      Some(NovaError::InputTooLarge)
+    );
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg =
+      EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+
+    // the limit is configurable
+    let pk = pk.with_max_num_vars(num_vars - 1);
+    let vk_small = vk.clone().with_max_num_vars(num_vars - 1);
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).err(),
+      Some(NovaError::InputTooLarge)
+    );
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify(&vk_small, &mut transcript, &comm, &point, &eval, &arg),
+      Err(NovaError::InputTooLarge)
+    );
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg).is_ok());
+  }
+
+  #[test]
+  fn test_ipa_verify_checks_shape_first() {
+    let num_vars = 2;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(66);
+    let (_, _, vk, _, point, eval, comm, arg) = prove_random_instance(num_vars, &mut rng);
+
+    // a 2-round argument at a point with 24 coordinates is rejected without computing anything
+    // of length 2^24, and leaves the transcript untouched
+    let long_point = vec![Fr::ONE; 24];
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify(&vk, &mut transcript, &comm, &long_point, &eval, &arg),
+      Err(NovaError::ProofLengthMismatch)
+    );
+    let mut fresh = Keccak256Transcript::<E>::new(b"TestEval");
+    assert_eq!(
+      transcript.squeeze(b"c").unwrap(),
+      fresh.squeeze(b"c").unwrap()
+    );
+
+    let mut arg_short = arg.clone();
+    arg_short.L_vec.pop();
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg_short),
+      Err(NovaError::ProofLengthMismatch)
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "parallel")]
+  fn test_ipa_prove_in_pool() {
+    let num_vars = 6;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(36);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let (poly, point, eval) = random_instance(num_vars, &mut rng);
+    let comm = CE::<E>::commit(&ck, &poly);
+
+    let pool = rayon::ThreadPoolBuilder::new()
+      .num_threads(2)
+      .build()
+      .unwrap();
+    assert_eq!(pool.install(rayon::current_num_threads), 2);
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg = EvaluationEngine::prove_in_pool(
+      &pool,
+      &ck,
+      &pk,
+      &mut transcript,
+      &comm,
+      &poly,
+      &point,
+      &eval,
+    )
+    .unwrap();
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg).is_ok());
+
+    // the argument does not depend on the number of threads
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let expected =
+      EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+    assert_eq!(arg.to_bytes(), expected.to_bytes());
+  }
+
+  #[test]
+  fn test_ipa_prove_in_place() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(45);
+    for num_vars in [0, 1, 6] {
+      // the key is longer than the polynomial, so that only a prefix of it is folded
+      let ck = CE::<E>::setup(b"test", 2 << num_vars);
+      let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+      let (poly, point, eval) = random_instance(num_vars, &mut rng);
+      let comm = CE::<E>::commit(&ck, &poly);
+
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let expected =
+        EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let arg = EvaluationEngine::prove_in_place(
+        &ck,
+        &pk,
+        &mut transcript,
+        &comm,
+        poly.clone(),
+        &point,
+        &eval,
+      )
+      .unwrap();
+      assert_eq!(arg.to_bytes(), expected.to_bytes());
+
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert!(EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg).is_ok());
+    }
+
+    // folding a key in place yields the same generators as folding it into a new key
+    let ck = CE::<E>::setup(b"test", 8);
+    let (w1, w2) = (Fr::random(&mut rng), Fr::random(&mut rng));
+    let mut ck_in_place = ck.clone();
+    ck_in_place.fold_in_place(&w1, &w2);
+    assert_eq!(ck_in_place, ck.fold(&w1, &w2));
+  }
+
+  #[test]
+  fn test_ipa_prove_sparse() {
+    let num_vars = 8;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(53);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let point = (0..num_vars)
+      .map(|_| Fr::random(&mut rng))
+      .collect::<Vec<Fr>>();
+
+    // a few entries, among which two that are folded onto the same position in the first
+    // round; and a polynomial that is too dense for any sparse round
+    let few = vec![
+      (200, Fr::random(&mut rng)),
+      (3, Fr::random(&mut rng)),
+      (131, Fr::random(&mut rng)),
+      (72, Fr::ONE),
+    ];
+    let many = (0..1 << num_vars)
+      .step_by(3)
+      .map(|i| (i, Fr::random(&mut rng)))
+      .collect::<Vec<_>>();
+    for entries in [vec![], few, many] {
+      let mut poly = vec![Fr::ZERO; 1 << num_vars];
+      for (i, v) in &entries {
+        poly[*i] = *v;
+      }
+      let eval = MultilinearPolynomial::evaluate_with(&poly, &point);
+      let comm = CE::<E>::commit(&ck, &poly);
+      assert_eq!(ck.commit_sparse(&entries).unwrap(), comm);
+
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let expected =
+        EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let arg =
+        EvaluationEngine::prove_sparse(&ck, &pk, &mut transcript, &comm, &entries, &point, &eval)
+          .unwrap();
+      assert_eq!(arg.to_bytes(), expected.to_bytes());
+
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert!(EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg).is_ok());
+    }
+
+    for entries in [vec![(256, Fr::ONE)], vec![(5, Fr::ONE), (5, Fr::ONE)]] {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert_eq!(
+        EvaluationEngine::prove_sparse(
+          &ck,
+          &pk,
+          &mut transcript,
+          &Commitment::<E>::default(),
+          &entries,
+          &point,
+          &Fr::ZERO
+        )
+        .err(),
+        Some(NovaError::InvalidIndex)
+      );
+    }
+  }
+
+  // proves an evaluation like `EvaluationEngine::prove`, but absorbs the commitments of each
+  // round in the order `R` then `L`, as an implementation with the opposite convention would
+  fn prove_right_first(
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut Keccak256Transcript<E>,
+    comm: &Commitment<E>,
+    poly: &[Fr],
+    point: &[Fr],
+    eval: &Fr,
+  ) -> InnerProductArgument<E> {
+    let u = InnerProductInstance::<E>::new_eq(comm, point, eval);
+    let b_vec = u.b_vec.to_full();
+    let mut state =
+      InnerProductArgument::prove_start(ck, &pk.ck_s, &u, poly.to_vec(), b_vec, transcript)
+        .unwrap();
+
+    while state.a_vec.len() > 1 {
+      let n = state.a_vec.len();
+      let (ck_L, ck_R) = state.ck.split_at(n / 2);
+      let (a_L, a_R) = state.a_vec.split_at(n / 2);
+      let c_L = inner_product(a_L, &state.b_vec[n / 2..]);
+      let c_R = inner_product(a_R, &state.b_vec[..n / 2]);
+      let comm_L = CE::<E>::commit(&ck_R, a_L) + CE::<E>::commit(&state.ck_c, &[c_L]);
+      let comm_R = CE::<E>::commit(&ck_L, a_R) + CE::<E>::commit(&state.ck_c, &[c_R]);
+
+      transcript.absorb(b"R", &comm_R);
+      transcript.absorb(b"L", &comm_L);
+      let r = transcript.squeeze(b"r").unwrap();
+      let r_inverse = r.invert().unwrap();
+
+      state.ck = state.ck.fold(&r_inverse, &r);
+      fold_halves(&mut state.a_vec, &r, &r_inverse);
+      fold_halves(&mut state.b_vec, &r_inverse, &r);
+      state.L_vec.push(comm_L);
+      state.R_vec.push(comm_R);
+    }
+
+    InnerProductArgument {
+      L_vec: state.L_vec,
+      R_vec: state.R_vec,
+      a_hat: state.a_vec[0],
+      instance_digest: state.instance_digest,
+    }
+  }
+
+  #[test]
+  fn test_ipa_round_order() {
+    let num_vars = 4;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(54);
+    let (ck, pk, vk, poly, point, eval, comm, arg) = prove_random_instance(num_vars, &mut rng);
+    let vk_right_first = vk.clone().with_round_order(RoundOrder::RightFirst);
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg_right_first = prove_right_first(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval);
+
+    // each argument verifies under the order it was made with, and only under that order
+    for (arg, vk_ok, vk_other) in [
+      (&arg, &vk, &vk_right_first),
+      (&arg_right_first, &vk_right_first, &vk),
+    ] {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert!(EvaluationEngine::verify(vk_ok, &mut transcript, &comm, &point, &eval, arg).is_ok());
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert_eq!(
+        EvaluationEngine::verify(vk_other, &mut transcript, &comm, &point, &eval, arg),
+        Err(NovaError::FinalCheckFailed)
+      );
+    }
+
+    // the batched verifiers derive their challenges under the order of the key as well
+    let (poly_2, point_2, eval_2) = random_instance(num_vars, &mut rng);
+    let comm_2 = CE::<E>::commit(&ck, &poly_2);
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let instances = [
+      (&poly, &point, &eval, &comm),
+      (&poly_2, &point_2, &eval_2, &comm_2),
+    ]
+    .into_iter()
+    .map(|(poly, point, eval, comm)| {
+      let arg = prove_right_first(&ck, &pk, &mut transcript, comm, poly, point, eval);
+      (*comm, point.clone(), *eval, arg)
+    })
+    .collect::<Vec<_>>();
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify_many(&vk_right_first, &instances, &mut transcript).is_ok());
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_many(&vk, &instances, &mut transcript),
+      Err(NovaError::FinalCheckFailed)
+    );
+  }
+
+  #[test]
+  fn test_ipa_key_bound() {
+    let num_vars = 4;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(46);
+    let ck_A = CE::<E>::setup(b"test-A", 1 << num_vars);
+    let ck_B = CE::<E>::setup(b"test-B", 1 << num_vars);
+    let (pk, vk_A) = EvaluationEngine::<E>::setup(&ck_A);
+    let (_, vk_B) = EvaluationEngine::<E>::setup(&ck_B);
+    let (poly, point, eval) = random_instance(num_vars, &mut rng);
+    let comm = CE::<E>::commit(&ck_A, &poly);
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg =
+      EvaluationEngine::prove_key_bound(&ck_A, &pk, &mut transcript, &comm, &poly, &point, &eval)
+        .unwrap();
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(
+      EvaluationEngine::verify_key_bound(&vk_A, &mut transcript, &comm, &point, &eval, &arg)
+        .is_ok()
+    );
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_key_bound(&vk_B, &mut transcript, &comm, &point, &eval, &arg),
+      Err(NovaError::CommitmentKeyMismatch)
+    );
+
+    // claiming the other key's digest changes the challenges, so the argument is rejected
+    let mut tampered = arg.clone();
+    tampered.key_digest = vk_B.ck_v.digest();
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify_key_bound(
+      &vk_B,
+      &mut transcript,
+      &comm,
+      &point,
+      &eval,
+      &tampered
+    )
+    .is_err());
+  }
+
+  #[test]
+  fn test_ipa_instance_mismatch() {
+    let num_vars = 4;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(62);
+    let (_, _, vk, _, point, eval, comm, arg) = prove_random_instance(num_vars, &mut rng);
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg).is_ok());
+
+    // a verifier with another point reports the mismatch before deriving the challenges
+    let mut other_point = point.clone();
+    other_point[1] += Fr::ONE;
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify(&vk, &mut transcript, &comm, &other_point, &eval, &arg),
+      Err(NovaError::InstanceMismatch)
+    );
+
+    // claiming the verifier's digest changes the challenges, so the argument is rejected
+    let mut tampered = arg.clone();
+    tampered.instance_digest = InnerProductInstance::<E>::new_eq(&comm, &other_point, &eval)
+      .append_to_transcript(&mut Keccak256Transcript::new(b"TestEval"));
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify(&vk, &mut transcript, &comm, &other_point, &eval, &tampered),
+      Err(NovaError::FinalCheckFailed)
+    );
+  }
+
+  #[test]
+  fn test_ipa_linear_combination() {
+    let num_vars = 4;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(47);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let (f, point, f_eval) = random_instance(num_vars, &mut rng);
+    let g = (0..1 << num_vars)
+      .map(|_| Fr::random(&mut rng))
+      .collect::<Vec<Fr>>();
+    let g_eval = MultilinearPolynomial::evaluate_with(&g, &point);
+    let (comm_f, comm_g) = (CE::<E>::commit(&ck, &f), CE::<E>::commit(&ck, &g));
+
+    // h = 2 f - 3 g
+    let coeffs = [Fr::from(2), -Fr::from(3)];
+    let eval = coeffs[0] * f_eval + coeffs[1] * g_eval;
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg = EvaluationEngine::prove_linear_combination(
+      &ck,
+      &pk,
+      &mut transcript,
+      &[(comm_f, &f), (comm_g, &g)],
+      &coeffs,
+      &point,
+      &eval,
+    )
+    .unwrap();
+
+    let comms = [comm_f, comm_g];
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify_linear_combination(
+      &vk,
+      &mut transcript,
+      &comms,
+      &coeffs,
+      &point,
+      &eval,
+      &arg
+    )
+    .is_ok());
+
+    // the argument does not hold for other coefficients, nor for another evaluation
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify_linear_combination(
+      &vk,
+      &mut transcript,
+      &comms,
+      &[Fr::from(2), Fr::from(3)],
+      &point,
+      &eval,
+      &arg
+    )
+    .is_err());
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify_linear_combination(
+      &vk,
+      &mut transcript,
+      &comms,
+      &coeffs,
+      &point,
+      &(eval + Fr::ONE),
+      &arg
+    )
+    .is_err());
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_linear_combination(
+        &vk,
+        &mut transcript,
+        &comms,
+        &coeffs[..1],
+        &point,
+        &eval,
+        &arg
+      ),
+      Err(NovaError::InvalidInputLength)
+    );
+  }
+
+  #[test]
+  fn test_ipa_hadamard() {
+    let n = 8;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(35);
+    let ck = CE::<E>::setup(b"test", n);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+
+    let a = (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>();
+    let b = (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>();
+    let c = a
+      .iter()
+      .zip(b.iter())
+      .map(|(a, b)| *a * b)
+      .collect::<Vec<Fr>>();
+    let (comm_a, comm_b) = (CE::<E>::commit(&ck, &a), CE::<E>::commit(&ck, &b));
+
+    let prove_and_verify = |c: &[Fr]| {
+      let comm_c = CE::<E>::commit(&ck, c);
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let arg = EvaluationEngine::prove_hadamard(
+        &ck,
+        &pk,
+        &mut transcript,
+        &comm_a,
+        &comm_b,
+        &comm_c,
+        &a,
+        &b,
+        c,
+      )
+      .unwrap();
+
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      EvaluationEngine::verify_hadamard(&vk, &mut transcript, &comm_a, &comm_b, &comm_c, n, &arg)
+    };
+    assert!(prove_and_verify(&c).is_ok());
+
+    // a single wrong entry of the product is caught
+    let mut tampered = c.clone();
+    tampered[3] += Fr::ONE;
+    assert!(prove_and_verify(&tampered).is_err());
+  }
+
+  #[test]
+  fn test_ipa_sparse_correction() {
+    let num_vars = 4;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(41);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let (base_poly, point, base_eval) = random_instance(num_vars, &mut rng);
+    let base_comm = CE::<E>::commit(&ck, &base_poly);
+
+    // patch three entries, and evaluate the patched polynomial
+    let corrections = [1, 6, 15]
+      .iter()
+      .map(|i| (*i, Fr::random(&mut rng)))
+      .collect::<Vec<_>>();
+    let mut target = base_poly.clone();
+    for (i, delta) in &corrections {
+      target[*i] += delta;
+    }
+    let eval = MultilinearPolynomial::evaluate_with(&target, &point);
+    assert_ne!(eval, base_eval);
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg = EvaluationEngine::prove_with_sparse_correction(
+      &ck,
+      &pk,
+      &mut transcript,
+      &base_comm,
+      &base_poly,
+      &corrections,
+      &point,
+      &eval,
+    )
+    .unwrap();
+
+    let verify = |corrections: &[(usize, Fr)], eval: &Fr| {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      EvaluationEngine::verify_with_sparse_correction(
+        &vk,
+        &mut transcript,
+        &base_comm,
+        corrections,
+        &point,
+        eval,
+        &arg,
+      )
+    };
+    assert!(verify(&corrections, &eval).is_ok());
+    assert_eq!(
+      verify(&corrections, &base_eval),
+      Err(NovaError::FinalCheckFailed)
+    );
+    assert_eq!(
+      verify(&corrections[..2], &eval),
+      Err(NovaError::FinalCheckFailed)
+    );
+    assert_eq!(
+      verify(&[(16, Fr::ONE)], &eval),
+      Err(NovaError::InvalidIndex)
+    );
+  }
+
+  #[test]
+  fn test_ipa_sparse_correction_long_point() {
+    // a point with more coordinates than the bits of an index
+    let mut rng = rand::rngs::StdRng::seed_from_u64(75);
+    let point = (0..70).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+    let corrections = [(5, Fr::from(3)), (usize::MAX, Fr::from(7))];
+
+    let expected = corrections
+      .iter()
+      .map(|(i, delta)| {
+        let eq = point.iter().enumerate().fold(Fr::ONE, |acc, (j, p)| {
+          if (*i as u128 >> (point.len() - 1 - j)) & 1 == 1 {
+            acc * p
+          } else {
+            acc * (Fr::ONE - p)
+          }
+        });
+        eq * delta
+      })
+      .sum::<Fr>();
+    assert_eq!(
+      EvaluationEngine::<E>::sparse_correction_eval(&corrections, &point),
+      Ok(expected)
+    );
+  }
+
+  #[test]
+  fn test_ipa_fixed_size() {
+    let num_vars = 3;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(34);
+    let ck = CE::<E>::setup(b"test", 1 << (num_vars + 1));
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let vk = vk.with_fixed_size(1 << num_vars);
+
+    let verify = |num_vars: usize, rng: &mut rand::rngs::StdRng| {
+      let (poly, point, eval) = random_instance(num_vars, rng);
+      let comm = CE::<E>::commit(&ck, &poly);
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let arg =
+        EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg)
+    };
+    assert!(verify(num_vars, &mut rng).is_ok());
+
+    // arguments for vectors of any other length are rejected
+    assert_eq!(
+      verify(num_vars + 1, &mut rng),
+      Err(NovaError::ProofLengthMismatch)
+    );
+    assert_eq!(
+      verify(num_vars - 1, &mut rng),
+      Err(NovaError::ProofLengthMismatch)
+    );
+  }
+
+  #[test]
+  fn test_ipa_multi_point() {
+    let num_vars = 4;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(63);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let (poly, _, _) = random_instance(num_vars, &mut rng);
+    let comm = CE::<E>::commit(&ck, &poly);
+
+    // three random points and the all-zeros point, followed by a repeated point
+    let mut points = (0..3)
+      .map(|_| {
+        (0..num_vars)
+          .map(|_| Fr::random(&mut rng))
+          .collect::<Vec<Fr>>()
+      })
+      .collect::<Vec<_>>();
+    points.push(vec![Fr::ZERO; num_vars]);
+    points.push(points[1].clone());
+    let mut evals = points
+      .iter()
+      .map(|p| MultilinearPolynomial::evaluate_with(&poly, p))
+      .collect::<Vec<Fr>>();
+    assert_eq!(evals[3], poly[0]);
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg =
+      EvaluationEngine::prove_multi_point(&ck, &pk, &mut transcript, &comm, &poly, &points, &evals)
+        .unwrap();
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify_multi_point(
+      &vk,
+      &mut transcript,
+      &comm,
+      &points,
+      &evals,
+      &arg
+    )
+    .is_ok());
+
+    // a wrong evaluation at any of the points is rejected, including at the repeated point,
+    // against which the other copy keeps its correct evaluation
+    for i in 0..points.len() {
+      evals[i] += Fr::ONE;
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert!(EvaluationEngine::verify_multi_point(
+        &vk,
+        &mut transcript,
+        &comm,
+        &points,
+        &evals,
+        &arg
+      )
+      .is_err());
+      evals[i] -= Fr::ONE;
+    }
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_multi_point(&vk, &mut transcript, &comm, &points, &evals[1..], &arg),
+      Err(NovaError::InvalidInputLength)
+    );
+  }
+
+  #[test]
+  fn test_ipa_batch_heterogeneous() {
+    let num_vars = 5;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(27);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+
+    let point = (0..num_vars)
+      .map(|_| Fr::random(&mut rng))
+      .collect::<Vec<_>>();
+    let polys = [3, 5]
+      .iter()
+      .map(|k| {
+        (0..1 << k)
+          .map(|_| Fr::random(&mut rng))
+          .collect::<Vec<_>>()
+      })
+      .collect::<Vec<_>>();
+    let comms = polys
+      .iter()
+      .map(|poly| CE::<E>::commit(&ck, poly))
+      .collect::<Vec<_>>();
+    let evals = polys
+      .iter()
+      .map(|poly| {
+        let k = poly.len().ilog2() as usize;
+        MultilinearPolynomial::evaluate_with(poly, &point[num_vars - k..])
+      })
+      .collect::<Vec<_>>();
+    let polys = polys.iter().map(|poly| poly.as_slice()).collect::<Vec<_>>();
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg =
+      EvaluationEngine::prove_batch(&ck, &pk, &mut transcript, &comms, &polys, &point, &evals)
+        .unwrap();
+
+    let verify = |num_vars: &[usize], evals: &[Fr]| {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      EvaluationEngine::verify_batch(&vk, &mut transcript, &comms, num_vars, &point, evals, &arg)
+    };
+    assert!(verify(&[3, 5], &evals).is_ok());
+    assert!(verify(&[3, 5], &[evals[0] + Fr::ONE, evals[1]]).is_err());
+    assert!(verify(&[5, 5], &evals).is_err());
+    assert_eq!(verify(&[3, 6], &evals), Err(NovaError::InvalidInputLength));
+  }
+
+  #[test]
+  fn test_ipa_batch_shared_point() {
+    let (num_vars, num_polys) = (6, 4);
+    let mut rng = rand::rngs::StdRng::seed_from_u64(38);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+
+    let point = (0..num_vars)
+      .map(|_| Fr::random(&mut rng))
+      .collect::<Vec<_>>();
+    let polys = (0..num_polys)
+      .map(|_| {
+        (0..1 << num_vars)
+          .map(|_| Fr::random(&mut rng))
+          .collect::<Vec<_>>()
+      })
+      .collect::<Vec<_>>();
+    let comms = polys
+      .iter()
+      .map(|poly| CE::<E>::commit(&ck, poly))
+      .collect::<Vec<_>>();
+    let evals = polys
+      .iter()
+      .map(|poly| MultilinearPolynomial::evaluate_with(poly, &point))
+      .collect::<Vec<_>>();
+    let poly_refs = polys.iter().map(|poly| poly.as_slice()).collect::<Vec<_>>();
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg = EvaluationEngine::prove_batch(
+      &ck,
+      &pk,
+      &mut transcript,
+      &comms,
+      &poly_refs,
+      &point,
+      &evals,
+    )
+    .unwrap();
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let num_vars_batch = vec![num_vars; num_polys];
+    assert!(EvaluationEngine::verify_batch(
+      &vk,
+      &mut transcript,
+      &comms,
+      &num_vars_batch,
+      &point,
+      &evals,
+      &arg
+    )
+    .is_ok());
+
+    // the batched argument has the size of a single one
+    let individual_len = polys
+      .iter()
+      .zip(comms.iter().zip(evals.iter()))
+      .map(|(poly, (comm, eval))| {
+        let mut transcript = Keccak256Transcript::new(b"TestEval");
+        EvaluationEngine::prove(&ck, &pk, &mut transcript, comm, poly, &point, eval)
+          .unwrap()
+          .to_bytes()
+          .len()
+      })
+      .sum::<usize>();
+    assert_eq!(arg.to_bytes().len() * num_polys, individual_len);
+    assert!(arg.to_bytes().len() < individual_len);
+  }
+
+  #[test]
+  fn test_ipa_known_coeffs() {
+    let num_vars = 4;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(39);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let (poly, point, eval) = random_instance(num_vars, &mut rng);
+    let comm = CE::<E>::commit(&ck, &poly);
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg = EvaluationEngine::prove_with_known_coeffs(
+      &ck,
+      &pk,
+      &mut transcript,
+      &comm,
+      &poly,
+      &point,
+      &eval,
+      &[0, 5],
+    )
+    .unwrap();
+
+    let verify = |known: &[(usize, Fr)]| {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      EvaluationEngine::verify_with_known_coeffs(
+        &vk,
+        &mut transcript,
+        &comm,
+        &point,
+        &eval,
+        known,
+        &arg,
+      )
+    };
+    assert!(verify(&[(0, poly[0]), (5, poly[5])]).is_ok());
+
+    // inconsistent known entries are rejected
+    assert_eq!(
+      verify(&[(0, poly[0]), (5, poly[5] + Fr::ONE)]),
+      Err(NovaError::InstanceMismatch)
+    );
+    assert_eq!(
+      verify(&[(0, poly[0]), (6, poly[5])]),
+      Err(NovaError::InstanceMismatch)
+    );
+    assert_eq!(
+      verify(&[(0, poly[0]), (16, poly[5])]),
+      Err(NovaError::InvalidIndex)
+    );
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::prove_with_known_coeffs(
+        &ck,
+        &pk,
+        &mut transcript,
+        &comm,
+        &poly,
+        &point,
+        &eval,
+        &[16],
+      )
+      .err(),
+      Some(NovaError::InvalidIndex)
+    );
+  }
+
+  #[test]
+  fn test_ipa_expected_a_hat() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(28);
+    for num_vars in [0, 1, 6] {
+      let (_, _, vk, poly, point, eval, comm, arg) = prove_random_instance(num_vars, &mut rng);
+
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let challenges =
+        EvaluationEngine::challenges(&vk, &mut transcript, &comm, &point, &eval, &arg).unwrap();
+      assert_eq!(
+        EvaluationEngine::expected_a_hat(&poly, &challenges).unwrap(),
+        arg.a_hat
+      );
+    }
+  }
+
+  #[test]
+  fn test_ipa_final_knowledge() {
+    let num_vars = 4;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(61);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let (poly, point, eval) = random_instance(num_vars, &mut rng);
+    let comm = CE::<E>::commit(&ck, &poly);
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg = EvaluationEngine::prove_final_knowledge(
+      &ck,
+      &pk,
+      &mut transcript,
+      &comm,
+      &poly,
+      &point,
+      &eval,
+      &mut OsRng,
+    )
+    .unwrap();
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify_final_knowledge(
+      &vk,
+      &mut transcript,
+      &comm,
+      &point,
+      &eval,
+      &arg
+    )
+    .is_ok());
+
+    // the response for a different `a_hat` is rejected
+    let mut tampered = arg.clone();
+    tampered.z += Fr::ONE;
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_final_knowledge(
+        &vk,
+        &mut transcript,
+        &comm,
+        &point,
+        &eval,
+        &tampered
+      ),
+      Err(NovaError::FinalCheckFailed)
+    );
+
+    // as is a wrong evaluation
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_final_knowledge(
+        &vk,
+        &mut transcript,
+        &comm,
+        &point,
+        &(eval + Fr::ONE),
+        &arg
+      ),
+      Err(NovaError::FinalCheckFailed)
+    );
+  }
+
+  #[test]
+  fn test_ipa_verify_streaming() {
+    let num_vars = 6;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(60);
+    let (_, _, vk, _, point, eval, comm, arg) = prove_random_instance(num_vars, &mut rng);
+
+    // the streamed verifier agrees with the in-memory one, on valid and invalid claims
+    for (eval, expected) in [
+      (eval, Ok(())),
+      (eval + Fr::ONE, Err(NovaError::FinalCheckFailed)),
+    ] {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert_eq!(
+        EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg),
+        expected
+      );
+      for block_len in [1, 4, 1 << num_vars, 1 << (num_vars + 2)] {
+        let mut transcript = Keccak256Transcript::new(b"TestEval");
+        assert_eq!(
+          EvaluationEngine::verify_streaming(
+            &vk,
+            &mut transcript,
+            &comm,
+            &point,
+            &eval,
+            &arg,
+            block_len
+          ),
+          expected
+        );
+      }
+    }
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_streaming(&vk, &mut transcript, &comm, &point, &eval, &arg, 3),
+      Err(NovaError::InvalidInputLength)
+    );
+  }
+
+  // an MSM service that records the size of each request and computes it in process
+  #[derive(Default)]
+  struct RecordingMsmService {
+    sizes: core::cell::RefCell<Vec<usize>>,
+  }
+
+  impl MsmService<E> for RecordingMsmService {
+    fn msm(&self, bases: &CommitmentKey<E>, scalars: &[Fr]) -> Result<Commitment<E>, NovaError> {
+      self.sizes.borrow_mut().push(scalars.len());
+      MsmService::<E>::msm(&LocalMsmService, bases, scalars)
+    }
+  }
+
+  #[test]
+  fn test_ipa_verify_with_msm_service() {
+    let num_vars = 5;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(67);
+    let (_, _, vk, _, point, eval, comm, arg) = prove_random_instance(num_vars, &mut rng);
+
+    // the verifier agrees with `verify` on valid and invalid claims, and requests the folded
+    // generator and then the final check
+    for (eval, expected) in [
+      (eval, Ok(())),
+      (eval + Fr::ONE, Err(NovaError::FinalCheckFailed)),
+    ] {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert_eq!(
+        EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg),
+        expected
+      );
+
+      let service = RecordingMsmService::default();
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert_eq!(
+        EvaluationEngine::verify_with_msm_service(
+          &vk,
+          &mut transcript,
+          &comm,
+          &point,
+          &eval,
+          &arg,
+          &service
+        ),
+        expected
+      );
+      assert_eq!(*service.sizes.borrow(), [1 << num_vars, 2 * num_vars + 3]);
+
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert_eq!(
+        EvaluationEngine::verify_with_msm_service(
+          &vk,
+          &mut transcript,
+          &comm,
+          &point,
+          &eval,
+          &arg,
+          &LocalMsmService
+        ),
+        expected
+      );
+    }
+
+    // a malformed argument is rejected before any request
+    let service = RecordingMsmService::default();
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_with_msm_service(
+        &vk,
+        &mut transcript,
+        &comm,
+        &point[1..],
+        &eval,
+        &arg,
+        &service
+      ),
+      Err(NovaError::ProofLengthMismatch)
+    );
+    assert!(service.sizes.borrow().is_empty());
+  }
+
+  #[test]
+  fn test_ipa_prove_streaming() {
+    let num_vars = 6;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(29);
+    let (ck, pk, vk, poly, point, eval, comm, arg) = prove_random_instance(num_vars, &mut rng);
+
+    for max_len in [1, 4, 1 << num_vars] {
+      let mut longest = 0;
+      let get_chunk = |range: Range<usize>| {
+        longest = longest.max(range.len());
+        poly[range].to_vec()
+      };
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let arg_streaming = EvaluationEngine::prove_streaming(
+        &ck,
+        &pk,
+        &mut transcript,
+        &comm,
+        &point,
+        &eval,
+        max_len,
+        get_chunk,
+      )
+      .unwrap();
+      assert_eq!(arg_streaming.to_bytes(), arg.to_bytes());
+      assert!(longest <= max_len);
+
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert!(
+        EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg_streaming)
+          .is_ok()
+      );
+    }
+
+    // a chunk of the wrong length is rejected
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::prove_streaming(
+        &ck,
+        &pk,
+        &mut transcript,
+        &comm,
+        &point,
+        &eval,
+        4,
+        |_| vec![Fr::ONE]
+      )
+      .err(),
+      Some(NovaError::InvalidInputLength)
+    );
+  }
+
+  #[test]
+  fn test_ipa_basis_mismatch() {
+    let num_vars = 3;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(30);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let (poly, point, eval) = random_instance(num_vars, &mut rng);
+    let zeta = Fr::random(&mut rng);
+    let eval_univariate = poly.iter().rev().fold(Fr::ZERO, |acc, c| acc * zeta + c);
+
+    let comm = CE::<E>::commit(&ck, &poly);
+    let comm_evals = TaggedCommitment::new(comm, Basis::Evaluations);
+    let comm_coeffs = TaggedCommitment::new(comm, Basis::Coefficients);
+
+    // each commitment opens in its own basis
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg =
+      EvaluationEngine::prove_tagged(&ck, &pk, &mut transcript, &comm_evals, &poly, &point, &eval)
+        .unwrap();
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify_tagged(
+      &vk,
+      &mut transcript,
+      &comm_evals,
+      &point,
+      &eval,
+      &arg
+    )
+    .is_ok());
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg_univariate = EvaluationEngine::prove_univariate_tagged(
+      &ck,
+      &pk,
+      &mut transcript,
+      &comm_coeffs,
+      &poly,
+      &zeta,
+      &eval_univariate,
+    )
+    .unwrap();
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify_univariate_tagged(
+      &vk,
+      &mut transcript,
+      &comm_coeffs,
+      poly.len(),
+      &zeta,
+      &eval_univariate,
+      &arg_univariate
+    )
+    .is_ok());
+
+    // mixing the bases is rejected
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::prove_tagged(
+        &ck,
+        &pk,
+        &mut transcript,
+        &comm_coeffs,
+        &poly,
+        &point,
+        &eval
+      )
+      .err(),
+      Some(NovaError::BasisMismatch)
+    );
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_tagged(&vk, &mut transcript, &comm_coeffs, &point, &eval, &arg),
+      Err(NovaError::BasisMismatch)
+    );
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::prove_univariate_tagged(
+        &ck,
+        &pk,
+        &mut transcript,
+        &comm_evals,
+        &poly,
+        &zeta,
+        &eval_univariate
+      )
+      .err(),
+      Some(NovaError::BasisMismatch)
+    );
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_univariate_tagged(
+        &vk,
+        &mut transcript,
+        &comm_evals,
+        poly.len(),
+        &zeta,
+        &eval_univariate,
+        &arg_univariate
+      ),
+      Err(NovaError::BasisMismatch)
+    );
+  }
+
+  #[test]
+  fn test_ipa_verify_with_membership() {
+    let num_vars = 3;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(31);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+
+    let instances = (0..4)
+      .map(|_| random_instance(num_vars, &mut rng))
+      .collect::<Vec<_>>();
+    let comms = instances
+      .iter()
+      .map(|(poly, _, _)| CE::<E>::commit(&ck, poly))
+      .collect::<Vec<_>>();
+    let tree = CommitmentTree::new::<E>(&comms).unwrap();
+    let root = tree.root();
+
+    let (poly, point, eval) = &instances[2];
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg =
+      EvaluationEngine::prove(&ck, &pk, &mut transcript, &comms[2], poly, point, eval).unwrap();
+
+    let verify = |path: &MembershipPath| {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      EvaluationEngine::verify_with_membership(
+        &vk,
+        &mut transcript,
+        &root,
+        path,
+        &comms[2],
+        point,
+        eval,
+        &arg,
+      )
+    };
+    let path = tree.path(2).unwrap();
+    assert_eq!(path.index(), 2);
+    assert!(verify(&path).is_ok());
+
+    // the path of another commitment does not show membership of `comms[2]`
+    assert_eq!(
+      verify(&tree.path(1).unwrap()),
+      Err(NovaError::InvalidMerklePath)
+    );
+    assert_eq!(tree.path(4).err(), Some(NovaError::InvalidIndex));
+  }
+
+  // a small prime field, to check the algebra of the folding without a curve
+  #[derive(ff::PrimeField)]
+  #[PrimeFieldModulus = "65537"]
+  #[PrimeFieldGenerator = "3"]
+  #[PrimeFieldReprEndianness = "little"]
+  struct Fp([u64; 1]);
+
+  #[test]
+  fn test_folding_identity() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(32);
+    let num_vars = 4;
+    let n = 1 << num_vars;
+    let a = (0..n).map(|_| Fp::random(&mut rng)).collect::<Vec<_>>();
+    let b = (0..n).map(|_| Fp::random(&mut rng)).collect::<Vec<_>>();
+
+    let (mut a_folded, mut b_folded) = (a.clone(), b.clone());
+    let mut r_vec = Vec::new();
+    while a_folded.len() > 1 {
+      let half = a_folded.len() / 2;
+      let c = inner_product(&a_folded, &b_folded);
+      let c_L = inner_product(&a_folded[..half], &b_folded[half..]);
+      let c_R = inner_product(&a_folded[half..], &b_folded[..half]);
+
+      let r = Fp::random(&mut rng);
+      let r_inverse = r.invert().unwrap();
+      fold_halves(&mut a_folded, &r, &r_inverse);
+      fold_halves(&mut b_folded, &r_inverse, &r);
+      r_vec.push(r);
+
+      assert_eq!(
+        inner_product(&a_folded, &b_folded),
+        c + r.square() * c_L + r_inverse.square() * c_R
+      );
+    }
+
+    // the folded vectors are the inner products with the tensor vectors of the challenges
+    let r_square = r_vec.iter().map(|r| r.square()).collect::<Vec<_>>();
+    let s = tensor_vector(&r_square, &batch_invert(&r_vec).unwrap());
+    assert_eq!(b_folded[0], inner_product(&b, &s));
+    assert_eq!(
+      a_folded[0],
+      inner_product(&a, &fold_weights(&r_vec).unwrap())
+    );
+  }
+
+  #[test]
+  fn test_ipa_round_sizes() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(64);
+    for num_vars in [0, 1, 5] {
+      let ck = CE::<E>::setup(b"test", 1 << num_vars);
+      let (pk, _) = EvaluationEngine::<E>::setup(&ck);
+      let (poly, point, eval) = random_instance(num_vars, &mut rng);
+      let comm = CE::<E>::commit(&ck, &poly);
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let arg =
+        EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+
+      // every round contributes two compressed points, followed by `a_hat` and the digest
+      let sizes = arg.round_sizes();
+      assert_eq!(sizes.len(), num_vars + 2);
+      assert!(sizes[..num_vars].iter().all(|size| *size == 2 * 32));
+      assert_eq!(sizes[num_vars..], [32, 32]);
+      assert_eq!(sizes.iter().sum::<usize>(), arg.size_estimate());
+      assert_eq!(sizes.iter().sum::<usize>(), arg.to_compact_bytes().len());
+    }
+  }
+
+  #[test]
+  fn test_ipa_compact_bytes() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(48);
+    for num_vars in [0, 1, 5] {
+      let (_, _, vk, _, point, eval, comm, arg) = prove_random_instance(num_vars, &mut rng);
+
+      // two compressed points per round, one scalar and the digest, with nothing else
+      let bytes = arg.to_compact_bytes();
+      assert_eq!(bytes.len(), 32 * (2 * num_vars + 2));
+      assert_eq!(bytes[bytes.len() - 32..], arg.instance_digest);
+      assert_eq!(
+        bytes[bytes.len() - 64..bytes.len() - 32],
+        arg
+          .a_hat
+          .to_repr()
+          .iter()
+          .rev()
+          .copied()
+          .collect::<Vec<u8>>()[..]
+      );
+
+      let decoded = InnerProductArgument::<E>::from_compact_bytes(&bytes).unwrap();
+      assert_eq!(decoded.to_bytes(), arg.to_bytes());
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert!(
+        EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &decoded).is_ok()
+      );
+
+      // the bincode encoding round-trips as well
+      let decoded = InnerProductArgument::<E>::from_bytes(&arg.to_bytes()).unwrap();
+      assert_eq!(decoded.to_compact_bytes(), bytes);
+
+      assert_eq!(
+        InnerProductArgument::<E>::from_compact_bytes(&bytes[1..]).err(),
+        Some(NovaError::MalformedProof)
+      );
+
+      // a commitment that is not a point of the curve is rejected
+      if num_vars > 0 {
+        let mut tampered = bytes.clone();
+        tampered[..32].copy_from_slice(&[0xff; 32]);
+        assert_eq!(
+          InnerProductArgument::<E>::from_compact_bytes(&tampered).err(),
+          Some(NovaError::PointNotOnCurve)
+        );
+      }
+    }
+
+    // a scalar that is not canonical is rejected
+    assert_eq!(
+      InnerProductArgument::<E>::from_compact_bytes(&[0xff; 64]).err(),
+      Some(NovaError::MalformedProof)
+    );
+  }
+
+  #[test]
+  fn test_ipa_truncated_bytes() {
+    let num_vars = 3;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(21);
+    let (_, _, vk, _, point, eval, comm, arg) = prove_random_instance(num_vars, &mut rng);
+
+    let bytes = arg.to_bytes();
+    let arg_decoded = InnerProductArgument::<E>::from_bytes(&bytes).unwrap();
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(
+      EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg_decoded).is_ok()
+    );
+
+    // every truncation is rejected, including one that drops exactly `a_hat`
+    for len in 0..bytes.len() {
+      assert_eq!(
+        InnerProductArgument::<E>::from_bytes(&bytes[..len]).err(),
+        Some(NovaError::MalformedProof)
+      );
+    }
+    let with_trailing = [&bytes[..], &[0u8]].concat();
+    assert_eq!(
+      InnerProductArgument::<E>::from_bytes(&with_trailing).err(),
+      Some(NovaError::MalformedProof)
+    );
+
+    // an argument that lost one of its `R` commitments, but is otherwise well-formed
+    let unchecked = (arg.L_vec.clone(), arg.R_vec[1..].to_vec(), arg.a_hat);
+    let bytes = bincode::serialize(&unchecked).unwrap();
+    assert_eq!(
+      InnerProductArgument::<E>::from_bytes(&bytes).err(),
+      Some(NovaError::MalformedProof)
+    );
+    assert!(bincode::deserialize::<InnerProductArgument<E>>(&bytes).is_err());
+  }
+
+  #[test]
+  fn test_ipa_json_round_trip() {
+    let num_vars = 3;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(15);
+    let (_, _, vk, _, point, eval, comm, arg) = prove_random_instance(num_vars, &mut rng);
+
+    let json = arg.to_json();
+    let arg_decoded = InnerProductArgument::<E>::from_json(&json).unwrap();
+    assert_eq!(
+      bincode::serialize(&arg).unwrap(),
+      bincode::serialize(&arg_decoded).unwrap()
+    );
+    assert_eq!(arg_decoded.to_json(), json);
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(
+      EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg_decoded).is_ok()
+    );
+
+    for invalid in [
+      "{}",
+      &json.replace("\"a_hat\"", "\"b_hat\""),
+      &json.replace("\"L\": \"", "\"L\": \"zz"),
+    ] {
+      assert!(matches!(
+        InnerProductArgument::<E>::from_json(invalid),
+        Err(NovaError::InvalidJson { .. })
+      ));
+    }
+
+    // well-formed JSON whose parts have the wrong length
+    for malformed in [
+      json.replace("\"a_hat\": \"", "\"a_hat\": \"00"),
+      json.replace("\"L\": \"", "\"L\": \"00"),
+      json.replace("\"instance_digest\": \"", "\"instance_digest\": \"00"),
+    ] {
+      assert_eq!(
+        InnerProductArgument::<E>::from_json(&malformed).err(),
+        Some(NovaError::MalformedProof)
+      );
+    }
+
+    // a commitment of the right length that is not a point of the curve
+    let (start, _) = json.match_indices("\"L\": \"").next().unwrap();
+    let start = start + "\"L\": \"".len();
+    let mut off_curve = json.clone();
+    off_curve.replace_range(start..start + 64, &"ff".repeat(32));
+    assert_eq!(
+      InnerProductArgument::<E>::from_json(&off_curve).err(),
+      Some(NovaError::PointNotOnCurve)
+    );
+  }
+
+  #[test]
+  fn test_ipa_json_schema() {
+    let ck = CE::<E>::setup(b"test", 2);
+    let arg = InnerProductArgument::<E> {
+      L_vec: vec![CE::<E>::commit(&ck, &[Fr::ONE])],
+      R_vec: vec![CE::<E>::commit(&ck, &[Fr::ZERO, Fr::ONE])],
+      a_hat: Fr::from(7),
+      instance_digest: [0xab; 32],
+    };
+
+    expect_test::expect![[r#"
+        {
+          "rounds": [
+            {
+              "L": "8b9022bed47e3abda609f39688e1d406d16eb6650c95113d56d051c832e8b19f",
+              "R": "abfa0b7fb2a311e99b2305496026d777664c905d489f8df9457cdf7a65700b25"
+            }
+          ],
+          "a_hat": "0700000000000000000000000000000000000000000000000000000000000000",
+          "instance_digest": "abababababababababababababababababababababababababababababababab"
+        }"#]]
+    .assert_eq(&arg.to_json());
+  }
+
+  #[test]
+  fn test_ipa_verify_incremental() {
+    let num_vars = 4;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(16);
+    let (ck, _, vk, _, point, eval, comm, arg) = prove_random_instance(num_vars, &mut rng);
+
+    let comm_other = CE::<E>::commit(&ck, &random_instance(num_vars, &mut rng).0);
+    for (comm, eval) in [(comm, eval), (comm_other, eval), (comm, eval + Fr::ONE)] {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let expected = EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg);
+
+      // the commitment is only supplied after the rest of the statement and the proof
+      let pending = EvaluationEngine::verify_incremental(&vk, &point, &eval, &arg).unwrap();
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert_eq!(pending.finalize(&mut transcript, &comm), expected);
+    }
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg).is_ok());
+
+    // a proof of the wrong shape is rejected before the commitment is known
+    assert_eq!(
+      EvaluationEngine::verify_incremental(&vk, &point[1..], &eval, &arg).err(),
+      Some(NovaError::ProofLengthMismatch)
+    );
+  }
+
+  #[test]
+  fn test_ipa_prove_prepared() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(17);
+    for num_vars in [0, 1, 6] {
+      let ck = CE::<E>::setup(b"test", 1 << num_vars);
+      let prepared = ck.prepare();
+      let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+
+      // many proofs are created in parallel with the same prepared key
+      let instances = (0..4)
+        .map(|_| random_instance(num_vars, &mut rng))
+        .collect::<Vec<_>>();
+      instances.par_iter().for_each(|(poly, point, eval)| {
+        let comm = prepared.commit(poly);
+        assert_eq!(comm, CE::<E>::commit(&ck, poly));
+
+        let mut transcript = Keccak256Transcript::new(b"TestEval");
+        let arg =
+          EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, poly, point, eval).unwrap();
+        let mut transcript = Keccak256Transcript::new(b"TestEval");
+        let arg_prepared = EvaluationEngine::prove_prepared(
+          &prepared,
+          &pk,
+          &mut transcript,
+          &comm,
+          poly,
+          point,
+          eval,
+        )
+        .unwrap();
+        assert_eq!(
+          bincode::serialize(&arg).unwrap(),
+          bincode::serialize(&arg_prepared).unwrap()
+        );
+
+        let mut transcript = Keccak256Transcript::new(b"TestEval");
+        assert!(
+          EvaluationEngine::verify(&vk, &mut transcript, &comm, point, eval, &arg_prepared).is_ok()
+        );
+      });
+    }
+  }
+
+  #[test]
+  fn test_ipa_error_variants() {
+    let num_vars = 3;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(18);
+    let (_, _, vk, _, point, eval, comm, arg) = prove_random_instance(num_vars, &mut rng);
+
+    let verify = |point: &[Fr], eval: &Fr, arg: &InnerProductArgument<E>| {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      EvaluationEngine::verify(&vk, &mut transcript, &comm, point, eval, arg)
+    };
+    assert!(verify(&point, &eval, &arg).is_ok());
+
+    // a proof for a different number of variables, or with inconsistent parts
+    assert_eq!(
+      verify(&point[1..], &eval, &arg),
+      Err(NovaError::ProofLengthMismatch)
+    );
+    let mut arg_short = arg.clone();
+    arg_short.R_vec.pop();
+    assert_eq!(
+      verify(&point, &eval, &arg_short),
+      Err(NovaError::ProofLengthMismatch)
+    );
+
+    let arg_long = InnerProductArgument::<E> {
+      L_vec: vec![arg.L_vec[0]; 32],
+      R_vec: vec![arg.R_vec[0]; 32],
+      a_hat: arg.a_hat,
+      instance_digest: arg.instance_digest,
+    };
+    assert_eq!(
+      verify(&point, &eval, &arg_long),
+      Err(NovaError::RoundCountExceeded)
+    );
+
+    // the bytes `ff..ff` do not encode a point
+    let json = arg.to_json();
+    let L = &json[json.find("\"L\": \"").unwrap() + 6..][..64];
+    assert_eq!(
+      InnerProductArgument::<E>::from_json(&json.replace(L, &"f".repeat(64))).err(),
+      Some(NovaError::PointNotOnCurve)
+    );
+
+    assert_eq!(
+      verify(&point, &(eval + Fr::ONE), &arg),
+      Err(NovaError::FinalCheckFailed)
+    );
+  }
+
+  #[test]
+  fn test_ipa_folded_generator() {
+    let num_vars = 4;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(10);
+    let (ck, _, vk, _, point, eval, comm, arg) = prove_random_instance(num_vars, &mut rng);
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let challenges =
+      EvaluationEngine::challenges(&vk, &mut transcript, &comm, &point, &eval, &arg).unwrap();
+
+    let ck_hat = EvaluationEngine::folded_generator(&ck, &challenges).unwrap();
+
+    // the same generator as the one obtained by folding the generators round by round
+    let ck_folded = challenges
+      .r_vec
+      .iter()
+      .fold(ck.clone(), |ck, r| ck.fold(&r.invert().unwrap(), r));
+    assert_eq!(ck_folded.length(), 1);
+    assert_eq!(ck_hat, CE::<E>::commit(&ck_folded, &[Fr::ONE]));
+
+    // and as the `commit(ck, s)` that `verify` computes
+    let r_square = challenges
+      .r_vec
+      .iter()
+      .map(|r| r.square())
+      .collect::<Vec<Fr>>();
+    let s = tensor_vector(&r_square, &batch_invert(&challenges.r_vec).unwrap());
+    assert_eq!(ck_hat, CE::<E>::commit(&ck, &s));
+
+    let (ck_short, _) = ck.split_at(1 << (num_vars - 1));
+    assert_eq!(
+      EvaluationEngine::folded_generator(&ck_short, &challenges),
+      Err(NovaError::InvalidCommitmentKeyLength)
+    );
+  }
+
+  #[test]
+  #[cfg(debug_assertions)]
+  fn test_ipa_self_check() {
+    let num_vars = 6;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(68);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, _vk) = EvaluationEngine::<E>::setup(&ck);
+    let (poly, point, eval) = random_instance(num_vars, &mut rng);
+    let comm = CE::<E>::commit(&ck, &poly);
+
+    // a witness with two nonzero entries, which the sparse prover keeps sparse in its first
+    // rounds
+    let entries = [(3, poly[3]), (40, poly[40])];
+    let mut sparse_poly = vec![Fr::ZERO; 1 << num_vars];
+    entries.iter().for_each(|(i, v)| sparse_poly[*i] = *v);
+    let sparse_comm = CE::<E>::commit(&ck, &sparse_poly);
+    let sparse_eval = MultilinearPolynomial::evaluate_with(&sparse_poly, &point);
+    let sharded = ShardedProver::<E>::new(vec![
+      (0..24, poly[..24].to_vec()),
+      (24..64, poly[24..].to_vec()),
+    ])
+    .unwrap();
+
+    type Prover<'a> = Box<dyn Fn(&mut Keccak256Transcript<E>) + 'a>;
+    let provers: [Prover<'_>; 3] = [
+      Box::new(|transcript| {
+        EvaluationEngine::prove(&ck, &pk, transcript, &comm, &poly, &point, &eval).unwrap();
+      }),
+      Box::new(|transcript| {
+        EvaluationEngine::prove_sparse(
+          &ck,
+          &pk,
+          transcript,
+          &sparse_comm,
+          &entries,
+          &point,
+          &sparse_eval,
+        )
+        .unwrap();
+      }),
+      Box::new(|transcript| {
+        sharded
+          .prove(&ck, &pk, transcript, &comm, &point, &eval)
+          .unwrap();
+      }),
+    ];
+    for prove in provers {
+      // the provers are consistent without the fault
+      prove(&mut Keccak256Transcript::new(b"TestEval"));
+
+      // each prover folds with a wrong inverse in its first round, which its self-check reports
+      FAULTY_R_INVERSE.with(|faulty| faulty.set(true));
+      let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        prove(&mut Keccak256Transcript::new(b"TestEval"))
+      }))
+      .unwrap_err();
+      FAULTY_R_INVERSE.with(|faulty| faulty.set(false));
+      let message = panic.downcast_ref::<String>().unwrap();
+      assert!(
+        message.contains("self-check failed at round 0"),
+        "{message}"
+      );
+    }
+  }
+
+  #[test]
+  fn test_ipa_verify_with_difference() {
+    let num_vars = 4;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(69);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let (f, point, eval_f) = random_instance(num_vars, &mut rng);
+    let (g, _, _) = random_instance(num_vars, &mut rng);
+    let eval_g = MultilinearPolynomial::evaluate_with(&g, &point);
+    let comm_f = CE::<E>::commit(&ck, &f);
+    let comm_g = CE::<E>::commit(&ck, &g);
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg_f =
+      EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm_f, &f, &point, &eval_f).unwrap();
+    let arg_g =
+      EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm_g, &g, &point, &eval_g).unwrap();
+
+    let verify = |eval_f: &Fr, eval_g: &Fr, delta: &Fr| {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      EvaluationEngine::verify_with_difference(
+        &vk,
+        &mut transcript,
+        &comm_f,
+        &comm_g,
+        &point,
+        eval_f,
+        eval_g,
+        delta,
+        &arg_f,
+        &arg_g,
+      )
+    };
+
+    let delta = eval_f - eval_g;
+    assert!(verify(&eval_f, &eval_g, &delta).is_ok());
+
+    // a violated difference, and evaluations that differ by `delta` but are not the opened ones
+    assert_eq!(
+      verify(&eval_f, &eval_g, &(delta + Fr::ONE)),
+      Err(NovaError::EvaluationRelationFailed)
+    );
+    assert_eq!(
+      verify(&(eval_f + Fr::ONE), &(eval_g + Fr::ONE), &delta),
+      Err(NovaError::FinalCheckFailed)
+    );
+  }
+
+  #[test]
+  fn test_ipa_nonce() {
+    let num_vars = 4;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(70);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let (poly, point, eval) = random_instance(num_vars, &mut rng);
+    let comm = CE::<E>::commit(&ck, &poly);
+
+    let prove = |nonce: &[u8; 32]| {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      EvaluationEngine::prove_with_nonce(
+        &ck,
+        &pk,
+        &mut transcript,
+        nonce,
+        &comm,
+        &poly,
+        &point,
+        &eval,
+      )
+      .unwrap()
+    };
+    let verify = |nonce: &[u8; 32], arg: &InnerProductArgument<E>| {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      EvaluationEngine::verify_with_nonce(&vk, &mut transcript, nonce, &comm, &point, &eval, arg)
+    };
+
+    let (nonce_a, nonce_b) = ([1; 32], [2; 32]);
+    let (arg_a, arg_b) = (prove(&nonce_a), prove(&nonce_b));
+    assert_eq!(arg_a.to_bytes(), prove(&nonce_a).to_bytes());
+    assert_ne!(arg_a.to_bytes(), arg_b.to_bytes());
+
+    // each argument only verifies under its own nonce
+    assert!(verify(&nonce_a, &arg_a).is_ok());
+    assert!(verify(&nonce_b, &arg_b).is_ok());
+    assert_eq!(verify(&nonce_b, &arg_a), Err(NovaError::FinalCheckFailed));
+    assert_eq!(verify(&nonce_a, &arg_b), Err(NovaError::FinalCheckFailed));
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg_a),
+      Err(NovaError::FinalCheckFailed)
+    );
+  }
+
+  #[test]
+  fn test_ipa_commit_scalar() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(71);
+    let ck = CE::<E>::setup(b"test", 4);
+    let (pk, _vk) = EvaluationEngine::<E>::setup(&ck);
+
+    // a key that is deserialized computes its table anew
+    let pk_decoded: ProverKey<E> = bincode::deserialize(&bincode::serialize(&pk).unwrap()).unwrap();
+
+    for x in [Fr::ZERO, Fr::ONE, -Fr::ONE, Fr::random(&mut rng)] {
+      let expected = CE::<E>::commit(&pk.ck_s, &[x]);
+      assert_eq!(pk.commit_scalar(&x), expected);
+      assert_eq!(pk_decoded.commit_scalar(&x), expected);
+    }
+  }
+
+  #[test]
+  fn test_ipa_public_product() {
+    let num_vars = 4;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(72);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let (f, point, eval_f) = random_instance(num_vars, &mut rng);
+    let (g, _, _) = random_instance(num_vars, &mut rng);
+    let comm = CE::<E>::commit(&ck, &f);
+
+    // the evaluation of the entrywise product, which differs from the product of evaluations
+    let h = f
+      .iter()
+      .zip(g.iter())
+      .map(|(f, g)| *f * g)
+      .collect::<Vec<Fr>>();
+    let eval = MultilinearPolynomial::evaluate_with(&h, &point);
+    assert_ne!(
+      eval,
+      eval_f * MultilinearPolynomial::evaluate_with(&g, &point)
+    );
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg = EvaluationEngine::prove_public_product(
+      &ck,
+      &pk,
+      &mut transcript,
+      &comm,
+      &f,
+      &g,
+      &point,
+      &eval,
+    )
+    .unwrap();
+
+    let verify = |public_poly: &[Fr], eval: &Fr| {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      EvaluationEngine::verify_public_product(
+        &vk,
+        &mut transcript,
+        &comm,
+        public_poly,
+        &point,
+        eval,
+        &arg,
+      )
+    };
+    assert!(verify(&g, &eval).is_ok());
+    assert_eq!(
+      verify(&g, &(eval + Fr::ONE)),
+      Err(NovaError::FinalCheckFailed)
+    );
+    assert_eq!(verify(&f, &eval), Err(NovaError::InstanceMismatch));
+    assert_eq!(verify(&g[1..], &eval), Err(NovaError::InvalidInputLength));
+
+    // another public polynomial for which the claimed evaluation also holds, obtained by
+    // moving weight between two entries of `g`, is bound to the instance and rejected
+    let eq = EqPolynomial::new(point.clone()).evals();
+    let delta = Fr::from(5);
+    let mut g_other = g.clone();
+    g_other[0] += delta * (f[0] * eq[0]).invert().unwrap();
+    g_other[1] -= delta * (f[1] * eq[1]).invert().unwrap();
+    let h_other = f
+      .iter()
+      .zip(g_other.iter())
+      .map(|(f, g)| *f * g)
+      .collect::<Vec<Fr>>();
+    assert_eq!(MultilinearPolynomial::evaluate_with(&h_other, &point), eval);
+    assert_eq!(verify(&g_other, &eval), Err(NovaError::InstanceMismatch));
+  }
+
+  #[test]
+  fn test_ipa_prove_with_folded_ck() {
+    let num_vars = 5;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(65);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+
+    let (poly, point, eval) = random_instance(num_vars, &mut rng);
+    let comm = CE::<E>::commit(&ck, &poly);
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let (arg, ck_folded) = EvaluationEngine::prove_with_folded_ck(
+      &ck,
+      &pk,
+      &mut transcript,
+      &comm,
+      &poly,
+      &point,
+      &eval,
+    )
+    .unwrap();
+
+    // the argument is the one output by `prove`
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let expected =
+      EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+    assert_eq!(arg.to_bytes(), expected.to_bytes());
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg).is_ok());
+
+    // and the key is the one the verifier reconstructs from the challenges
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let challenges =
+      EvaluationEngine::challenges(&vk, &mut transcript, &comm, &point, &eval, &arg).unwrap();
+    let r_square = challenges
+      .r_vec
+      .iter()
+      .map(|r| r.square())
+      .collect::<Vec<Fr>>();
+    let s = tensor_vector(&r_square, &batch_invert(&challenges.r_vec).unwrap());
+    assert_eq!(ck_folded.length(), 1);
+    assert_eq!(
+      ck_folded,
+      CommitmentKey::<E>::reinterpret_commitments_as_ck(&[CE::<E>::commit(&ck, &s)]).unwrap()
+    );
+  }
+
+  // exercises any multilinear polynomial commitment scheme through `MultilinearEvaluation`
+  fn test_multilinear_evaluation_with<E: Engine, EE: MultilinearEvaluation<E>>() {
+    let num_vars = 4;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(9);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EE::setup(&ck);
+
+    let poly = (0..(1 << num_vars))
+      .map(|_| E::Scalar::random(&mut rng))
+      .collect::<Vec<_>>();
+    let point = (0..num_vars)
+      .map(|_| E::Scalar::random(&mut rng))
+      .collect::<Vec<_>>();
+    let comm = CE::<E>::commit(&ck, &poly);
+
+    let mut transcript = E::TE::new(b"TestEval");
+    let (eval, arg) = EE::open(&ck, &pk, &mut transcript, &comm, &poly, &point).unwrap();
+
+    let mut transcript = E::TE::new(b"TestEval");
+    assert!(EE::verify_opening(&vk, &mut transcript, &comm, &point, &eval, &arg).is_ok());
+
+    let mut transcript = E::TE::new(b"TestEval");
+    assert!(EE::verify_opening(
+      &vk,
+      &mut transcript,
+      &comm,
+      &point,
+      &(eval + E::Scalar::ONE),
+      &arg
+    )
+    .is_err());
+
+    let mut transcript = E::TE::new(b"TestEval");
+    assert!(EE::open(&ck, &pk, &mut transcript, &comm, &poly[1..], &point).is_err());
+  }
+
+  #[test]
+  fn test_ipa_verify_constant_time() {
+    let num_vars = 4;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(50);
+    let (_, _, vk, _, point, eval, comm, arg) = prove_random_instance(num_vars, &mut rng);
+
+    // both paths accept the honest evaluation and reject the others with the same error
+    for eval in [eval, eval + Fr::ONE] {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let expected = EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg);
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert_eq!(
+        EvaluationEngine::verify_constant_time(&vk, &mut transcript, &comm, &point, &eval, &arg),
+        expected
+      );
+    }
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_constant_time(
+        &vk,
+        &mut transcript,
+        &comm,
+        &point,
+        &(eval + Fr::ONE),
+        &arg
+      ),
+      Err(NovaError::FinalCheckFailed)
+    );
+
+    // the checks before the final one are those of `verify`
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_constant_time(
+        &vk,
+        &mut transcript,
+        &Commitment::<E>::default(),
+        &point,
+        &eval,
+        &arg
+      ),
+      Err(NovaError::TrivialCommitment)
+    );
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_constant_time(&vk, &mut transcript, &comm, &point[1..], &eval, &arg),
+      Err(NovaError::ProofLengthMismatch)
+    );
+    let vk_right_first = vk.clone().with_round_order(RoundOrder::RightFirst);
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify_constant_time(
+        &vk_right_first,
+        &mut transcript,
+        &comm,
+        &point,
+        &eval,
+        &arg
+      ),
+      Err(NovaError::FinalCheckFailed)
+    );
+  }
+
+  #[test]
+  fn test_ipa_verifier_key_from_prover_key() {
+    let num_vars = 4;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(58);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, _) = EvaluationEngine::<E>::setup(&ck);
+    let (poly, point, eval) = random_instance(num_vars, &mut rng);
+    let comm = CE::<E>::commit(&ck, &poly);
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let arg =
+      EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+
+    // the verifier only receives the serialized prover key, besides the shared commitment key
+    let pk = bincode::deserialize::<ProverKey<E>>(&bincode::serialize(&pk).unwrap()).unwrap();
+    let vk = VerifierKey::from_prover_key(&pk, &ck);
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert!(EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg).is_ok());
+  }
+
+  #[test]
+  fn test_ipa_setup_with_soundness() {
+    fn check<E: Engine>()
+    where
+      E::GE: DlogGroup,
+      CommitmentKey<E>: CommitmentKeyExtTrait<E>,
+    {
+      let ck = CE::<E>::setup(b"test", 4);
+      let (_, vk) = EvaluationEngine::<E>::setup(&ck);
+      assert_eq!(vk.soundness_bits(), None);
+
+      let (_, vk) = EvaluationEngine::<E>::setup_with_soundness(&ck, 128).unwrap();
+      assert_eq!(vk.soundness_bits(), Some(128));
+      let vk = bincode::deserialize::<VerifierKey<E>>(&bincode::serialize(&vk).unwrap()).unwrap();
+      assert_eq!(vk.soundness_bits(), Some(128));
+
+      // none of the backends has a field too small for 128 bits, so we ask for more than the
+      // field provides
+      assert_eq!(
+        EvaluationEngine::<E>::setup_with_soundness(&ck, challenge_soundness_bits::<E>() + 1).err(),
+        Some(NovaError::InsufficientSoundness)
+      );
     }
+
+    check::<E>();
+    check::<crate::provider::Bn256EngineIPA>();
+    check::<crate::provider::Secp256k1Engine>();
+  }
+
+  #[test]
+  fn test_ipa_prove_with_eq() {
+    let num_vars = 4;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(56);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let (_, point, _) = random_instance(num_vars, &mut rng);
+    let eq = EqPolynomial::new(point.clone()).evals();
+
+    // several polynomials opened at the same point share one table
+    for _ in 0..3 {
+      let poly = (0..1 << num_vars)
+        .map(|_| Fr::random(&mut rng))
+        .collect::<Vec<Fr>>();
+      let eval = MultilinearPolynomial::evaluate_with(&poly, &point);
+      let comm = CE::<E>::commit(&ck, &poly);
+
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let arg = EvaluationEngine::prove_with_eq(
+        &ck,
+        &pk,
+        &mut transcript,
+        &comm,
+        &poly,
+        &point,
+        &eq,
+        &eval,
+      )
+      .unwrap();
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let expected =
+        EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+      assert_eq!(arg.to_bytes(), expected.to_bytes());
+
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert!(EvaluationEngine::verify_with_eq(
+        &vk,
+        &mut transcript,
+        &comm,
+        &point,
+        &eq,
+        &eval,
+        &arg
+      )
+      .is_ok());
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert!(EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg).is_ok());
+    }
+
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::prove_with_eq(
+        &ck,
+        &pk,
+        &mut transcript,
+        &Commitment::<E>::default(),
+        &[Fr::ZERO; 16],
+        &point,
+        &eq[..8],
+        &Fr::ZERO
+      )
+      .err(),
+      Some(NovaError::InvalidInputLength)
+    );
+  }
+
+  #[test]
+  #[cfg(debug_assertions)]
+  #[should_panic]
+  fn test_ipa_prove_with_wrong_eq() {
+    let num_vars = 2;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(57);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, _) = EvaluationEngine::<E>::setup(&ck);
+    let (poly, point, eval) = random_instance(num_vars, &mut rng);
+    let comm = CE::<E>::commit(&ck, &poly);
+
+    // a table for another point is caught in debug builds
+    let eq = EqPolynomial::new(vec![Fr::ONE; num_vars]).evals();
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let _ =
+      EvaluationEngine::prove_with_eq(&ck, &pk, &mut transcript, &comm, &poly, &point, &eq, &eval);
+  }
+
+  #[test]
+  fn test_ipa_all_vertices() {
+    let num_vars = 3;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(55);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let (poly, _, _) = random_instance(num_vars, &mut rng);
+    let comm = CE::<E>::commit(&ck, &poly);
+
+    // the table agrees with the evaluation of the polynomial at each vertex
+    let table = EvaluationEngine::<E>::full_table(&poly);
+    for (i, v) in table.iter().enumerate() {
+      let point = EvaluationEngine::<E>::vertex(num_vars, i);
+      assert_eq!(MultilinearPolynomial::evaluate_with(&poly, &point), *v);
+    }
+
+    // open each vertex individually, on one transcript
+    let mut transcript = Keccak256Transcript::new(b"TestEval");
+    let openings = (0..1 << num_vars)
+      .map(|i| {
+        let point = EvaluationEngine::<E>::vertex(num_vars, i);
+        let eval = MultilinearPolynomial::evaluate_with(&poly, &point);
+        let arg =
+          EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+        (i, arg)
+      })
+      .collect::<Vec<_>>();
+
+    let verify = |table: &[Fr], openings: &[(usize, InnerProductArgument<E>)]| {
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      EvaluationEngine::verify_all_vertices(&vk, &mut transcript, &comm, table, openings)
+    };
+    assert!(verify(table, &openings).is_ok());
+
+    let mut tampered = table.to_vec();
+    tampered[5] += Fr::ONE;
+    assert!(verify(&tampered, &openings).is_err());
+    assert_eq!(
+      verify(&table[..3], &openings),
+      Err(NovaError::InvalidInputLength)
+    );
+    let out_of_range = [(8, openings[0].1.clone())];
+    assert_eq!(verify(table, &out_of_range), Err(NovaError::InvalidIndex));
+  }
+
+  #[test]
+  fn test_ipa_self_test() {
+    assert!(EvaluationEngine::<E>::self_test().is_ok());
+    assert!(EvaluationEngine::<crate::provider::Bn256EngineIPA>::self_test().is_ok());
+  }
+
+  #[test]
+  fn test_multilinear_evaluation() {
+    test_multilinear_evaluation_with::<E, EvaluationEngine<E>>();
+    test_multilinear_evaluation_with::<
+      crate::provider::Bn256EngineIPA,
+      EvaluationEngine<crate::provider::Bn256EngineIPA>,
+    >();
+  }
+
+  // an engine that is identical to `PallasEngine` except for its transcript
+  #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+  struct FailingEngine;
+
+  impl Engine for FailingEngine {
+    type Base = <E as Engine>::Base;
+    type Scalar = <E as Engine>::Scalar;
+    type GE = <E as Engine>::GE;
+    type RO = <E as Engine>::RO;
+    type ROCircuit = <E as Engine>::ROCircuit;
+    type TE = FailingTranscript;
+    type CE = crate::provider::pedersen::CommitmentEngine<Self>;
+  }
+
+  // a transcript that fails to derive the challenge labeled `FAILING_LABEL` once it has
+  // derived `FAILING_ROUND` challenges, or that derives zero instead if `zero` is set
+  const FAILING_LABEL: &[u8] = b"r";
+  const FAILING_ROUND: usize = 2;
+
+  #[derive(Clone)]
+  struct FailingTranscript {
+    transcript: Keccak256Transcript<FailingEngine>,
+    round: usize,
+    zero: bool,
+  }
+
+  impl FailingTranscript {
+    fn zero(label: &'static [u8]) -> Self {
+      FailingTranscript {
+        zero: true,
+        ..Self::new(label)
+      }
+    }
+  }
+
+  impl TranscriptEngineTrait<FailingEngine> for FailingTranscript {
+    fn new(label: &'static [u8]) -> Self {
+      FailingTranscript {
+        transcript: Keccak256Transcript::new(label),
+        round: 0,
+        zero: false,
+      }
+    }
+
+    fn squeeze(&mut self, label: &'static [u8]) -> Result<Fr, NovaError> {
+      if label == FAILING_LABEL && self.round == FAILING_ROUND {
+        if self.zero {
+          self.round += 1;
+          return Ok(Fr::ZERO);
+        }
+        return Err(NovaError::TranscriptError {
+          label: String::from_utf8_lossy(label).into_owned(),
+        });
+      }
+      self.round += 1;
+      self.transcript.squeeze(label)
+    }
+
+    fn absorb<T: TranscriptReprTrait<<FailingEngine as Engine>::GE>>(
+      &mut self,
+      label: &'static [u8],
+      o: &T,
+    ) {
+      self.transcript.absorb(label, o)
+    }
+
+    fn dom_sep(&mut self, bytes: &'static [u8]) {
+      self.transcript.dom_sep(bytes)
+    }
+  }
+
+  #[test]
+  fn test_ipa_transcript_error() {
+    let num_vars = 3;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+    let ck = CE::<FailingEngine>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<FailingEngine>::setup(&ck);
+
+    let (poly, point, eval) = random_instance(num_vars, &mut rng);
+    let comm = CE::<FailingEngine>::commit(&ck, &poly);
+    let expected = NovaError::TranscriptError {
+      label: "r".to_string(),
+    };
+
+    let mut transcript = FailingTranscript::new(b"TestEval");
+    let res = EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval);
+    assert_eq!(res.err(), Some(expected.clone()));
+
+    // the failed proof leaves the transcript as it was
+    assert_eq!(transcript.round, 0);
+    assert_eq!(
+      transcript.squeeze(b"c").unwrap(),
+      FailingTranscript::new(b"TestEval").squeeze(b"c").unwrap()
+    );
+
+    // produce an argument with a transcript that does not fail, and check that the error of
+    // the failing transcript is surfaced by the verifier
+    let mut transcript = FailingTranscript::new(b"TestEval");
+    transcript.round = FAILING_ROUND + 1;
+    let arg =
+      EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+
+    let mut transcript = FailingTranscript::new(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg),
+      Err(expected)
+    );
+  }
+
+  #[test]
+  fn test_ipa_degenerate_challenge_prover() {
+    let num_vars = 3;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(76);
+    let ck = CE::<FailingEngine>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<FailingEngine>::setup(&ck);
+    let (zk_pk, _) = ZKEvaluationEngine::<FailingEngine>::setup(&ck);
+    let (poly, point, eval) = random_instance(num_vars, &mut rng);
+    let comm = CE::<FailingEngine>::commit(&ck, &poly);
+
+    // the prover folds with the zero challenge in `push_round`
+    let mut transcript = FailingTranscript::zero(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).err(),
+      Some(NovaError::DegenerateChallenge)
+    );
+
+    // the zero-knowledge prover folds with its own loop
+    let mut transcript = FailingTranscript::zero(b"TestEval");
+    let mut blind_rng = rand::rngs::StdRng::seed_from_u64(1);
+    assert_eq!(
+      ZKEvaluationEngine::prove_with_rng(
+        &ck,
+        &zk_pk,
+        &mut transcript,
+        &comm,
+        &poly,
+        &point,
+        &eval,
+        &mut blind_rng,
+      )
+      .err(),
+      Some(NovaError::DegenerateChallenge)
+    );
+
+    // the verifier inverts the challenges together with `batch_invert`
+    let mut transcript = FailingTranscript::new(b"TestEval");
+    transcript.round = FAILING_ROUND + 1;
+    let arg =
+      EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+    let mut transcript = FailingTranscript::zero(b"TestEval");
+    assert_eq!(
+      EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg),
+      Err(NovaError::DegenerateChallenge)
+    );
   }
 }
@@ -0,0 +1,328 @@
+//! This module implements a hiding variant of the IPA-based `EvaluationEngine`
+use crate::{
+  errors::NovaError,
+  provider::{
+    deferred_check::DeferredCheck,
+    ipa_pc::{
+      batch_invert, check_num_vars, fold_halves, inner_product, round_challenges, tensor_vector,
+      EvaluationEngine, InnerProductInstance, ProverKey, VerifierKey,
+    },
+    pedersen::CommitmentKeyExtTrait,
+    traits::DlogGroup,
+  },
+  spartan::polys::eq::EqPolynomial,
+  traits::{
+    commitment::{CommitmentEngineTrait, Len},
+    evaluation::EvaluationEngineTrait,
+    Engine, TranscriptEngineTrait,
+  },
+  Commitment, CommitmentKey, CE,
+};
+use core::{iter, marker::PhantomData};
+use ff::Field;
+use rand_core::{CryptoRng, OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// Provides a hiding variant of the IPA evaluation engine, whose arguments reveal nothing about
+/// the committed polynomial beyond the evaluation.
+///
+/// `EvaluationEngine` reveals the folded witness `a_hat`, and its round commitments `L` and `R`
+/// are deterministic functions of the witness. Here, each round commitment is blinded with a
+/// fresh multiple of a blinding generator `H`, and instead of revealing `a_hat` and the total
+/// blind, the prover shows knowledge of both with a final Schnorr-style step. The blinds are
+/// drawn from an RNG supplied to `prove_with_rng`, or from `OsRng` in `prove`. The commitment
+/// to the polynomial itself is taken as given, so it must be hiding for the whole protocol to be.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ZKEvaluationEngine<E: Engine> {
+  _p: PhantomData<E>,
+}
+
+/// Provides the prover key of `ZKEvaluationEngine`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ZKProverKey<E: Engine> {
+  pk: ProverKey<E>,
+  ck_h: CommitmentKey<E>,
+}
+
+/// Provides the verifier key of `ZKEvaluationEngine`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ZKVerifierKey<E: Engine> {
+  vk: VerifierKey<E>,
+  ck_h: CommitmentKey<E>,
+}
+
+/// A hiding inner product argument, as produced by `ZKEvaluationEngine`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ZKInnerProductArgument<E: Engine> {
+  L_vec: Vec<Commitment<E>>,
+  R_vec: Vec<Commitment<E>>,
+  // the commitment of the final step to the random masks of `a_hat` and of the total blind
+  T: Commitment<E>,
+  z_a: E::Scalar,
+  z_blind: E::Scalar,
+}
+
+impl<E> EvaluationEngineTrait<E> for ZKEvaluationEngine<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+  CommitmentKey<E>: CommitmentKeyExtTrait<E>,
+{
+  type ProverKey = ZKProverKey<E>;
+  type VerifierKey = ZKVerifierKey<E>;
+  type EvaluationArgument = ZKInnerProductArgument<E>;
+
+  fn setup(
+    ck: &<<E as Engine>::CE as CommitmentEngineTrait<E>>::CommitmentKey,
+  ) -> (Self::ProverKey, Self::VerifierKey) {
+    let (pk, vk) = EvaluationEngine::<E>::setup(ck);
+    let ck_h = E::CE::setup(b"ipa-zk-blind", 1);
+
+    (
+      ZKProverKey {
+        pk,
+        ck_h: ck_h.clone(),
+      },
+      ZKVerifierKey { vk, ck_h },
+    )
+  }
+
+  fn prove(
+    ck: &CommitmentKey<E>,
+    pk: &Self::ProverKey,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    poly: &[E::Scalar],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+  ) -> Result<Self::EvaluationArgument, NovaError> {
+    Self::prove_with_rng(ck, pk, transcript, comm, poly, point, eval, &mut OsRng)
+  }
+
+  fn verify(
+    vk: &Self::VerifierKey,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &Self::EvaluationArgument,
+  ) -> Result<(), NovaError> {
+    vk.vk.check_point(point)?;
+
+    let n = 1 << point.len();
+    if arg.L_vec.len() >= 32 || arg.L_vec.len() != arg.R_vec.len() || n != 1 << arg.L_vec.len() {
+      return Err(NovaError::ProofLengthMismatch);
+    }
+    if vk.vk.ck_v.length() < n {
+      return Err(NovaError::InvalidCommitmentKeyLength);
+    }
+
+    let U = InnerProductInstance::<E>::new_eq(comm, point, eval);
+    transcript.dom_sep(Self::protocol_name());
+    transcript.absorb(b"U", &U);
+    let r_c = transcript.squeeze(b"r")?;
+
+    let r = round_challenges::<E>(transcript, &arg.L_vec, &arg.R_vec, vk.vk.round_order)?;
+    transcript.absorb(b"T", &arg.T);
+    let e = transcript.squeeze(b"e")?;
+
+    let r_square = r.iter().map(|r_i| r_i.square()).collect::<Vec<E::Scalar>>();
+    let r_inverse = batch_invert(&r)?;
+    let r_inverse_square = r_inverse
+      .iter()
+      .map(|r_i| r_i.square())
+      .collect::<Vec<E::Scalar>>();
+    let s = tensor_vector(&r_square, &r_inverse);
+    let b_hat = U.b_vec.inner_product_with_tensor(&s, &r, &r_inverse);
+
+    // with `G_hat = <s, ck>` and `P_hat = <r_square, L_vec> + <r_inverse_square, R_vec> + comm +
+    // c * r_c * ck_c`, the argument verifies iff
+    // z_a * (G_hat + b_hat * r_c * ck_c) + z_blind * H = T + e * P_hat
+    let bases = {
+      let (ck, _) = vk.vk.ck_v.split_at(n);
+      let (ck_c, _) = vk.vk.ck_s.split_at(1);
+      let ck_L = CommitmentKey::<E>::reinterpret_commitments_as_ck(&arg.L_vec)?;
+      let ck_R = CommitmentKey::<E>::reinterpret_commitments_as_ck(&arg.R_vec)?;
+      let ck_P = CommitmentKey::<E>::reinterpret_commitments_as_ck(&[U.comm_a_vec])?;
+      let ck_T = CommitmentKey::<E>::reinterpret_commitments_as_ck(&[arg.T])?;
+      ck_c
+        .combine(&ck)
+        .combine(&ck_L)
+        .combine(&ck_R)
+        .combine(&ck_P)
+        .combine(&vk.ck_h)
+        .combine(&ck_T)
+    };
+
+    let scalars = iter::once(r_c * (arg.z_a * b_hat - e * U.c))
+      .chain(s.iter().map(|s_i| arg.z_a * s_i))
+      .chain(r_square.iter().map(|r_i| -e * r_i))
+      .chain(r_inverse_square.iter().map(|r_i| -e * r_i))
+      .chain([-e, arg.z_blind, -E::Scalar::ONE])
+      .collect::<Vec<E::Scalar>>();
+
+    DeferredCheck::<E> {
+      bases,
+      scalars,
+      num_key_bases: n + 1,
+    }
+    .check()
+  }
+}
+
+impl<E> ZKEvaluationEngine<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+  CommitmentKey<E>: CommitmentKeyExtTrait<E>,
+{
+  const fn protocol_name() -> &'static [u8] {
+    b"ZKIPA"
+  }
+
+  /// Proves an evaluation like `prove`, drawing the blinds from `rng`
+  pub fn prove_with_rng<R: RngCore + CryptoRng>(
+    ck: &CommitmentKey<E>,
+    pk: &ZKProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    poly: &[E::Scalar],
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    rng: &mut R,
+  ) -> Result<ZKInnerProductArgument<E>, NovaError> {
+    check_num_vars(point, pk.pk.max_num_vars)?;
+
+    let mut b_vec = EqPolynomial::new(point.to_vec()).evals();
+    if poly.len() != b_vec.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+    if ck.length() < b_vec.len() {
+      return Err(NovaError::InvalidCommitmentKeyLength);
+    }
+
+    let U = InnerProductInstance::<E>::new(comm, &b_vec, eval);
+    transcript.dom_sep(Self::protocol_name());
+    transcript.absorb(b"U", &U);
+    let r_c = transcript.squeeze(b"r")?;
+    let ck_c = pk.pk.ck_s.scale(&r_c);
+    let ck_h = &pk.ck_h;
+
+    let (mut ck, _) = ck.split_at(b_vec.len());
+    let mut a_vec = poly.to_vec();
+    let (mut L_vec, mut R_vec) = (Vec::new(), Vec::new());
+
+    // the commitment to the polynomial is not blinded, and each round adds to the blind
+    let mut blind = E::Scalar::ZERO;
+    while a_vec.len() > 1 {
+      let n = a_vec.len();
+      let (ck_L, ck_R) = ck.split_at(n / 2);
+      let c_L = inner_product(&a_vec[..n / 2], &b_vec[n / 2..]);
+      let c_R = inner_product(&a_vec[n / 2..], &b_vec[..n / 2]);
+      let (blind_L, blind_R) = (E::Scalar::random(&mut *rng), E::Scalar::random(&mut *rng));
+
+      let L = CE::<E>::commit(&ck_R, &a_vec[..n / 2])
+        + CE::<E>::commit(&ck_c, &[c_L])
+        + CE::<E>::commit(ck_h, &[blind_L]);
+      let R = CE::<E>::commit(&ck_L, &a_vec[n / 2..])
+        + CE::<E>::commit(&ck_c, &[c_R])
+        + CE::<E>::commit(ck_h, &[blind_R]);
+      transcript.absorb(b"L", &L);
+      transcript.absorb(b"R", &R);
+      let r = transcript.squeeze(b"r")?;
+      let r_inverse = Option::from(r.invert()).ok_or(NovaError::DegenerateChallenge)?;
+
+      fold_halves(&mut a_vec, &r, &r_inverse);
+      fold_halves(&mut b_vec, &r_inverse, &r);
+      ck = ck.fold(&r_inverse, &r);
+      blind += r.square() * blind_L + r_inverse.square() * blind_R;
+      L_vec.push(L);
+      R_vec.push(R);
+    }
+
+    // the folded commitment is `a_hat * (G_hat + b_hat * ck_c) + blind * H`, and the prover
+    // shows that it knows `a_hat` and `blind` without revealing them
+    let (a_hat, b_hat) = (a_vec[0], b_vec[0]);
+    let (d, s) = (E::Scalar::random(&mut *rng), E::Scalar::random(&mut *rng));
+    let T = CE::<E>::commit(&ck, &[d])
+      + CE::<E>::commit(&ck_c, &[d * b_hat])
+      + CE::<E>::commit(ck_h, &[s]);
+    transcript.absorb(b"T", &T);
+    let e = transcript.squeeze(b"e")?;
+
+    Ok(ZKInnerProductArgument {
+      L_vec,
+      R_vec,
+      T,
+      z_a: d + e * a_hat,
+      z_blind: s + e * blind,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::provider::{
+    ipa_pc::tests::random_instance, keccak::Keccak256Transcript, PallasEngine,
+  };
+  use rand::SeedableRng;
+
+  type E = PallasEngine;
+  type Fr = <E as Engine>::Scalar;
+
+  #[test]
+  fn test_zk_ipa() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(40);
+    for num_vars in [0, 1, 5] {
+      let ck = CE::<E>::setup(b"test", 1 << num_vars);
+      let (pk, vk) = ZKEvaluationEngine::<E>::setup(&ck);
+      let (poly, point, eval) = random_instance(num_vars, &mut rng);
+      let comm = CE::<E>::commit(&ck, &poly);
+
+      let prove = |seed: u64| {
+        let mut transcript = Keccak256Transcript::new(b"TestEval");
+        let mut blind_rng = rand::rngs::StdRng::seed_from_u64(seed);
+        ZKEvaluationEngine::prove_with_rng(
+          &ck,
+          &pk,
+          &mut transcript,
+          &comm,
+          &poly,
+          &point,
+          &eval,
+          &mut blind_rng,
+        )
+        .unwrap()
+      };
+      let verify = |eval: &Fr, arg: &ZKInnerProductArgument<E>| {
+        let mut transcript = Keccak256Transcript::new(b"TestEval");
+        ZKEvaluationEngine::verify(&vk, &mut transcript, &comm, &point, eval, arg)
+      };
+
+      // two proofs of the same evaluation differ, and both verify
+      let (arg1, arg2) = (prove(1), prove(2));
+      assert_ne!(
+        bincode::serialize(&arg1).unwrap(),
+        bincode::serialize(&arg2).unwrap()
+      );
+      assert_eq!(
+        bincode::serialize(&arg1).unwrap(),
+        bincode::serialize(&prove(1)).unwrap()
+      );
+      assert!(verify(&eval, &arg1).is_ok());
+      assert!(verify(&eval, &arg2).is_ok());
+      assert_eq!(
+        verify(&(eval + Fr::ONE), &arg1),
+        Err(NovaError::FinalCheckFailed)
+      );
+
+      let mut tampered = arg1.clone();
+      tampered.z_a += Fr::ONE;
+      assert_eq!(verify(&eval, &tampered), Err(NovaError::FinalCheckFailed));
+    }
+  }
+}
@@ -13,53 +13,82 @@ const KECCAK256_STATE_SIZE: usize = 64;
 const KECCAK256_PREFIX_CHALLENGE_LO: u8 = 0;
 const KECCAK256_PREFIX_CHALLENGE_HI: u8 = 1;
 
+/// The largest number of challenges that `Keccak256Transcript` derives from one state update
+pub const MAX_SQUEEZE_RATE: usize = 128;
+
 /// Provides an implementation of `TranscriptEngine`
 #[derive(Debug, Clone)]
 pub struct Keccak256Transcript<E: Engine> {
   round: u16,
   state: [u8; KECCAK256_STATE_SIZE],
   transcript: Keccak256,
+  squeeze_rate: usize,
   _p: PhantomData<E>,
 }
 
 fn compute_updated_state(keccak_instance: Keccak256, input: &[u8]) -> [u8; KECCAK256_STATE_SIZE] {
+  compute_updated_states(keccak_instance, input, 1)[0]
+}
+
+// hashes `input` once, and derives `k` outputs from the result, where the `j`-th output is
+// separated from the others by the prefixes `2j` and `2j + 1` of its two halves
+fn compute_updated_states(
+  keccak_instance: Keccak256,
+  input: &[u8],
+  k: usize,
+) -> Vec<[u8; KECCAK256_STATE_SIZE]> {
   let mut updated_instance = keccak_instance;
   updated_instance.update(input);
 
-  let input_lo = &[KECCAK256_PREFIX_CHALLENGE_LO];
-  let input_hi = &[KECCAK256_PREFIX_CHALLENGE_HI];
+  (0..k)
+    .map(|j| {
+      let input_lo = &[KECCAK256_PREFIX_CHALLENGE_LO + 2 * j as u8];
+      let input_hi = &[KECCAK256_PREFIX_CHALLENGE_HI + 2 * j as u8];
 
-  let mut hasher_lo = updated_instance.clone();
-  let mut hasher_hi = updated_instance;
+      let mut hasher_lo = updated_instance.clone();
+      let mut hasher_hi = updated_instance.clone();
 
-  hasher_lo.update(input_lo);
-  hasher_hi.update(input_hi);
+      hasher_lo.update(input_lo);
+      hasher_hi.update(input_hi);
 
-  let output_lo = hasher_lo.finalize();
-  let output_hi = hasher_hi.finalize();
+      let output_lo = hasher_lo.finalize();
+      let output_hi = hasher_hi.finalize();
 
-  [output_lo, output_hi]
-    .concat()
-    .as_slice()
-    .try_into()
-    .unwrap()
+      [output_lo, output_hi]
+        .concat()
+        .as_slice()
+        .try_into()
+        .unwrap()
+    })
+    .collect()
 }
 
-impl<E: Engine> TranscriptEngineTrait<E> for Keccak256Transcript<E> {
-  fn new(label: &'static [u8]) -> Self {
-    let keccak_instance = Keccak256::new();
-    let input = [PERSONA_TAG, label].concat();
-    let output = compute_updated_state(keccak_instance.clone(), &input);
-
-    Self {
-      round: 0u16,
-      state: output,
-      transcript: keccak_instance,
-      _p: PhantomData,
+impl<E: Engine> Keccak256Transcript<E> {
+  /// Sets the number of challenges that `squeeze_vec` derives from each update of the state,
+  /// which is one by default. With a rate of `k`, the input of the round is hashed once, and
+  /// `k` challenges are derived from it with distinct prefixes, instead of hashing the input
+  /// anew for each challenge. Returns `NovaError::InvalidInputLength` if the rate is zero or
+  /// exceeds `MAX_SQUEEZE_RATE`.
+  ///
+  /// The challenges derived from one update are independent as outputs of the hash function,
+  /// but no message can be absorbed between them, so a higher rate is only sound for a
+  /// protocol that would not absorb a message between these challenges anyway. A transcript
+  /// with a rate other than one squeezes different vectors of challenges, so the prover and the
+  /// verifier must agree on the rate.
+  pub fn with_squeeze_rate(mut self, rate: usize) -> Result<Self, NovaError> {
+    if rate == 0 || rate > MAX_SQUEEZE_RATE {
+      return Err(NovaError::InvalidInputLength);
     }
+    self.squeeze_rate = rate;
+    Ok(self)
   }
 
-  fn squeeze(&mut self, label: &'static [u8]) -> Result<E::Scalar, NovaError> {
+  // updates the state with the input of the round, and returns `k` outputs derived from it
+  fn squeeze_outputs(
+    &mut self,
+    label: &'static [u8],
+    k: usize,
+  ) -> Result<Vec<[u8; KECCAK256_STATE_SIZE]>, NovaError> {
     // we gather the full input from the round, preceded by the current state of the transcript
     let input = [
       DOM_SEP_TAG,
@@ -68,26 +97,60 @@ impl<E: Engine> TranscriptEngineTrait<E> for Keccak256Transcript<E> {
       label,
     ]
     .concat();
-    let output = compute_updated_state(self.transcript.clone(), &input);
+    let outputs = compute_updated_states(self.transcript.clone(), &input, k);
 
     // update state
     self.round = {
       if let Some(v) = self.round.checked_add(1) {
         v
       } else {
-        return Err(NovaError::InternalTranscriptError);
+        return Err(NovaError::TranscriptError {
+          label: String::from_utf8_lossy(label).into_owned(),
+        });
       }
     };
-    self.state.copy_from_slice(&output);
+    self.state.copy_from_slice(&outputs[k - 1]);
     self.transcript = Keccak256::new();
 
+    Ok(outputs)
+  }
+}
+
+impl<E: Engine> TranscriptEngineTrait<E> for Keccak256Transcript<E> {
+  fn new(label: &'static [u8]) -> Self {
+    let keccak_instance = Keccak256::new();
+    let input = [PERSONA_TAG, label].concat();
+    let output = compute_updated_state(keccak_instance.clone(), &input);
+
+    Self {
+      round: 0u16,
+      state: output,
+      transcript: keccak_instance,
+      squeeze_rate: 1,
+      _p: PhantomData,
+    }
+  }
+
+  fn squeeze(&mut self, label: &'static [u8]) -> Result<E::Scalar, NovaError> {
+    let output = self.squeeze_outputs(label, 1)?;
+
     // squeeze out a challenge
-    Ok(E::Scalar::from_uniform(&output))
+    Ok(E::Scalar::from_uniform(&output[0]))
+  }
+
+  fn squeeze_vec(&mut self, label: &'static [u8], n: usize) -> Result<Vec<E::Scalar>, NovaError> {
+    let mut challenges = Vec::with_capacity(n);
+    while challenges.len() < n {
+      let k = self.squeeze_rate.min(n - challenges.len());
+      let outputs = self.squeeze_outputs(label, k)?;
+      challenges.extend(outputs.iter().map(|output| E::Scalar::from_uniform(output)));
+    }
+    Ok(challenges)
   }
 
   fn absorb<T: TranscriptReprTrait<E::GE>>(&mut self, label: &'static [u8], o: &T) {
     self.transcript.update(label);
-    self.transcript.update(&o.to_transcript_bytes());
+    self.transcript.update(o.to_transcript_bytes());
   }
 
   fn dom_sep(&mut self, bytes: &'static [u8]) {
@@ -99,16 +162,29 @@ impl<E: Engine> TranscriptEngineTrait<E> for Keccak256Transcript<E> {
 #[cfg(test)]
 mod tests {
   use crate::{
+    errors::NovaError,
     provider::keccak::Keccak256Transcript,
     provider::{
       Bn256EngineKZG, GrumpkinEngine, PallasEngine, Secp256k1Engine, Secq256k1Engine, VestaEngine,
     },
-    traits::{Engine, PrimeFieldExt, TranscriptEngineTrait, TranscriptReprTrait},
+    traits::{Engine, PrimeFieldExt, TranscriptEngineTrait, TranscriptReprTrait, VrfVerifier},
   };
   use ff::PrimeField;
   use rand::Rng;
   use sha3::{Digest, Keccak256};
 
+  #[test]
+  fn test_keccak_transcript_exhausted() {
+    let mut transcript: Keccak256Transcript<PallasEngine> = Keccak256Transcript::new(b"test");
+    transcript.round = u16::MAX;
+    assert_eq!(
+      transcript.squeeze(b"c1"),
+      Err(NovaError::TranscriptError {
+        label: "c1".to_string()
+      })
+    );
+  }
+
   fn test_keccak_transcript_with<E: Engine>(expected_h1: &'static str, expected_h2: &'static str) {
     let mut transcript: Keccak256Transcript<E> = Keccak256Transcript::new(b"test");
 
@@ -153,6 +229,114 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_keccak_transcript_squeeze_rate() {
+    type E = PallasEngine;
+    let transcript = || {
+      let mut transcript: Keccak256Transcript<E> = Keccak256Transcript::new(b"test");
+      transcript.absorb(b"s", &<E as Engine>::Scalar::from(3u64));
+      transcript
+    };
+
+    // the default rate squeezes the same challenges as `squeeze`, one per state update
+    let mut expected = transcript();
+    let expected = (0..5)
+      .map(|_| expected.squeeze(b"c").unwrap())
+      .collect::<Vec<_>>();
+    let mut t = transcript();
+    assert_eq!(t.squeeze_vec(b"c", 5).unwrap(), expected);
+    assert_eq!(t.round, 5);
+
+    // a rate of two derives two challenges per state update, deterministically
+    let squeeze = |n| {
+      let mut t = transcript().with_squeeze_rate(2).unwrap();
+      let challenges = t.squeeze_vec(b"c", n).unwrap();
+      (challenges, t.round)
+    };
+    let (challenges, round) = squeeze(5);
+    assert_eq!(round, 3);
+    assert_eq!(squeeze(5).0, challenges);
+    assert_eq!(challenges[0], expected[0]);
+    assert_ne!(challenges[1], expected[1]);
+    for i in 0..challenges.len() {
+      assert!(!challenges[i + 1..].contains(&challenges[i]));
+    }
+
+    assert!(transcript().with_squeeze_rate(0).is_err());
+    assert!(transcript()
+      .with_squeeze_rate(super::MAX_SQUEEZE_RATE + 1)
+      .is_err());
+  }
+
+  // a keyed stand-in for a VRF: the output is a hash of the key and the input, and the proof
+  // is a hash of the key and the output
+  struct KeyedVrf([u8; 32]);
+
+  impl KeyedVrf {
+    fn eval(&self, input: &[u8]) -> (Vec<u8>, Vec<u8>) {
+      let output = Keccak256::digest([&self.0, input].concat()).to_vec();
+      let proof = Keccak256::digest([&self.0, output.as_slice()].concat()).to_vec();
+      (output, proof)
+    }
+  }
+
+  impl VrfVerifier for KeyedVrf {
+    fn verify(&self, input: &[u8], output: &[u8], proof: &[u8]) -> bool {
+      self.eval(input) == (output.to_vec(), proof.to_vec())
+    }
+  }
+
+  #[test]
+  fn test_keccak_transcript_vrf() {
+    let vrf = KeyedVrf([7; 32]);
+    let squeeze = |input: &[u8], output: &[u8], proof: &[u8]| {
+      let mut transcript: Keccak256Transcript<PallasEngine> = Keccak256Transcript::new(b"test");
+      transcript.absorb(b"s", &<PallasEngine as Engine>::Scalar::from(3u64));
+      transcript.absorb_vrf(&vrf, input, output, proof)?;
+      transcript.squeeze(b"c")
+    };
+
+    // a valid VRF output yields deterministic challenges, which depend on the output
+    let (output, proof) = vrf.eval(b"round 1");
+    let c = squeeze(b"round 1", &output, &proof).unwrap();
+    assert_eq!(squeeze(b"round 1", &output, &proof).unwrap(), c);
+    let (output2, proof2) = vrf.eval(b"round 2");
+    assert_ne!(squeeze(b"round 2", &output2, &proof2).unwrap(), c);
+
+    let mut transcript: Keccak256Transcript<PallasEngine> = Keccak256Transcript::new(b"test");
+    transcript.absorb(b"s", &<PallasEngine as Engine>::Scalar::from(3u64));
+    assert_ne!(transcript.squeeze(b"c").unwrap(), c);
+
+    // an output that the proof does not attest is rejected
+    assert_eq!(
+      squeeze(b"round 1", &output2, &proof),
+      Err(NovaError::InvalidVrfProof)
+    );
+    assert_eq!(
+      squeeze(b"round 2", &output, &proof),
+      Err(NovaError::InvalidVrfProof)
+    );
+  }
+
+  #[test]
+  fn test_keccak_transcript_checkpoint() {
+    type E = PallasEngine;
+    let mut transcript: Keccak256Transcript<E> = Keccak256Transcript::new(b"test");
+    transcript.absorb(b"s", &<E as Engine>::Scalar::from(3u64));
+    let mut fresh = transcript.clone();
+
+    // a transcript that advances past a checkpoint and is then restored derives the same
+    // challenges as one that never advanced
+    let checkpoint = transcript.checkpoint();
+    transcript.absorb(b"s", &<E as Engine>::Scalar::from(5u64));
+    let c_speculative = transcript.squeeze(b"c").unwrap();
+    transcript.restore(checkpoint);
+
+    let c = transcript.squeeze(b"c").unwrap();
+    assert_eq!(c, fresh.squeeze(b"c").unwrap());
+    assert_ne!(c, c_speculative);
+  }
+
   #[test]
   fn test_keccak_example() {
     let mut hasher = Keccak256::new();
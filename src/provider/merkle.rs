@@ -0,0 +1,187 @@
+//! This module implements a binary Merkle tree with SHA3-256 over a vector of field elements,
+//! used to commit to a polynomial with a hash function alongside a group-based commitment.
+//!
+//! Leaves and internal nodes are hashed with distinct prefixes, so that an internal node
+//! cannot be passed off as a leaf.
+use crate::{
+  errors::NovaError,
+  traits::{Group, TranscriptReprTrait},
+};
+use ff::PrimeField;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+fn hash_leaf(bytes: &[u8]) -> [u8; 32] {
+  let mut hasher = Sha3_256::new();
+  hasher.update([LEAF_PREFIX]);
+  hasher.update(bytes);
+  hasher.finalize().into()
+}
+
+fn hash_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+  let mut hasher = Sha3_256::new();
+  hasher.update([NODE_PREFIX]);
+  hasher.update(left);
+  hasher.update(right);
+  hasher.finalize().into()
+}
+
+/// The root of a Merkle tree, which can be absorbed in a transcript
+pub(crate) struct MerkleRoot(pub(crate) [u8; 32]);
+
+impl<G: Group> TranscriptReprTrait<G> for MerkleRoot {
+  fn to_transcript_bytes(&self) -> Vec<u8> {
+    self.0.to_vec()
+  }
+}
+
+/// A Merkle tree over a vector whose length is a power of two
+pub(crate) struct MerkleTree {
+  // the layers of the tree, from the leaves up to the root
+  layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+  pub(crate) fn new<F: PrimeField>(v: &[F]) -> Result<Self, NovaError> {
+    Self::from_leaves(v.iter().map(|v_i| v_i.to_repr()))
+  }
+
+  // builds a tree whose leaves are the given byte strings, of which there must be a power of two
+  pub(crate) fn from_leaves<T, I>(leaves: I) -> Result<Self, NovaError>
+  where
+    T: AsRef<[u8]>,
+    I: IntoIterator<Item = T>,
+  {
+    let leaves = leaves
+      .into_iter()
+      .map(|leaf| hash_leaf(leaf.as_ref()))
+      .collect::<Vec<_>>();
+    if !leaves.len().is_power_of_two() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let mut layers = vec![leaves];
+    while layers.last().unwrap().len() > 1 {
+      let layer = layers
+        .last()
+        .unwrap()
+        .chunks(2)
+        .map(|pair| hash_node(&pair[0], &pair[1]))
+        .collect();
+      layers.push(layer);
+    }
+
+    Ok(MerkleTree { layers })
+  }
+
+  pub(crate) fn root(&self) -> [u8; 32] {
+    self.layers.last().unwrap()[0]
+  }
+
+  // returns the siblings of the nodes on the path from leaf `index` to the root
+  pub(crate) fn path(&self, index: usize) -> MerklePath {
+    let siblings = self.layers[..self.layers.len() - 1]
+      .iter()
+      .enumerate()
+      .map(|(height, layer)| layer[(index >> height) ^ 1])
+      .collect();
+
+    MerklePath { siblings }
+  }
+}
+
+/// The siblings of the nodes on the path from a leaf to the root of a Merkle tree
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct MerklePath {
+  siblings: Vec<[u8; 32]>,
+}
+
+impl MerklePath {
+  // checks that `v` is the leaf at `index` of a tree with `num_leaves` leaves and root `root`
+  pub(crate) fn verify<F: PrimeField>(
+    &self,
+    root: &[u8; 32],
+    num_leaves: usize,
+    index: usize,
+    v: &F,
+  ) -> Result<(), NovaError> {
+    self.verify_leaf(root, num_leaves, index, v.to_repr().as_ref())
+  }
+
+  // like `verify`, for a tree built with `MerkleTree::from_leaves`
+  pub(crate) fn verify_leaf(
+    &self,
+    root: &[u8; 32],
+    num_leaves: usize,
+    index: usize,
+    leaf: &[u8],
+  ) -> Result<(), NovaError> {
+    if !num_leaves.is_power_of_two()
+      || index >= num_leaves
+      || self.siblings.len() != num_leaves.ilog2() as usize
+    {
+      return Err(NovaError::InvalidMerklePath);
+    }
+
+    let computed =
+      self
+        .siblings
+        .iter()
+        .enumerate()
+        .fold(hash_leaf(leaf), |node, (height, sibling)| {
+          if (index >> height) & 1 == 0 {
+            hash_node(&node, sibling)
+          } else {
+            hash_node(sibling, &node)
+          }
+        });
+    if computed != *root {
+      return Err(NovaError::InvalidMerklePath);
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use ff::Field;
+  use pasta_curves::pallas;
+  use rand::SeedableRng;
+
+  type F = pallas::Scalar;
+
+  #[test]
+  fn test_merkle_path() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    for n in [1, 2, 16] {
+      let v = (0..n).map(|_| F::random(&mut rng)).collect::<Vec<F>>();
+      let tree = MerkleTree::new(&v).unwrap();
+      let root = tree.root();
+
+      for (i, v_i) in v.iter().enumerate() {
+        let path = tree.path(i);
+        assert!(path.verify(&root, n, i, v_i).is_ok());
+        assert_eq!(
+          path.verify(&root, n, i, &(*v_i + F::ONE)),
+          Err(NovaError::InvalidMerklePath)
+        );
+        assert_eq!(
+          path.verify(&root, n, n, v_i),
+          Err(NovaError::InvalidMerklePath)
+        );
+        if n > 1 {
+          assert_eq!(
+            path.verify(&root, n, i ^ 1, v_i),
+            Err(NovaError::InvalidMerklePath)
+          );
+        }
+      }
+    }
+
+    assert!(MerkleTree::new(&[F::ONE; 3]).is_err());
+  }
+}
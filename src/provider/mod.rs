@@ -4,16 +4,29 @@
 pub mod hyperkzg;
 pub mod ipa_pc;
 
+// public module so that Poseidon constants can be imported for the random oracles
+pub mod poseidon;
+
 // crate-public modules, made crate-public mostly for tests
 pub(crate) mod bn256_grumpkin;
 pub(crate) mod pasta;
 pub(crate) mod pedersen;
-pub(crate) mod poseidon;
 pub(crate) mod secp_secq;
 pub(crate) mod traits;
 
 // crate-private modules
+mod commitment_tree;
+mod deferred_check;
+mod generator_table;
+mod ipa_zk;
 mod keccak;
+mod merkle;
+mod msm_service;
+mod proof_bundle;
+mod ptau;
+mod sharded_prover;
+
+pub use pedersen::{CommitmentKeyRef, MultiCommit, PartialCommitment, PreparedCommitmentKey};
 
 use crate::{
   provider::{
@@ -0,0 +1,40 @@
+//! This module defines the service that computes the multi-scalar multiplications of the IPA
+//! verifier
+use crate::{
+  errors::NovaError,
+  traits::{
+    commitment::{CommitmentEngineTrait, Len},
+    Engine,
+  },
+  Commitment, CommitmentKey, CE,
+};
+
+/// Computes the multi-scalar multiplications of a verifier, for example on a remote
+/// accelerator that batches the requests of many verifications
+pub trait MsmService<E: Engine> {
+  /// Returns the multi-scalar multiplication of `bases` by `scalars`, which have the same
+  /// length, i.e., the commitment to `scalars` with the generators `bases`
+  fn msm(
+    &self,
+    bases: &CommitmentKey<E>,
+    scalars: &[E::Scalar],
+  ) -> Result<Commitment<E>, NovaError>;
+}
+
+/// An `MsmService` that computes multi-scalar multiplications in process, with the
+/// commitment engine
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LocalMsmService;
+
+impl<E: Engine> MsmService<E> for LocalMsmService {
+  fn msm(
+    &self,
+    bases: &CommitmentKey<E>,
+    scalars: &[E::Scalar],
+  ) -> Result<Commitment<E>, NovaError> {
+    if bases.length() != scalars.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+    Ok(CE::<E>::commit(bases, scalars))
+  }
+}
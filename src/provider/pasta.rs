@@ -1,4 +1,5 @@
 //! This module implements the Nova traits for `pallas::Point`, `pallas::Scalar`, `vesta::Point`, `vesta::Scalar`.
+use crate::parallel::prelude::*;
 use crate::{
   provider::traits::DlogGroup,
   traits::{Group, PrimeFieldExt, TranscriptReprTrait},
@@ -14,7 +15,6 @@ use pasta_curves::{
   group::{cofactor::CofactorCurveAffine, Curve, Group as AnotherGroup},
   pallas, vesta, Ep, EpAffine, Eq, EqAffine,
 };
-use rayon::prelude::*;
 use sha3::Shake256;
 use std::io::Read;
 
@@ -65,6 +65,12 @@ macro_rules! impl_traits {
         $name::Point::from(*p)
       }
 
+      fn batch_affine(points: &[Self]) -> Vec<Self::AffineGroupElement> {
+        let mut affine = vec![$name_curve_affine::identity(); points.len()];
+        <Self as Curve>::batch_normalize(points, &mut affine);
+        affine
+      }
+
       fn from_label(label: &'static [u8], n: usize) -> Vec<Self::AffineGroupElement> {
         let mut shake = Shake256::default();
         shake.update(label);
@@ -83,7 +89,7 @@ macro_rules! impl_traits {
           })
           .collect();
 
-        let num_threads = rayon::current_num_threads();
+        let num_threads = crate::parallel::current_num_threads();
         if ck_proj.len() > num_threads {
           let chunk = (ck_proj.len() as f64 / num_threads as f64).ceil() as usize;
           (0..num_threads)
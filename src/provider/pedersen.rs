@@ -1,7 +1,9 @@
 //! This module provides an implementation of a commitment engine
+use crate::parallel::prelude::*;
 use crate::{
   errors::NovaError,
   provider::traits::DlogGroup,
+  spartan::polys::eq::EqPolynomial,
   traits::{
     commitment::{CommitmentEngineTrait, CommitmentTrait, Len},
     AbsorbInROTrait, Engine, ROTrait, TranscriptReprTrait,
@@ -9,21 +11,118 @@ use crate::{
 };
 use core::{
   fmt::Debug,
+  hash::{Hash, Hasher},
+  iter,
   marker::PhantomData,
-  ops::{Add, Mul, MulAssign},
+  ops::{Add, Mul, MulAssign, Neg, Range, Sub},
 };
-use ff::Field;
-use rayon::prelude::*;
+use ff::{Field, PrimeField, PrimeFieldBits};
+use group::GroupEncoding;
+use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::{collections::HashSet, sync::Arc};
+use subtle::{Choice, ConstantTimeEq};
 
-/// A type that holds commitment generators
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// A type that holds commitment generators.
+///
+/// Keys are compared and hashed by a digest of their generators, which is computed on first
+/// use and cached, so that a key can be used as a map key without comparing every generator.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CommitmentKey<E>
 where
   E: Engine,
   E::GE: DlogGroup,
 {
   ck: Vec<<E::GE as DlogGroup>::AffineGroupElement>,
+  #[serde(skip, default = "OnceCell::new")]
+  digest: OnceCell<[u8; 32]>,
+}
+
+impl<E> CommitmentKey<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  fn new(ck: Vec<<E::GE as DlogGroup>::AffineGroupElement>) -> Self {
+    CommitmentKey {
+      ck,
+      digest: OnceCell::new(),
+    }
+  }
+
+  /// Returns a digest of the generators, which is computed once and cached
+  pub fn digest(&self) -> [u8; 32] {
+    *self.digest.get_or_init(|| {
+      let mut hasher = Sha3_256::new();
+      hasher.update((self.ck.len() as u64).to_le_bytes());
+      for g in &self.ck {
+        hasher.update(g.to_transcript_bytes());
+      }
+      hasher.finalize().into()
+    })
+  }
+
+  /// Returns true if `self` and `other` have no generator in common. Keys derived from the
+  /// same label share generators, and keys meant to be independent must not: a prover that
+  /// knows relations between the generators of two protocols can break the binding of a
+  /// commitment used across them. The keys are compared via digests of their generators.
+  pub fn disjoint_from(&self, other: &Self) -> bool {
+    let digests = self
+      .ck
+      .iter()
+      .map(Self::generator_digest)
+      .collect::<HashSet<_>>();
+    !other
+      .ck
+      .iter()
+      .any(|g| digests.contains(&Self::generator_digest(g)))
+  }
+
+  /// Derives a commitment key like `CommitmentEngine::setup`, and checks that it is disjoint
+  /// from each key in `others`. Returns `NovaError::OverlappingCommitmentKeys` otherwise.
+  pub fn setup_disjoint(
+    label: &'static [u8],
+    n: usize,
+    others: &[&Self],
+  ) -> Result<Self, NovaError> {
+    let ck = CommitmentKey::new(E::GE::from_label(label, n.next_power_of_two()));
+    if others.iter().any(|other| !ck.disjoint_from(other)) {
+      return Err(NovaError::OverlappingCommitmentKeys);
+    }
+    Ok(ck)
+  }
+
+  fn generator_digest(g: &<E::GE as DlogGroup>::AffineGroupElement) -> [u8; 32] {
+    Sha3_256::digest(g.to_transcript_bytes()).into()
+  }
+}
+
+impl<E> PartialEq for CommitmentKey<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  fn eq(&self, other: &Self) -> bool {
+    self.digest() == other.digest()
+  }
+}
+
+impl<E> Eq for CommitmentKey<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+}
+
+impl<E> Hash for CommitmentKey<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.digest().hash(state);
+  }
 }
 
 impl<E> Len for CommitmentKey<E>
@@ -36,6 +135,630 @@ where
   }
 }
 
+impl<E> CommitmentKey<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  /// Returns a borrowed view of the generators in `self`
+  pub fn as_view(&self) -> CommitmentKeyRef<'_, E> {
+    CommitmentKeyRef { ck: &self.ck }
+  }
+
+  /// Returns an iterator over the generators, in the canonical order in which `setup` derives
+  /// them from the label, so that the `i`-th generator commits to the `i`-th entry of a vector
+  pub fn generators(&self) -> impl Iterator<Item = &<E::GE as DlogGroup>::AffineGroupElement> {
+    self.ck.iter()
+  }
+
+  /// Returns the number of generators
+  pub fn len(&self) -> usize {
+    self.ck.len()
+  }
+
+  /// Returns true if the key has no generators
+  pub fn is_empty(&self) -> bool {
+    self.ck.is_empty()
+  }
+
+  /// Returns the fraction of the generators that are used to commit to a vector of length
+  /// `poly_len`, at most 1. The unused generators waste memory, which a key sized with
+  /// `required_len` avoids; see `warn_if_underutilized`.
+  pub fn utilization(&self, poly_len: usize) -> f64 {
+    if self.ck.is_empty() {
+      1.0
+    } else {
+      (poly_len as f64 / self.ck.len() as f64).min(1.0)
+    }
+  }
+
+  /// Logs a warning with `tracing` if less than half of the generators are used to commit to
+  /// vectors of length `poly_len`, and returns whether it did. This is meant to be called once
+  /// per key, e.g., when the key is set up for a circuit, rather than for every commitment.
+  pub fn warn_if_underutilized(&self, poly_len: usize) -> bool {
+    let utilization = self.utilization(poly_len);
+    let underutilized = utilization < Self::LOW_UTILIZATION;
+    if underutilized {
+      tracing::warn!(
+        num_generators = self.ck.len(),
+        poly_len,
+        utilization,
+        "the commitment key is much larger than needed"
+      );
+    }
+    underutilized
+  }
+
+  /// Returns the number of generators needed to commit to a multilinear polynomial in
+  /// `num_vars` variables
+  pub fn required_len(num_vars: usize) -> usize {
+    1 << num_vars
+  }
+
+  // the utilization below which `warn_if_underutilized` warns
+  const LOW_UTILIZATION: f64 = 0.5;
+
+  /// Commits to the entries of a vector at positions `range`, given as `v`, using the
+  /// corresponding generators. The commitment to the full vector is assembled from the
+  /// partial commitments to disjoint ranges with `PartialCommitment::combine`.
+  pub fn commit_partial(
+    &self,
+    range: Range<usize>,
+    v: &[E::Scalar],
+  ) -> Result<PartialCommitment<E>, NovaError> {
+    if range.len() != v.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+    if range.end > self.ck.len() {
+      return Err(NovaError::InvalidCommitmentKeyLength);
+    }
+
+    let comm = Commitment {
+      comm: E::GE::vartime_multiscalar_mul(v, &self.ck[range.clone()]),
+    };
+    Ok(PartialCommitment { range, comm })
+  }
+
+  /// Commits to the vector whose only nonzero entries are `v` at position `i` for each `(i, v)`
+  /// in `entries`, with a multiexponentiation over the generators at these positions only.
+  /// Returns `NovaError::InvalidCommitmentKeyLength` if a position has no generator.
+  pub fn commit_sparse(&self, entries: &[(usize, E::Scalar)]) -> Result<Commitment<E>, NovaError> {
+    if entries.iter().any(|(i, _)| *i >= self.ck.len()) {
+      return Err(NovaError::InvalidCommitmentKeyLength);
+    }
+
+    let (scalars, bases): (Vec<_>, Vec<_>) = entries
+      .iter()
+      .map(|(i, v)| (*v, self.ck[*i].clone()))
+      .unzip();
+    Ok(Commitment {
+      comm: E::GE::vartime_multiscalar_mul(&scalars, &bases),
+    })
+  }
+
+  /// Commits to a vector like `CommitmentEngine::commit`, with a multiexponentiation whose
+  /// memory does not grow with the length of `v`. The scalars are processed in windows of
+  /// `window_bits` bits, from 1 to 16, with a single buffer of `2^window_bits - 1` buckets that
+  /// is reused across the windows, on the calling thread. The entries are taken in chunks of
+  /// at least `LOW_MEMORY_CHUNK` entries, and the bits of each scalar of a chunk are computed
+  /// once for all of the windows. A smaller window takes less memory but more group additions:
+  /// about `256 / window_bits` additions per entry for 256-bit scalars. Returns
+  /// `NovaError::InvalidInputLength` for a window out of range.
+  pub fn commit_low_memory(
+    &self,
+    v: &[E::Scalar],
+    window_bits: usize,
+  ) -> Result<Commitment<E>, NovaError> {
+    if !(1..=16).contains(&window_bits) {
+      return Err(NovaError::InvalidInputLength);
+    }
+    if v.len() > self.ck.len() {
+      return Err(NovaError::InvalidCommitmentKeyLength);
+    }
+
+    let num_bits = E::Scalar::NUM_BITS as usize;
+    let num_windows = num_bits.div_ceil(window_bits);
+    // the chunks are no shorter than the buckets, so that summing the buckets does not
+    // dominate the additions of the entries
+    let chunk_len = Self::LOW_MEMORY_CHUNK.max(1 << window_bits);
+    let mut buckets = vec![E::GE::zero(); (1 << window_bits) - 1];
+    let mut comm = E::GE::zero();
+    for (v_chunk, ck_chunk) in v.chunks(chunk_len).zip(self.ck.chunks(chunk_len)) {
+      let chunk_bits = v_chunk
+        .iter()
+        .map(|v_i| v_i.to_le_bits())
+        .collect::<Vec<_>>();
+      let mut acc = E::GE::zero();
+      for w in (0..num_windows).rev() {
+        for _ in 0..window_bits {
+          acc = acc + acc;
+        }
+
+        buckets.iter_mut().for_each(|b| *b = E::GE::zero());
+        for (bits, g_i) in chunk_bits.iter().zip(ck_chunk.iter()) {
+          let digit = (0..window_bits)
+            .map(|k| w * window_bits + k)
+            .take_while(|j| *j < num_bits)
+            .fold(0, |d, j| {
+              d | (usize::from(bits[j]) << (j - w * window_bits))
+            });
+          if digit != 0 {
+            buckets[digit - 1] += E::GE::group(g_i);
+          }
+        }
+
+        // sum_d d * buckets[d - 1], with running sums from the highest bucket down
+        let mut running = E::GE::zero();
+        for b in buckets.iter().rev() {
+          running += *b;
+          acc += running;
+        }
+      }
+      comm += acc;
+    }
+
+    Ok(Commitment { comm })
+  }
+
+  // the smallest number of entries of a chunk in `commit_low_memory`
+  const LOW_MEMORY_CHUNK: usize = 1 << 10;
+
+  /// Commits to a vector like `CommitmentEngine::commit`, in time that does not depend on the
+  /// entries of `v`. The multiexponentiation behind `commit` branches and indexes tables on the
+  /// bits of the scalars, which can leak a witness through timing or power measurements. Here,
+  /// each scalar is processed in fixed windows of `CT_WINDOW_BITS` bits, and the multiple of the
+  /// generator for each window is selected from a table by a constant-time scan over all of its
+  /// entries. This is several times slower than `commit`.
+  #[cfg(feature = "ct")]
+  pub fn commit_constant_time(&self, v: &[E::Scalar]) -> Commitment<E>
+  where
+    E::GE: subtle::ConditionallySelectable,
+  {
+    assert!(self.ck.len() >= v.len());
+
+    // each thread handles a chunk of the vector, sharing the doublings across its entries
+    let chunk_len = v
+      .len()
+      .div_ceil(crate::parallel::current_num_threads())
+      .max(1);
+    let comm = v
+      .par_chunks(chunk_len)
+      .zip(self.ck[..v.len()].par_chunks(chunk_len))
+      .map(|(v, ck)| Self::commit_constant_time_serial(v, ck))
+      .reduce(E::GE::zero, |a, b| a + b);
+    Commitment { comm }
+  }
+
+  #[cfg(feature = "ct")]
+  fn commit_constant_time_serial(
+    v: &[E::Scalar],
+    ck: &[<E::GE as DlogGroup>::AffineGroupElement],
+  ) -> E::GE
+  where
+    E::GE: subtle::ConditionallySelectable,
+  {
+    use subtle::{ConditionallySelectable, ConstantTimeEq};
+
+    // the table for each generator `G` holds `0, G, 2G, ..., (2^c - 1) G`
+    let tables = ck
+      .iter()
+      .map(|g| {
+        let g = E::GE::group(g);
+        iter::successors(Some(E::GE::zero()), |p| Some(*p + g))
+          .take(1 << Self::CT_WINDOW_BITS)
+          .collect::<Vec<_>>()
+      })
+      .collect::<Vec<_>>();
+    let bits = v.iter().map(|v_i| v_i.to_le_bits()).collect::<Vec<_>>();
+
+    let num_bits = bits.first().map_or(0, |b| b.len());
+    let num_windows = num_bits.div_ceil(Self::CT_WINDOW_BITS);
+    let mut acc = E::GE::zero();
+    for j in (0..num_windows).rev() {
+      for _ in 0..Self::CT_WINDOW_BITS {
+        acc = acc + acc;
+      }
+      for (table, bits) in tables.iter().zip(bits.iter()) {
+        let window = (0..Self::CT_WINDOW_BITS)
+          .filter(|k| j * Self::CT_WINDOW_BITS + k < num_bits)
+          .fold(0u8, |w, k| {
+            w | ((bits[j * Self::CT_WINDOW_BITS + k] as u8) << k)
+          });
+        let mut p = table[0];
+        for (k, t) in table.iter().enumerate().skip(1) {
+          p.conditional_assign(t, window.ct_eq(&(k as u8)));
+        }
+        acc += p;
+      }
+    }
+    acc
+  }
+
+  /// Commits to the evaluations of the `eq` polynomial at `point`, i.e., computes
+  /// `commit(ck, EqPolynomial::new(point).evals())`, without materializing the `2^m` entries.
+  /// We use the tensor structure of `eq`: for a split of the `m` coordinates in halves,
+  /// `eq(point)` is the tensor product of `eq(point_hi)` and `eq(point_lo)`. So each block of
+  /// `2^{|point_lo|}` generators is committed against the same vector `eq(point_lo)`, and the
+  /// block commitments are combined with `eq(point_hi)`, so that only `O(2^{m/2})` scalars are
+  /// held in memory.
+  pub fn commit_eq(&self, point: &[E::Scalar]) -> Result<Commitment<E>, NovaError> {
+    let m = point.len();
+    if m >= usize::BITS as usize || self.ck.len() < 1 << m {
+      return Err(NovaError::InvalidCommitmentKeyLength);
+    }
+
+    let (point_hi, point_lo) = point.split_at(m / 2);
+    let evals_hi = EqPolynomial::evals_from_points(point_hi);
+    let evals_lo = EqPolynomial::evals_from_points(point_lo);
+
+    let blocks = self.ck[..1 << m]
+      .par_chunks(evals_lo.len())
+      .map(|ck_block| E::GE::vartime_multiscalar_mul(&evals_lo, ck_block).affine())
+      .collect::<Vec<_>>();
+
+    Ok(Commitment {
+      comm: E::GE::vartime_multiscalar_mul(&evals_hi, &blocks),
+    })
+  }
+
+  /// Commits to each vector in `polys`, like committing to them one after another. The vectors
+  /// are split into chunks of similar size, which are committed in parallel and summed per
+  /// vector, so that the work stays balanced across cores when a batch mixes small and large
+  /// vectors.
+  pub fn commit_batch(&self, polys: &[&[E::Scalar]]) -> Result<Vec<Commitment<E>>, NovaError> {
+    if polys.iter().any(|p| p.len() > self.ck.len()) {
+      return Err(NovaError::InvalidCommitmentKeyLength);
+    }
+
+    // we aim for a few chunks per thread, so that work stealing can even out the load, but
+    // keep the chunks large enough for the multi-scalar multiplications to be efficient
+    let total_len = polys.iter().map(|p| p.len()).sum::<usize>();
+    let chunk_size = total_len
+      .div_ceil(4 * crate::parallel::current_num_threads())
+      .max(Self::MIN_BATCH_CHUNK);
+
+    let chunks = polys
+      .iter()
+      .enumerate()
+      .flat_map(|(i, p)| {
+        (0..p.len())
+          .step_by(chunk_size)
+          .map(move |start| (i, start))
+      })
+      .collect::<Vec<_>>();
+    let partial = chunks
+      .par_iter()
+      .map(|&(i, start)| {
+        let end = (start + chunk_size).min(polys[i].len());
+        E::GE::vartime_multiscalar_mul(&polys[i][start..end], &self.ck[start..end])
+      })
+      .collect::<Vec<_>>();
+
+    let mut comms = vec![E::GE::zero(); polys.len()];
+    for (&(i, _), comm) in chunks.iter().zip(partial) {
+      comms[i] += comm;
+    }
+    Ok(comms.into_iter().map(|comm| Commitment { comm }).collect())
+  }
+
+  // the smallest number of entries of a chunk committed by `commit_batch`
+  const MIN_BATCH_CHUNK: usize = 1 << 10;
+
+  /// Commits to the vector of length `len` whose `i`-th entry is `f(i)`, like committing to
+  /// the materialized vector, without holding the vector in memory. The positions are split
+  /// into chunks of `FN_CHUNK` entries, and each chunk is evaluated and committed in parallel
+  /// against its generators, so that at most one chunk per thread is held at a time. Returns
+  /// `NovaError::InvalidCommitmentKeyLength` if `len` exceeds the number of generators.
+  pub fn commit_fn<F>(&self, len: usize, f: F) -> Result<Commitment<E>, NovaError>
+  where
+    F: Fn(usize) -> E::Scalar + Sync,
+  {
+    if len > self.ck.len() {
+      return Err(NovaError::InvalidCommitmentKeyLength);
+    }
+
+    let comm = (0..len.div_ceil(Self::FN_CHUNK))
+      .into_par_iter()
+      .map(|k| {
+        let range = k * Self::FN_CHUNK..((k + 1) * Self::FN_CHUNK).min(len);
+        let v = range.clone().map(&f).collect::<Vec<E::Scalar>>();
+        E::GE::vartime_multiscalar_mul(&v, &self.ck[range])
+      })
+      .reduce(E::GE::zero, |a, b| a + b);
+    Ok(Commitment { comm })
+  }
+
+  // the number of entries of a chunk evaluated and committed at once by `commit_fn`
+  const FN_CHUNK: usize = 1 << 16;
+
+  /// Commits to each column of a `rows x cols` matrix, given in row-major order as `data`,
+  /// using the same first `rows` generators for every column. The columns are committed in
+  /// parallel against the shared preprocessed generators.
+  pub fn commit_matrix(
+    &self,
+    rows: usize,
+    cols: usize,
+    data: &[E::Scalar],
+  ) -> Result<Vec<Commitment<E>>, NovaError> {
+    if rows.checked_mul(cols) != Some(data.len()) {
+      return Err(NovaError::InvalidInputLength);
+    }
+    if rows > self.ck.len() {
+      return Err(NovaError::InvalidCommitmentKeyLength);
+    }
+
+    let ck = &self.ck[..rows];
+    Ok(
+      (0..cols)
+        .into_par_iter()
+        .map(|j| {
+          let column = (0..rows)
+            .map(|i| data[i * cols + j])
+            .collect::<Vec<E::Scalar>>();
+          Commitment {
+            comm: E::GE::vartime_multiscalar_mul(&column, ck),
+          }
+        })
+        .collect(),
+    )
+  }
+}
+
+/// A commitment to the entries of a vector in a range of positions, produced by
+/// `CommitmentKey::commit_partial`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct PartialCommitment<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  range: Range<usize>,
+  comm: Commitment<E>,
+}
+
+impl<E> PartialCommitment<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  /// Returns the range of positions covered by the partial commitment
+  pub fn range(&self) -> &Range<usize> {
+    &self.range
+  }
+
+  /// Combines partial commitments to disjoint ranges into a commitment to the whole vector,
+  /// where positions not covered by any range are treated as zero. Returns an error if two of
+  /// the ranges overlap, since the entries in the overlap would be counted twice.
+  pub fn combine(parts: &[Self]) -> Result<Commitment<E>, NovaError> {
+    let mut ranges = parts.iter().map(|p| p.range.clone()).collect::<Vec<_>>();
+    ranges.sort_by_key(|r| r.start);
+    if ranges.windows(2).any(|w| w[0].end > w[1].start) {
+      return Err(NovaError::InvalidIndex);
+    }
+
+    Ok(
+      parts
+        .iter()
+        .fold(Commitment::default(), |acc, p| acc + p.comm),
+    )
+  }
+}
+
+/// A borrowed view of commitment generators, which supports the read operations of a
+/// commitment key without owning (or copying) the generators. It can wrap the generators of an
+/// existing `CommitmentKey`, or any slice of preprocessed group elements, such as one backed by
+/// a memory-mapped file.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CommitmentKeyRef<'a, E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  ck: &'a [<E::GE as DlogGroup>::AffineGroupElement],
+}
+
+impl<E> Clone for CommitmentKeyRef<'_, E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<E> Copy for CommitmentKeyRef<'_, E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+}
+
+impl<E> Len for CommitmentKeyRef<'_, E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  fn length(&self) -> usize {
+    self.ck.len()
+  }
+}
+
+impl<'a, E> CommitmentKeyRef<'a, E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  /// Creates a view over the provided generators
+  pub fn new(ck: &'a [<E::GE as DlogGroup>::AffineGroupElement]) -> Self {
+    CommitmentKeyRef { ck }
+  }
+
+  /// Commits to the provided vector using the generators in the view
+  pub fn commit(&self, v: &[E::Scalar]) -> Commitment<E> {
+    assert!(self.ck.len() >= v.len());
+    Commitment {
+      comm: E::GE::vartime_multiscalar_mul(v, &self.ck[..v.len()]),
+    }
+  }
+
+  /// Splits the view into two views at a specified point, without copying any generators
+  pub fn split_at(&self, n: usize) -> (Self, Self) {
+    let (L, R) = self.ck.split_at(n);
+    (CommitmentKeyRef { ck: L }, CommitmentKeyRef { ck: R })
+  }
+
+  /// Copies the generators in the view into an owned commitment key
+  pub fn to_owned(&self) -> CommitmentKey<E> {
+    CommitmentKey::new(self.ck.to_vec())
+  }
+}
+
+/// A commitment key with a precomputed table of multiples of its generators, for a long-lived
+/// prover that commits against the same generators many times.
+///
+/// The table holds `2^{c j} G_i` for each generator `G_i` and each window `j` of `c` bits of a
+/// scalar, so that a commitment is computed as a single pass of the bucket method over all
+/// windows, without combining the windows with doublings. The table takes `ceil(b / c)` times
+/// the memory of the key for `b`-bit scalars, and is built once by `CommitmentKey::prepare`.
+/// The key is cheap to clone and is `Sync`, so one prepared key can be shared across threads.
+#[derive(Clone, Debug)]
+pub struct PreparedCommitmentKey<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  ck: Arc<CommitmentKey<E>>,
+  // the multiple of generator `i` for window `j` is at position `i * num_windows + j`
+  table: Arc<Vec<<E::GE as DlogGroup>::AffineGroupElement>>,
+  window_bits: usize,
+  num_windows: usize,
+}
+
+impl<E> CommitmentKey<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  /// Precomputes the table of multiples of the generators in `self`
+  pub fn prepare(&self) -> PreparedCommitmentKey<E> {
+    // larger windows make the final summation of the buckets more expensive, so we grow
+    // them with the number of generators
+    let window_bits = (self.ck.len().max(2).ilog2() as usize)
+      .saturating_sub(2)
+      .clamp(4, 16);
+    let num_windows = (E::Scalar::NUM_BITS as usize).div_ceil(window_bits);
+
+    let table = self
+      .ck
+      .par_iter()
+      .flat_map_iter(|g| {
+        iter::successors(Some(E::GE::group(g)), move |p| {
+          Some((0..window_bits).fold(*p, |p, _| p + p))
+        })
+        .take(num_windows)
+        .map(|p| p.affine())
+        .collect::<Vec<_>>()
+      })
+      .collect();
+
+    PreparedCommitmentKey {
+      ck: Arc::new(self.clone()),
+      table: Arc::new(table),
+      window_bits,
+      num_windows,
+    }
+  }
+}
+
+impl<E> Len for PreparedCommitmentKey<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  fn length(&self) -> usize {
+    self.ck.length()
+  }
+}
+
+impl<E> PreparedCommitmentKey<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  /// Returns the commitment key from which the table was computed
+  pub fn ck(&self) -> &CommitmentKey<E> {
+    &self.ck
+  }
+
+  /// Commits to `v` like `CommitmentEngine::commit` with the underlying key, using the table
+  pub fn commit(&self, v: &[E::Scalar]) -> Commitment<E> {
+    assert!(self.ck.ck.len() >= v.len());
+    self.commit_at(0, v)
+  }
+
+  /// Commits to the entries of a vector at positions `range`, like
+  /// `CommitmentKey::commit_partial`, using the table
+  pub fn commit_partial(
+    &self,
+    range: Range<usize>,
+    v: &[E::Scalar],
+  ) -> Result<PartialCommitment<E>, NovaError> {
+    if range.len() != v.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+    if range.end > self.ck.ck.len() {
+      return Err(NovaError::InvalidCommitmentKeyLength);
+    }
+
+    let comm = self.commit_at(range.start, v);
+    Ok(PartialCommitment { range, comm })
+  }
+
+  // commits to `v` with the generators starting at position `offset`
+  pub(crate) fn commit_at(&self, offset: usize, v: &[E::Scalar]) -> Commitment<E> {
+    let (c, w) = (self.window_bits, self.num_windows);
+    let chunk_size = v
+      .len()
+      .div_ceil(crate::parallel::current_num_threads())
+      .max(1);
+
+    let comm = v
+      .par_chunks(chunk_size)
+      .enumerate()
+      .map(|(k, chunk)| {
+        // the digits of all the windows share the same buckets, since the table already
+        // accounts for the position of each window
+        let mut buckets = vec![E::GE::zero(); (1 << c) - 1];
+        for (t, scalar) in chunk.iter().enumerate() {
+          let bits = scalar.to_le_bits();
+          let bases = &self.table[(offset + k * chunk_size + t) * w..][..w];
+          for (j, base) in bases.iter().enumerate() {
+            let digit = (j * c..((j + 1) * c).min(E::Scalar::NUM_BITS as usize))
+              .rev()
+              .fold(0, |acc, b| (acc << 1) | usize::from(bits[b]));
+            if digit != 0 {
+              buckets[digit - 1] += E::GE::group(base);
+            }
+          }
+        }
+
+        // computes the sum of `(d + 1) * buckets[d]` with running sums
+        let mut running_sum = E::GE::zero();
+        let mut acc = E::GE::zero();
+        for bucket in buckets.into_iter().rev() {
+          running_sum += bucket;
+          acc += running_sum;
+        }
+        acc
+      })
+      .reduce(E::GE::zero, |x, y| x + y);
+
+    Commitment { comm }
+  }
+}
+
 /// A type that holds a commitment
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(bound = "")]
@@ -43,6 +766,20 @@ pub struct Commitment<E: Engine> {
   pub(crate) comm: E::GE,
 }
 
+impl<E> Commitment<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  /// Normalizes a batch of commitments to preprocessed (affine) group elements with a single
+  /// shared field inversion. Commitments hold the non-normalized output of `commit`, and
+  /// adding or scaling them does not normalize, so a pipeline that combines many commitments
+  /// can normalize the results once, at the end, with this method.
+  pub fn normalize_batch(comms: &[Self]) -> Vec<<E::GE as DlogGroup>::AffineGroupElement> {
+    E::GE::batch_affine(&comms.iter().map(|c| c.comm).collect::<Vec<_>>())
+  }
+}
+
 impl<E> CommitmentTrait<E> for Commitment<E>
 where
   E: Engine,
@@ -51,6 +788,33 @@ where
   fn to_coordinates(&self) -> (E::Base, E::Base, bool) {
     self.comm.to_coordinates()
   }
+
+  fn compressed_len() -> usize {
+    <E::GE as GroupEncoding>::Repr::default().as_ref().len()
+  }
+
+  fn to_compressed_bytes(&self) -> Vec<u8> {
+    self.comm.to_bytes().as_ref().to_vec()
+  }
+
+  fn from_compressed_bytes(bytes: &[u8]) -> Result<Self, NovaError> {
+    let mut repr = <E::GE as GroupEncoding>::Repr::default();
+    if bytes.len() != repr.as_ref().len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+    repr.as_mut().copy_from_slice(bytes);
+    let comm = Option::from(E::GE::from_bytes(&repr)).ok_or(NovaError::PointNotOnCurve)?;
+    Ok(Commitment { comm })
+  }
+
+  fn is_identity(&self) -> Choice {
+    // the encoding of a point is canonical, so this compares the points
+    self
+      .comm
+      .to_bytes()
+      .as_ref()
+      .ct_eq(E::GE::zero().to_bytes().as_ref())
+  }
 }
 
 impl<E: Engine> Default for Commitment<E>
@@ -111,7 +875,7 @@ where
   }
 }
 
-impl<'a, 'b, E> Mul<&'b E::Scalar> for &'a Commitment<E>
+impl<'b, E> Mul<&'b E::Scalar> for &Commitment<E>
 where
   E: Engine,
   E::GE: DlogGroup,
@@ -152,6 +916,34 @@ where
   }
 }
 
+impl<E> Sub for Commitment<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  type Output = Commitment<E>;
+
+  fn sub(self, other: Commitment<E>) -> Commitment<E> {
+    Commitment {
+      comm: self.comm - other.comm,
+    }
+  }
+}
+
+impl<E> Neg for Commitment<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  type Output = Commitment<E>;
+
+  fn neg(self) -> Commitment<E> {
+    Commitment {
+      comm: E::GE::zero() - self.comm,
+    }
+  }
+}
+
 /// Provides a commitment engine
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CommitmentEngine<E: Engine> {
@@ -167,9 +959,7 @@ where
   type Commitment = Commitment<E>;
 
   fn setup(label: &'static [u8], n: usize) -> Self::CommitmentKey {
-    Self::CommitmentKey {
-      ck: E::GE::from_label(label, n.next_power_of_two()),
-    }
+    CommitmentKey::new(E::GE::from_label(label, n.next_power_of_two()))
   }
 
   fn commit(ck: &Self::CommitmentKey, v: &[E::Scalar]) -> Self::Commitment {
@@ -197,6 +987,14 @@ where
   /// Folds the two commitment keys into one using the provided weights
   fn fold(&self, w1: &E::Scalar, w2: &E::Scalar) -> Self;
 
+  /// Folds the commitment key like `fold`, replacing `self` with the result
+  fn fold_in_place(&mut self, w1: &E::Scalar, w2: &E::Scalar)
+  where
+    Self: Sized,
+  {
+    *self = self.fold(w1, w2);
+  }
+
   /// Scales the commitment key using the provided scalar
   fn scale(&self, r: &E::Scalar) -> Self;
 
@@ -215,12 +1013,8 @@ where
 {
   fn split_at(&self, n: usize) -> (CommitmentKey<E>, CommitmentKey<E>) {
     (
-      CommitmentKey {
-        ck: self.ck[0..n].to_vec(),
-      },
-      CommitmentKey {
-        ck: self.ck[n..].to_vec(),
-      },
+      CommitmentKey::new(self.ck[0..n].to_vec()),
+      CommitmentKey::new(self.ck[n..].to_vec()),
     )
   }
 
@@ -230,7 +1024,7 @@ where
       c.extend(other.ck.clone());
       c
     };
-    CommitmentKey { ck }
+    CommitmentKey::new(ck)
   }
 
   // combines the left and right halves of `self` using `w1` and `w2` as the weights
@@ -246,7 +1040,22 @@ where
       })
       .collect();
 
-    CommitmentKey { ck }
+    CommitmentKey::new(ck)
+  }
+
+  // like `fold`, but writes the folded generators over the left half of `self` and truncates
+  // it, so that no new key is allocated
+  fn fold_in_place(&mut self, w1: &E::Scalar, w2: &E::Scalar) {
+    let w = [*w1, *w2];
+    let n = self.ck.len() / 2;
+    let (L, R) = self.ck.split_at_mut(n);
+
+    L.par_iter_mut().zip(R.par_iter()).for_each(|(L, R)| {
+      let bases = [L.clone(), R.clone()];
+      *L = E::GE::vartime_multiscalar_mul(&w, &bases).affine();
+    });
+    self.ck.truncate(n);
+    self.digest = OnceCell::new();
   }
 
   /// Scales each element in `self` by `r`
@@ -258,15 +1067,613 @@ where
       .map(|g| E::GE::vartime_multiscalar_mul(&[*r], &[g]).affine())
       .collect();
 
-    CommitmentKey { ck: ck_scaled }
+    CommitmentKey::new(ck_scaled)
   }
 
   /// reinterprets a vector of commitments as a set of generators
   fn reinterpret_commitments_as_ck(c: &[Commitment<E>]) -> Result<Self, NovaError> {
-    let ck = (0..c.len())
-      .into_par_iter()
-      .map(|i| c[i].comm.affine())
-      .collect();
-    Ok(CommitmentKey { ck })
+    Ok(CommitmentKey::new(Commitment::normalize_batch(c)))
+  }
+}
+
+/// A trait for commitment keys that commit to many vectors at once, each against the first
+/// generators of the key, which for many short vectors is cheaper than committing to the
+/// vectors one after another
+pub trait MultiCommit<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  /// Commits to each vector in `vs`, returning the same commitments as `commit` on each vector
+  fn multi_commit(&self, vs: &[Vec<E::Scalar>]) -> Result<Vec<Commitment<E>>, NovaError>;
+}
+
+impl<E> MultiCommit<E> for CommitmentKey<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  // Since all the vectors are committed against the same first generators, we compute once a
+  // table of the multiples `d * 2^{c j} * G_i` of each generator `G_i` for each window `j` of
+  // `c` bits and each digit `d`. The scalars of all the vectors are decomposed into windows in
+  // a single pass, and each window of a scalar then costs one addition of a table entry, with
+  // no doublings. Building the table costs `2^c - 1` additions per generator and window, so
+  // the window grows with the number of vectors that share the table. With few vectors, the
+  // table does not pay off, and we commit to each vector with an MSM instead.
+  fn multi_commit(&self, vs: &[Vec<E::Scalar>]) -> Result<Vec<Commitment<E>>, NovaError> {
+    if vs.iter().any(|v| v.len() > self.ck.len()) {
+      return Err(NovaError::InvalidCommitmentKeyLength);
+    }
+
+    let num_bits = E::Scalar::NUM_BITS as usize;
+    let max_len = vs.iter().map(|v| v.len()).max().unwrap_or(0);
+    let total_len = vs.iter().map(|v| v.len()).sum::<usize>();
+    let table_len = |c: usize| max_len * num_bits.div_ceil(c) * ((1 << c) - 1);
+    let c = (2..=Self::MAX_MULTI_COMMIT_WINDOW).rev().find(|c| {
+      max_len * ((1 << c) - 1) <= total_len && table_len(*c) <= Self::MAX_MULTI_COMMIT_TABLE
+    });
+    let Some(c) = c else {
+      return Ok(
+        vs.par_iter()
+          .map(|v| Commitment {
+            comm: E::GE::vartime_multiscalar_mul(v, &self.ck[..v.len()]),
+          })
+          .collect(),
+      );
+    };
+    let num_windows = num_bits.div_ceil(c);
+    let num_digits = (1 << c) - 1;
+
+    // the multiple `d * 2^{c j} * G_i` is at position `(i * num_windows + j) * num_digits + d - 1`
+    let table = self.ck[..max_len]
+      .par_iter()
+      .flat_map_iter(|g| {
+        let window_bases = iter::successors(Some(E::GE::group(g)), move |p| {
+          Some((0..c).fold(*p, |p, _| p + p))
+        })
+        .take(num_windows);
+        window_bases
+          .flat_map(move |base| {
+            iter::successors(Some(base), move |p| Some(*p + base)).take(num_digits)
+          })
+          .collect::<Vec<_>>()
+      })
+      .collect::<Vec<E::GE>>();
+
+    Ok(
+      vs.par_iter()
+        .map(|v| {
+          let comm = v
+            .iter()
+            .enumerate()
+            .fold(E::GE::zero(), |mut acc, (i, scalar)| {
+              let bits = scalar.to_le_bits();
+              for j in 0..num_windows {
+                let digit = (j * c..((j + 1) * c).min(num_bits))
+                  .rev()
+                  .fold(0, |acc, b| (acc << 1) | usize::from(bits[b]));
+                if digit != 0 {
+                  acc += table[(i * num_windows + j) * num_digits + digit - 1];
+                }
+              }
+              acc
+            });
+          Commitment { comm }
+        })
+        .collect(),
+    )
+  }
+}
+
+impl<E> CommitmentKey<E>
+where
+  E: Engine,
+  E::GE: DlogGroup,
+{
+  // the largest window of `multi_commit`, in bits
+  const MAX_MULTI_COMMIT_WINDOW: usize = 8;
+
+  // the largest number of entries of the table of `multi_commit`
+  const MAX_MULTI_COMMIT_TABLE: usize = 1 << 21;
+
+  // the number of bits of a scalar processed per window by `commit_constant_time`
+  #[cfg(feature = "ct")]
+  const CT_WINDOW_BITS: usize = 4;
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::provider::PallasEngine;
+  use rand::SeedableRng;
+
+  type E = PallasEngine;
+  type Fr = <E as Engine>::Scalar;
+
+  #[test]
+  fn test_compressed_bytes() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(13);
+    let ck = CommitmentEngine::<E>::setup(b"test", 4);
+    let v = (0..4).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>();
+
+    for comm in [
+      CommitmentEngine::<E>::commit(&ck, &v),
+      Commitment::default(),
+    ] {
+      let bytes = comm.to_compressed_bytes();
+      assert_eq!(bytes.len(), Commitment::<E>::compressed_len());
+      assert_eq!(Commitment::<E>::from_compressed_bytes(&bytes), Ok(comm));
+      assert_eq!(
+        Commitment::<E>::from_compressed_bytes(&bytes[1..]),
+        Err(NovaError::InvalidInputLength)
+      );
+    }
+    assert_eq!(
+      Commitment::<E>::from_compressed_bytes(&[0xff; 32]),
+      Err(NovaError::PointNotOnCurve)
+    );
+  }
+
+  #[test]
+  fn test_is_identity() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(14);
+    let ck = CommitmentEngine::<E>::setup(b"test", 4);
+    let v = (0..4).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>();
+    let comm = CommitmentEngine::<E>::commit(&ck, &v);
+
+    assert!(bool::from(Commitment::<E>::default().is_identity()));
+    assert!(bool::from((comm - comm).is_identity()));
+    assert!(!bool::from(comm.is_identity()));
+  }
+
+  #[test]
+  fn test_generators() {
+    let ck = CommitmentEngine::<E>::setup(b"test", 8);
+    let gens = ck.generators().collect::<Vec<_>>();
+    assert_eq!(gens.len(), ck.len());
+    assert!(!ck.is_empty());
+
+    for (i, g) in gens.iter().enumerate() {
+      assert!(gens[..i].iter().all(|h| h != g));
+
+      let mut e_i = vec![Fr::ZERO; ck.len()];
+      e_i[i] = Fr::ONE;
+      assert_eq!(
+        CommitmentEngine::commit(&ck, &e_i).comm,
+        <E as Engine>::GE::group(g)
+      );
+    }
+  }
+
+  #[test]
+  fn test_multi_commit() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+    let ck = CommitmentEngine::<E>::setup(b"test", 64);
+
+    for lens in [vec![], vec![0, 0], vec![16; 100], vec![1, 64, 0, 7, 33]] {
+      let vs = lens
+        .iter()
+        .map(|n| (0..*n).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>())
+        .collect::<Vec<_>>();
+      let comms = ck.multi_commit(&vs).unwrap();
+      assert_eq!(comms.len(), vs.len());
+      for (v, comm) in vs.iter().zip(comms.iter()) {
+        assert_eq!(*comm, CommitmentEngine::commit(&ck, v));
+      }
+    }
+
+    assert_eq!(
+      ck.multi_commit(&[vec![Fr::ONE; 65]]),
+      Err(NovaError::InvalidCommitmentKeyLength)
+    );
+  }
+
+  #[test]
+  fn test_disjoint_keys() {
+    let ck = CommitmentEngine::<E>::setup(b"test", 16);
+    let ck_same = CommitmentEngine::<E>::setup(b"test", 4);
+    let ck_other = CommitmentEngine::<E>::setup(b"other", 16);
+
+    assert!(!ck.disjoint_from(&ck_same));
+    assert!(!ck_same.disjoint_from(&ck));
+    assert!(ck.disjoint_from(&ck_other));
+
+    assert_eq!(
+      CommitmentKey::setup_disjoint(b"test", 8, &[&ck_other, &ck]).err(),
+      Some(NovaError::OverlappingCommitmentKeys)
+    );
+    let ck_new = CommitmentKey::setup_disjoint(b"new", 8, &[&ck_other, &ck]).unwrap();
+    assert_eq!(ck_new, CommitmentEngine::<E>::setup(b"new", 8));
+  }
+
+  #[cfg(feature = "ct")]
+  #[test]
+  fn test_commit_constant_time() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(8);
+    let ck = CommitmentEngine::<E>::setup(b"test", 16);
+    for n in [0, 1, 5, 16] {
+      let v = (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+      assert_eq!(
+        ck.commit_constant_time(&v),
+        CommitmentEngine::<E>::commit(&ck, &v)
+      );
+    }
+
+    // scalars with extreme bit patterns
+    let v = vec![Fr::ZERO, -Fr::ONE];
+    assert_eq!(
+      ck.commit_constant_time(&v),
+      CommitmentEngine::<E>::commit(&ck, &v)
+    );
+  }
+
+  #[test]
+  fn test_commit_low_memory() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(9);
+    let ck = CommitmentEngine::<E>::setup(b"test", 64);
+    for n in [0, 1, 7, 64] {
+      let v = (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+      let expected = CommitmentEngine::<E>::commit(&ck, &v);
+      for window_bits in [1, 3, 4, 8, 16] {
+        assert_eq!(ck.commit_low_memory(&v, window_bits), Ok(expected));
+      }
+    }
+
+    // several chunks, the last of which is partial
+    let long_ck = CommitmentEngine::<E>::setup(b"test", 2500);
+    let v = (0..2500).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+    let expected = CommitmentEngine::<E>::commit(&long_ck, &v);
+    for window_bits in [4, 11] {
+      assert_eq!(long_ck.commit_low_memory(&v, window_bits), Ok(expected));
+    }
+
+    let v = vec![Fr::ZERO, -Fr::ONE, Fr::ONE];
+    assert_eq!(
+      ck.commit_low_memory(&v, 5),
+      Ok(CommitmentEngine::<E>::commit(&ck, &v))
+    );
+
+    assert_eq!(
+      ck.commit_low_memory(&v, 0),
+      Err(NovaError::InvalidInputLength)
+    );
+    assert_eq!(
+      ck.commit_low_memory(&vec![Fr::ONE; 65], 4),
+      Err(NovaError::InvalidCommitmentKeyLength)
+    );
+  }
+
+  #[test]
+  fn test_utilization() {
+    let num_vars = 4;
+    let n = CommitmentKey::<E>::required_len(num_vars);
+    assert_eq!(n, 16);
+
+    let ck = CommitmentEngine::<E>::setup(b"test", 4 * n);
+    assert_eq!(ck.utilization(n), 0.25);
+    assert_eq!(ck.utilization(4 * n), 1.0);
+    assert_eq!(ck.utilization(8 * n), 1.0);
+    assert!(ck.warn_if_underutilized(n));
+    assert!(!ck.warn_if_underutilized(2 * n));
+
+    let ck = CommitmentEngine::<E>::setup(b"test", n);
+    assert_eq!(ck.utilization(n), 1.0);
+  }
+
+  #[test]
+  fn test_commitment_key_hash() {
+    use std::collections::hash_map::DefaultHasher;
+
+    let hash = |ck: &CommitmentKey<E>| {
+      let mut hasher = DefaultHasher::new();
+      ck.hash(&mut hasher);
+      hasher.finish()
+    };
+
+    let ck = CommitmentEngine::<E>::setup(b"test", 16);
+    let ck_same = CommitmentEngine::<E>::setup(b"test", 16);
+    let ck_other = CommitmentEngine::<E>::setup(b"other", 16);
+
+    assert_eq!(ck, ck_same);
+    assert_eq!(hash(&ck), hash(&ck_same));
+    assert_ne!(ck, ck_other);
+    assert_ne!(hash(&ck), hash(&ck_other));
+
+    // a prefix of a key is a different key
+    assert_ne!(ck, ck.split_at(8).0);
+
+    // the key can be used to index a map; the cached digest never changes once it is set, so
+    // its interior mutability does not affect the hash
+    #[allow(clippy::mutable_key_type)]
+    let mut cache = std::collections::HashMap::new();
+    cache.insert(ck, 1);
+    assert_eq!(cache.get(&ck_same), Some(&1));
+    assert_eq!(cache.get(&ck_other), None);
+  }
+
+  #[test]
+  fn test_prepared_commitment_key() {
+    let n = 100;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+    let ck = CommitmentEngine::<E>::setup(b"test", n);
+    let prepared = ck.prepare();
+    assert_eq!(prepared.length(), ck.length());
+    assert_eq!(prepared.ck(), &ck);
+
+    let vectors = (0..8)
+      .map(|i| {
+        let mut v = (0..n - i)
+          .map(|_| Fr::random(&mut rng))
+          .collect::<Vec<Fr>>();
+        // edge cases of the digits: zero, one, and the largest scalar
+        v[0] = Fr::ZERO;
+        v[1] = Fr::ONE;
+        v[2] = -Fr::ONE;
+        v
+      })
+      .collect::<Vec<_>>();
+
+    // the prepared key is shared by threads that commit concurrently
+    std::thread::scope(|scope| {
+      for v in &vectors {
+        let (prepared, ck) = (&prepared, &ck);
+        scope.spawn(move || {
+          assert_eq!(prepared.commit(v), CommitmentEngine::commit(ck, v));
+        });
+      }
+    });
+
+    let range = 10..40;
+    assert_eq!(
+      prepared
+        .commit_partial(range.clone(), &vectors[0][range.clone()])
+        .unwrap(),
+      ck.commit_partial(range.clone(), &vectors[0][range])
+        .unwrap()
+    );
+    assert_eq!(
+      prepared.commit_partial(0..ck.length() + 1, &vec![Fr::ONE; ck.length() + 1]),
+      Err(NovaError::InvalidCommitmentKeyLength)
+    );
+  }
+
+  #[test]
+  fn test_commit_eq() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(4);
+    let ck = CommitmentEngine::<E>::setup(b"test", 1 << 7);
+    for m in 0..=7 {
+      let point = (0..m).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>();
+      assert_eq!(
+        ck.commit_eq(&point).unwrap(),
+        CommitmentEngine::commit(&ck, &EqPolynomial::new(point).evals())
+      );
+    }
+
+    assert_eq!(
+      ck.commit_eq(&[Fr::ONE; 8]),
+      Err(NovaError::InvalidCommitmentKeyLength)
+    );
+  }
+
+  #[test]
+  fn test_commit_batch() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(5);
+    let ck = CommitmentEngine::<E>::setup(b"test", 1 << 13);
+
+    // a mix of large and small vectors, including an empty one
+    let polys = [1 << 13, 0, 1, 100, 5000, 1 << 12]
+      .iter()
+      .map(|&n| (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>())
+      .collect::<Vec<_>>();
+    let polys = polys.iter().map(|p| p.as_slice()).collect::<Vec<_>>();
+
+    let comms = ck.commit_batch(&polys).unwrap();
+    assert_eq!(comms.len(), polys.len());
+    for (comm, p) in comms.iter().zip(polys.iter()) {
+      assert_eq!(*comm, CommitmentEngine::commit(&ck, p));
+    }
+
+    assert!(ck.commit_batch(&[]).unwrap().is_empty());
+    let too_long = vec![Fr::ONE; (1 << 13) + 1];
+    assert_eq!(
+      ck.commit_batch(&[&too_long]),
+      Err(NovaError::InvalidCommitmentKeyLength)
+    );
+  }
+
+  #[test]
+  fn test_commit_fn() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(12);
+    let ck = CommitmentEngine::<E>::setup(b"test", 1 << 17);
+    let g = (0..100000)
+      .map(|_| Fr::random(&mut rng))
+      .collect::<Vec<Fr>>();
+
+    // lengths within one chunk, of exactly one chunk, and spanning a partial last chunk
+    for len in [0, 1, 1 << 16, 100000] {
+      let v = g[..len].iter().map(|g_i| g_i.square()).collect::<Vec<Fr>>();
+      assert_eq!(
+        ck.commit_fn(len, |i| g[i].square()).unwrap(),
+        CommitmentEngine::commit(&ck, &v)
+      );
+    }
+
+    assert_eq!(
+      ck.commit_fn((1 << 17) + 1, |_| Fr::ONE),
+      Err(NovaError::InvalidCommitmentKeyLength)
+    );
+  }
+
+  #[test]
+  fn test_commitment_key_ref() {
+    let n = 16;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    let ck = CommitmentEngine::<E>::setup(b"test", n);
+    let v = (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>();
+
+    let view = ck.as_view();
+    assert_eq!(view.length(), ck.length());
+    assert_eq!(view.commit(&v), CommitmentEngine::commit(&ck, &v));
+    assert_eq!(view.to_owned(), ck);
+
+    // splitting the view matches splitting the owned key
+    let (L, R) = view.split_at(n / 4);
+    let (ck_L, ck_R) = ck.split_at(n / 4);
+    assert_eq!(
+      L.commit(&v[..n / 4]),
+      CommitmentEngine::commit(&ck_L, &v[..n / 4])
+    );
+    assert_eq!(
+      R.commit(&v[n / 4..]),
+      CommitmentEngine::commit(&ck_R, &v[n / 4..])
+    );
+    assert_eq!(CommitmentKeyRef::<E>::new(&ck.ck[n / 4..]), R);
+  }
+
+  #[test]
+  fn test_commit_partial() {
+    let n = 16;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+    let ck = CommitmentEngine::<E>::setup(b"test", n);
+    let v = (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>();
+
+    // the partial commitments can be computed independently and combined in any order
+    let parts = [9..n, 0..4, 4..9]
+      .into_iter()
+      .map(|range| ck.commit_partial(range.clone(), &v[range]).unwrap())
+      .collect::<Vec<_>>();
+    assert_eq!(
+      PartialCommitment::combine(&parts).unwrap(),
+      CommitmentEngine::commit(&ck, &v)
+    );
+
+    // ranges that are not covered are treated as zeros
+    let mut v_prefix = v[..9].to_vec();
+    v_prefix.resize(n, Fr::ZERO);
+    assert_eq!(
+      PartialCommitment::combine(&parts[1..]).unwrap(),
+      CommitmentEngine::commit(&ck, &v_prefix)
+    );
+
+    // overlapping ranges are rejected
+    let overlapping = ck.commit_partial(3..5, &v[3..5]).unwrap();
+    assert_eq!(
+      PartialCommitment::combine(&[parts.clone(), vec![overlapping]].concat()),
+      Err(NovaError::InvalidIndex)
+    );
+
+    assert_eq!(
+      ck.commit_partial(0..3, &v[..4]),
+      Err(NovaError::InvalidInputLength)
+    );
+    assert_eq!(
+      ck.commit_partial(n..n + 1, &v[..1]),
+      Err(NovaError::InvalidCommitmentKeyLength)
+    );
+  }
+
+  #[test]
+  fn test_commitment_ops() {
+    let n = 8;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(2);
+    let ck = CommitmentEngine::<E>::setup(b"test", n);
+    let v = (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>();
+    let w = (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>();
+    let comm_v = CommitmentEngine::commit(&ck, &v);
+    let comm_w = CommitmentEngine::commit(&ck, &w);
+
+    assert_eq!(comm_v - comm_v, Commitment::default());
+    assert_eq!(-comm_v + comm_v, Commitment::default());
+    assert_eq!(
+      comm_v - comm_w,
+      CommitmentEngine::commit(
+        &ck,
+        &v.iter()
+          .zip(w.iter())
+          .map(|(a, b)| *a - b)
+          .collect::<Vec<Fr>>()
+      )
+    );
+
+    let r = Fr::random(&mut rng);
+    let scaled = v.iter().map(|v_i| r * v_i).collect::<Vec<Fr>>();
+    assert_eq!(comm_v * r, CommitmentEngine::commit(&ck, &scaled));
+  }
+
+  #[test]
+  fn test_normalize_batch() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(10);
+    let ck = CommitmentEngine::<E>::setup(b"test", 16);
+    let comms = (0..5)
+      .map(|_| {
+        let v = (0..16).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        CommitmentEngine::commit(&ck, &v)
+      })
+      .collect::<Vec<_>>();
+
+    // sums of the commitments as they come out of `commit`, normalized once at the end
+    let sums = (1..=comms.len())
+      .map(|k| {
+        comms[..k]
+          .iter()
+          .fold(Commitment::default(), |acc, c| acc + *c)
+      })
+      .collect::<Vec<Commitment<E>>>();
+    let normalized = Commitment::normalize_batch(&sums);
+
+    // sums of the commitments normalized one by one
+    let expected = (1..=comms.len())
+      .map(|k| {
+        comms[..k]
+          .iter()
+          .map(|c| <E as Engine>::GE::group(&c.comm.affine()))
+          .fold(<E as Engine>::GE::zero(), |acc, g| acc + g)
+          .affine()
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(normalized, expected);
+
+    assert!(Commitment::<E>::normalize_batch(&[]).is_empty());
+    assert_eq!(
+      Commitment::<E>::normalize_batch(&[Commitment::default()]),
+      vec![<E as Engine>::GE::zero().affine()]
+    );
+  }
+
+  #[test]
+  fn test_commit_matrix() {
+    let (rows, cols) = (4, 8);
+    let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+    let ck = CommitmentEngine::<E>::setup(b"test", rows * cols);
+    let data = (0..rows * cols)
+      .map(|_| Fr::random(&mut rng))
+      .collect::<Vec<Fr>>();
+
+    let comms = ck.commit_matrix(rows, cols, &data).unwrap();
+    assert_eq!(comms.len(), cols);
+    for (j, comm) in comms.iter().enumerate() {
+      let column = (0..rows).map(|i| data[i * cols + j]).collect::<Vec<Fr>>();
+      assert_eq!(*comm, CommitmentEngine::commit(&ck, &column));
+    }
+
+    // a linear combination of the columns commits to the same combination of the matrix
+    let w = (0..cols).map(|_| Fr::random(&mut rng)).collect::<Vec<Fr>>();
+    let combined = comms
+      .iter()
+      .zip(w.iter())
+      .fold(Commitment::default(), |acc, (c, w_j)| acc + *c * *w_j);
+    let product = (0..rows)
+      .map(|i| (0..cols).map(|j| data[i * cols + j] * w[j]).sum())
+      .collect::<Vec<Fr>>();
+    assert_eq!(combined, CommitmentEngine::commit(&ck, &product));
+
+    assert_eq!(
+      ck.commit_matrix(rows, cols, &data[1..]),
+      Err(NovaError::InvalidInputLength)
+    );
+    assert_eq!(
+      ck.commit_matrix(rows * cols + 1, 1, &[data.clone(), vec![Fr::ONE]].concat()),
+      Err(NovaError::InvalidCommitmentKeyLength)
+    );
   }
 }
@@ -1,5 +1,8 @@
 //! Poseidon Constants and Poseidon-based RO used in Nova
-use crate::traits::{ROCircuitTrait, ROTrait};
+use crate::{
+  errors::NovaError,
+  traits::{ROCircuitTrait, ROTrait},
+};
 use bellpepper_core::{
   boolean::{AllocatedBit, Boolean},
   num::AllocatedNum,
@@ -10,6 +13,7 @@ use ff::{PrimeField, PrimeFieldBits};
 use generic_array::typenum::U24;
 use neptune::{
   circuit2::Elt,
+  hash_type::HashType,
   poseidon::PoseidonConstants,
   sponge::{
     api::{IOPattern, SpongeAPI, SpongeOp},
@@ -31,6 +35,120 @@ impl<Scalar: PrimeField> Default for PoseidonConstantsCircuit<Scalar> {
   }
 }
 
+/// The round configuration of an externally-specified Poseidon instance
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoseidonConfig {
+  /// the number of full rounds (must be even and non-zero, as they are split around the partial rounds)
+  pub full_rounds: usize,
+  /// the number of partial rounds (must be non-zero)
+  pub partial_rounds: usize,
+}
+
+impl<Scalar: PrimeField> PoseidonConstantsCircuit<Scalar> {
+  /// The width of the Poseidon permutation used in Nova (arity + 1)
+  const WIDTH: usize = 25;
+
+  /// Builds Poseidon constants from externally-generated round constants and MDS matrix,
+  /// so that the RO can match a Poseidon instance used by another system.
+  /// `round_constants` must hold exactly `WIDTH * (full_rounds + partial_rounds)` entries, ordered by round,
+  /// and `mds` must be a symmetric `WIDTH x WIDTH` matrix, since neptune's circuit gadget multiplies
+  /// the state on the other side of the matrix than the native permutation does.
+  pub fn from_tables(
+    round_constants: Vec<Scalar>,
+    mds: Vec<Vec<Scalar>>,
+    config: PoseidonConfig,
+  ) -> Result<Self, NovaError> {
+    if mds.len() != Self::WIDTH
+      || mds.iter().any(|row| row.len() != Self::WIDTH)
+      || round_constants.len() != Self::WIDTH * (config.full_rounds + config.partial_rounds)
+    {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    if config.full_rounds == 0
+      || config.full_rounds % 2 != 0
+      || config.partial_rounds == 0
+      || (0..Self::WIDTH).any(|i| (0..i).any(|j| mds[i][j] != mds[j][i]))
+      || !is_factorizable_mds(&mds, config.partial_rounds)
+    {
+      return Err(NovaError::InvalidPoseidonConstants);
+    }
+
+    Ok(Self(PoseidonConstants::new_from_parameters(
+      Self::WIDTH,
+      mds,
+      round_constants,
+      config.full_rounds,
+      config.partial_rounds,
+      HashType::Sponge,
+      Strength::Standard,
+    )))
+  }
+}
+
+/// Returns the matrix obtained by removing the first row and the first column of `m`
+fn minor<F: PrimeField>(m: &[Vec<F>]) -> Vec<Vec<F>> {
+  m[1..].iter().map(|row| row[1..].to_vec()).collect()
+}
+
+/// Checks if a square matrix is invertible using Gaussian elimination
+fn is_invertible<F: PrimeField>(m: &[Vec<F>]) -> bool {
+  let mut m = m.to_vec();
+  let n = m.len();
+  for col in 0..n {
+    let pivot = match (col..n).find(|&row| !bool::from(m[row][col].is_zero())) {
+      Some(pivot) => pivot,
+      None => return false,
+    };
+    m.swap(col, pivot);
+
+    let inv = m[col][col].invert().unwrap();
+    let pivot_row = m[col].clone();
+    for row in m.iter_mut().skip(col + 1) {
+      let factor = row[col] * inv;
+      for (v, p) in row.iter_mut().zip(pivot_row.iter()).skip(col) {
+        *v -= factor * p;
+      }
+    }
+  }
+  true
+}
+
+/// Checks that neptune can precompute its optimized constants from `mds`.
+/// This inverts `mds`, its minor, and the minor of every matrix produced while factoring
+/// the transposed matrix into one sparse matrix per partial round, panicking if any is singular.
+fn is_factorizable_mds<F: PrimeField>(mds: &[Vec<F>], partial_rounds: usize) -> bool {
+  if !is_invertible(mds) || !is_invertible(&minor(mds)) {
+    return false;
+  }
+
+  let n = mds.len();
+  let base = (0..n)
+    .map(|i| (0..n).map(|j| mds[j][i]).collect::<Vec<F>>())
+    .collect::<Vec<_>>();
+  let mut curr = base.clone();
+  for _ in 0..partial_rounds {
+    let curr_hat = minor(&curr);
+    // `curr` itself is invertible since both `base` and the minor of the previous matrix are
+    if !is_invertible(&curr_hat) {
+      return false;
+    }
+
+    // curr <- base * diag(1, curr_hat)
+    curr = (0..n)
+      .map(|i| {
+        (0..n)
+          .map(|j| match j {
+            0 => base[i][0],
+            _ => (1..n).map(|k| base[i][k] * curr_hat[k - 1][j - 1]).sum(),
+          })
+          .collect()
+      })
+      .collect();
+  }
+  true
+}
+
 /// A Poseidon-based RO to use outside circuits
 #[derive(Serialize, Deserialize)]
 pub struct PoseidonRO<Base, Scalar>
@@ -201,7 +319,7 @@ mod tests {
   use ff::Field;
   use rand::rngs::OsRng;
 
-  fn test_poseidon_ro_with<E: Engine>()
+  fn test_poseidon_ro_with<E: Engine>(constants: PoseidonConstantsCircuit<E::Scalar>)
   where
     // we can print the field elements we get from E's Base & Scalar fields,
     // and compare their byte representations
@@ -212,7 +330,6 @@ mod tests {
   {
     // Check that the number computed inside the circuit is equal to the number computed outside the circuit
     let mut csprng: OsRng = OsRng;
-    let constants = PoseidonConstantsCircuit::<E::Scalar>::default();
     let num_absorbs = 32;
     let mut ro: PoseidonRO<E::Scalar, E::Base> = PoseidonRO::new(constants.clone(), num_absorbs);
     let mut ro_gadget: PoseidonROCircuit<E::Scalar> =
@@ -233,13 +350,144 @@ mod tests {
     assert_eq!(num.to_repr(), num2.get_value().unwrap().to_repr());
   }
 
+  fn test_poseidon_from_tables_with<E: Engine>() {
+    let default = PoseidonConstantsCircuit::<E::Base>::default();
+    let config = PoseidonConfig {
+      full_rounds: default.0.full_rounds,
+      partial_rounds: default.0.partial_rounds,
+    };
+    let round_constants = default.0.round_constants.clone().unwrap();
+    let mds = default.0.mds_matrices.m.clone();
+
+    // rebuilding the constants from the built-in tables yields the same hash
+    let supplied =
+      PoseidonConstantsCircuit::from_tables(round_constants.clone(), mds.clone(), config).unwrap();
+    let num_absorbs = 8;
+    let mut ro: PoseidonRO<E::Base, E::Scalar> = PoseidonRO::new(default, num_absorbs);
+    let mut ro_supplied: PoseidonRO<E::Base, E::Scalar> = PoseidonRO::new(supplied, num_absorbs);
+    for i in 0..num_absorbs {
+      ro.absorb(E::Base::from(i as u64));
+      ro_supplied.absorb(E::Base::from(i as u64));
+    }
+    assert_eq!(
+      ro.squeeze(NUM_CHALLENGE_BITS),
+      ro_supplied.squeeze(NUM_CHALLENGE_BITS)
+    );
+
+    // tables with the wrong dimensions are rejected
+    let short = round_constants[..round_constants.len() - 1].to_vec();
+    assert_eq!(
+      PoseidonConstantsCircuit::from_tables(short, mds.clone(), config).err(),
+      Some(NovaError::InvalidInputLength)
+    );
+    let long = [round_constants.clone(), vec![E::Base::ONE]].concat();
+    assert_eq!(
+      PoseidonConstantsCircuit::from_tables(long, mds.clone(), config).err(),
+      Some(NovaError::InvalidInputLength)
+    );
+    assert_eq!(
+      PoseidonConstantsCircuit::from_tables(round_constants.clone(), mds[1..].to_vec(), config)
+        .err(),
+      Some(NovaError::InvalidInputLength)
+    );
+
+    // degenerate round configurations are rejected
+    let width = PoseidonConstantsCircuit::<E::Base>::WIDTH;
+    for (full_rounds, partial_rounds) in [(0, 60), (8, 0), (7, 61)] {
+      let config = PoseidonConfig {
+        full_rounds,
+        partial_rounds,
+      };
+      let round_constants = vec![E::Base::ONE; width * (full_rounds + partial_rounds)];
+      assert_eq!(
+        PoseidonConstantsCircuit::from_tables(round_constants, mds.clone(), config).err(),
+        Some(NovaError::InvalidPoseidonConstants)
+      );
+    }
+
+    // asymmetric matrices are rejected
+    let mut asymmetric = mds.clone();
+    asymmetric[0][1] += E::Base::ONE;
+    assert_eq!(
+      PoseidonConstantsCircuit::from_tables(round_constants.clone(), asymmetric, config).err(),
+      Some(NovaError::InvalidPoseidonConstants)
+    );
+
+    // singular matrices are rejected instead of panicking in neptune
+    let singular = vec![vec![E::Base::ONE; width]; width];
+    assert_eq!(
+      PoseidonConstantsCircuit::from_tables(round_constants.clone(), singular, config).err(),
+      Some(NovaError::InvalidPoseidonConstants)
+    );
+    // an invertible matrix `diag([[0, 1], [1, 0]], I)` whose minor is singular
+    let mut singular_minor = vec![vec![E::Base::ZERO; width]; width];
+    singular_minor[0][1] = E::Base::ONE;
+    singular_minor[1][0] = E::Base::ONE;
+    for (i, row) in singular_minor.iter_mut().enumerate().skip(2) {
+      row[i] = E::Base::ONE;
+    }
+    assert_eq!(
+      PoseidonConstantsCircuit::from_tables(round_constants, singular_minor, config).err(),
+      Some(NovaError::InvalidPoseidonConstants)
+    );
+  }
+
+  #[test]
+  fn test_poseidon_from_tables() {
+    test_poseidon_from_tables_with::<PallasEngine>();
+    test_poseidon_from_tables_with::<Bn256EngineKZG>();
+    test_poseidon_from_tables_with::<Secp256k1Engine>();
+  }
+
+  // An external instance with a symmetric Cauchy MDS matrix `mds[i][j] = 1 / (2i + 2j + 2)`,
+  // round constants `1, 2, 3, ...`, 8 full and 3 partial rounds.
+  fn external_constants<F: PrimeField>() -> PoseidonConstantsCircuit<F> {
+    let width = PoseidonConstantsCircuit::<F>::WIDTH;
+    let config = PoseidonConfig {
+      full_rounds: 8,
+      partial_rounds: 3,
+    };
+    let round_constants = (0..width * (config.full_rounds + config.partial_rounds))
+      .map(|i| F::from(i as u64 + 1))
+      .collect::<Vec<F>>();
+    let mds = (0..width)
+      .map(|i| {
+        (0..width)
+          .map(|j| F::from((2 * i + 2 * j + 2) as u64).invert().unwrap())
+          .collect()
+      })
+      .collect::<Vec<Vec<F>>>();
+    PoseidonConstantsCircuit::from_tables(round_constants, mds, config).unwrap()
+  }
+
+  #[test]
+  fn test_poseidon_from_tables_known_answer() {
+    // The expected output was computed over the Pallas base field with an independent implementation
+    // of the Poseidon sponge.
+    type F = <PallasEngine as Engine>::Base;
+    let num_absorbs = 8;
+    let mut ro: PoseidonRO<F, <PallasEngine as Engine>::Scalar> =
+      PoseidonRO::new(external_constants(), num_absorbs);
+    for i in 0..num_absorbs {
+      ro.absorb(F::from(i as u64));
+    }
+    assert_eq!(
+      ro.squeeze(NUM_CHALLENGE_BITS),
+      <PallasEngine as Engine>::Scalar::from_u128(0x0ddac3de059d39a088e900be9749d008)
+    );
+  }
+
   #[test]
   fn test_poseidon_ro() {
-    test_poseidon_ro_with::<PallasEngine>();
-    test_poseidon_ro_with::<VestaEngine>();
-    test_poseidon_ro_with::<Bn256EngineKZG>();
-    test_poseidon_ro_with::<GrumpkinEngine>();
-    test_poseidon_ro_with::<Secp256k1Engine>();
-    test_poseidon_ro_with::<Secq256k1Engine>();
+    test_poseidon_ro_with::<PallasEngine>(PoseidonConstantsCircuit::default());
+    test_poseidon_ro_with::<VestaEngine>(PoseidonConstantsCircuit::default());
+    test_poseidon_ro_with::<Bn256EngineKZG>(PoseidonConstantsCircuit::default());
+    test_poseidon_ro_with::<GrumpkinEngine>(PoseidonConstantsCircuit::default());
+    test_poseidon_ro_with::<Secp256k1Engine>(PoseidonConstantsCircuit::default());
+    test_poseidon_ro_with::<Secq256k1Engine>(PoseidonConstantsCircuit::default());
+
+    // imported constants are used consistently inside and outside the circuit
+    test_poseidon_ro_with::<PallasEngine>(external_constants());
+    test_poseidon_ro_with::<Bn256EngineKZG>(external_constants());
   }
 }
@@ -0,0 +1,192 @@
+//! This module implements a bundle of IPA evaluation arguments that stores the round
+//! commitments they share once
+use crate::{
+  errors::NovaError,
+  provider::ipa_pc::{InnerProductArgument, UncheckedInnerProductArgument},
+  traits::Engine,
+  Commitment,
+};
+use alloc::collections::BTreeMap;
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+
+/// A bundle of inner product arguments that stores each distinct round commitment once, e.g.,
+/// for arguments that open the same polynomial at the same point on behalf of different
+/// parties. Each argument refers to its commitments by their indices in a shared pool, so a
+/// serialized bundle holds a commitment once however many arguments it appears in.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ProofBundle<E: Engine> {
+  comms: Vec<Commitment<E>>,
+  args: Vec<BundledArgument<E>>,
+}
+
+// an argument in a bundle, whose commitments are indices in the pool of the bundle
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+struct BundledArgument<E: Engine> {
+  L_indices: Vec<u32>,
+  R_indices: Vec<u32>,
+  a_hat: E::Scalar,
+  instance_digest: [u8; 32],
+}
+
+impl<E: Engine> ProofBundle<E> {
+  /// Bundles the arguments, storing each distinct commitment of their rounds once
+  pub fn new(args: &[InnerProductArgument<E>]) -> Self {
+    // commitments are identified by their encodings, as they need not be hashable
+    let mut indices = BTreeMap::new();
+    let mut comms = Vec::new();
+    let mut index_of = |comm: &Commitment<E>| {
+      *indices
+        .entry(bincode::serialize(comm).unwrap())
+        .or_insert_with(|| {
+          comms.push(*comm);
+          (comms.len() - 1) as u32
+        })
+    };
+
+    let args = args
+      .iter()
+      .map(|arg| BundledArgument {
+        L_indices: arg.L_vec.iter().map(&mut index_of).collect(),
+        R_indices: arg.R_vec.iter().map(&mut index_of).collect(),
+        a_hat: arg.a_hat,
+        instance_digest: arg.instance_digest,
+      })
+      .collect();
+
+    ProofBundle { comms, args }
+  }
+
+  /// Returns the number of arguments in the bundle
+  pub fn len(&self) -> usize {
+    self.args.len()
+  }
+
+  /// Returns true if the bundle has no arguments
+  pub fn is_empty(&self) -> bool {
+    self.args.is_empty()
+  }
+
+  /// Returns the arguments in the bundle, in the order in which they were bundled. Returns
+  /// `NovaError::MalformedProof` if an argument refers to a commitment outside of the pool, or
+  /// has different numbers of `L` and `R` commitments.
+  pub fn arguments(&self) -> Result<Vec<InnerProductArgument<E>>, NovaError> {
+    let lookup = |indices: &[u32]| {
+      indices
+        .iter()
+        .map(|i| {
+          self
+            .comms
+            .get(*i as usize)
+            .copied()
+            .ok_or(NovaError::MalformedProof)
+        })
+        .collect::<Result<Vec<_>, NovaError>>()
+    };
+
+    self
+      .args
+      .iter()
+      .map(|arg| {
+        InnerProductArgument::try_from(UncheckedInnerProductArgument {
+          L_vec: lookup(&arg.L_indices)?,
+          R_vec: lookup(&arg.R_indices)?,
+          a_hat: arg.a_hat,
+          instance_digest: arg.instance_digest,
+        })
+      })
+      .collect()
+  }
+
+  /// Serializes the bundle with `bincode`
+  pub fn to_bytes(&self) -> Vec<u8> {
+    bincode::serialize(self).unwrap()
+  }
+
+  /// Deserializes a bundle serialized with `to_bytes`. Returns `NovaError::MalformedProof` if
+  /// the bytes do not encode a bundle, or encode one whose arguments are malformed as in
+  /// `arguments`.
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, NovaError> {
+    let bundle: Self = bincode::DefaultOptions::new()
+      .with_fixint_encoding()
+      .reject_trailing_bytes()
+      .deserialize(bytes)
+      .map_err(|_| NovaError::MalformedProof)?;
+    bundle.arguments()?;
+    Ok(bundle)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    provider::{
+      ipa_pc::{tests::random_instance, EvaluationEngine},
+      keccak::Keccak256Transcript,
+      PallasEngine,
+    },
+    traits::{
+      commitment::CommitmentEngineTrait, evaluation::EvaluationEngineTrait, TranscriptEngineTrait,
+    },
+    CE,
+  };
+  use rand::SeedableRng;
+
+  type E = PallasEngine;
+
+  #[test]
+  fn test_ipa_proof_bundle() {
+    let num_vars = 5;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(51);
+    let ck = CE::<E>::setup(b"test", 1 << num_vars);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+
+    // three parties open the same polynomial at the same point, and a fourth opens another
+    let (poly, point, eval) = random_instance(num_vars, &mut rng);
+    let (other_poly, other_point, other_eval) = random_instance(num_vars, &mut rng);
+    let instances = [
+      (&poly, &point, eval),
+      (&poly, &point, eval),
+      (&other_poly, &other_point, other_eval),
+      (&poly, &point, eval),
+    ];
+    let args = instances
+      .iter()
+      .map(|(poly, point, eval)| {
+        let comm = CE::<E>::commit(&ck, poly);
+        let mut transcript = Keccak256Transcript::new(b"TestEval");
+        EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, poly, point, eval).unwrap()
+      })
+      .collect::<Vec<_>>();
+
+    let bundle = ProofBundle::new(&args);
+    assert_eq!(bundle.len(), args.len());
+    assert_eq!(bundle.comms.len(), 2 * 2 * num_vars);
+
+    let bytes = bundle.to_bytes();
+    let concatenated = args.iter().map(|arg| arg.to_bytes().len()).sum::<usize>();
+    assert!(bytes.len() < concatenated);
+
+    let decoded = ProofBundle::<E>::from_bytes(&bytes)
+      .unwrap()
+      .arguments()
+      .unwrap();
+    for ((arg, decoded), (poly, point, eval)) in args.iter().zip(decoded.iter()).zip(instances) {
+      assert_eq!(decoded.to_bytes(), arg.to_bytes());
+      let comm = CE::<E>::commit(&ck, poly);
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert!(EvaluationEngine::verify(&vk, &mut transcript, &comm, point, &eval, decoded).is_ok());
+    }
+
+    // an index outside of the pool is rejected
+    let mut malformed = bundle.clone();
+    malformed.args[0].L_indices[0] = bundle.comms.len() as u32;
+    assert_eq!(
+      ProofBundle::<E>::from_bytes(&malformed.to_bytes()).err(),
+      Some(NovaError::MalformedProof)
+    );
+  }
+}
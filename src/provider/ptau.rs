@@ -0,0 +1,278 @@
+//! This module reads `HyperKZG` commitment keys over BN256 from powers-of-tau files in the
+//! `.ptau` format produced by snarkjs ceremonies.
+//!
+//! A `.ptau` file starts with the magic bytes `ptau`, a version and a number of sections. Each
+//! section has a type, a length, and a body. We use the header section (the field modulus and
+//! the power of the ceremony), the section with the powers of tau in G1, and the section with
+//! the powers of tau in G2. Points are stored as little-endian coordinates in Montgomery form.
+use crate::{
+  errors::NovaError,
+  provider::{
+    bn256_grumpkin::bn256,
+    hyperkzg::CommitmentKey,
+    traits::{DlogGroup, PairingGroup},
+    Bn256EngineKZG,
+  },
+};
+use ff::{Field, PrimeField};
+use halo2curves::bn256::{Fq2, G2Affine, G2};
+use pasta_curves::arithmetic::CurveAffine;
+use rand_core::OsRng;
+use std::io::{self, Read};
+
+const PTAU_MAGIC: &[u8; 4] = b"ptau";
+const PTAU_VERSION: u32 = 1;
+const SECTION_HEADER: u32 = 1;
+const SECTION_TAU_G1: u32 = 2;
+const SECTION_TAU_G2: u32 = 3;
+
+// the number of bytes of an element of the base field
+const N8: usize = 32;
+
+fn invalid(reason: &str) -> NovaError {
+  NovaError::InvalidPtauFile {
+    reason: reason.to_string(),
+  }
+}
+
+fn read_bytes<R: Read>(reader: &mut R, n: usize) -> Result<Vec<u8>, NovaError> {
+  let mut buf = vec![0u8; n];
+  reader
+    .read_exact(&mut buf)
+    .map_err(|e| invalid(&e.to_string()))?;
+  Ok(buf)
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, NovaError> {
+  Ok(u32::from_le_bytes(
+    read_bytes(reader, 4)?.try_into().unwrap(),
+  ))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, NovaError> {
+  Ok(u64::from_le_bytes(
+    read_bytes(reader, 8)?.try_into().unwrap(),
+  ))
+}
+
+fn skip<R: Read>(reader: &mut R, n: u64) -> Result<(), NovaError> {
+  let skipped =
+    io::copy(&mut reader.take(n), &mut io::sink()).map_err(|e| invalid(&e.to_string()))?;
+  if skipped != n {
+    return Err(invalid("unexpected end of file"));
+  }
+  Ok(())
+}
+
+// the inverse of the Montgomery factor R = 2^256, with which the elements of the base field
+// stored in Montgomery form are decoded
+fn montgomery_r_inv() -> bn256::Base {
+  bn256::Base::from(2).pow_vartime([256]).invert().unwrap()
+}
+
+// decodes an element of the base field stored in Montgomery form, given `r_inv = R^{-1}`
+fn read_fq<R: Read>(reader: &mut R, r_inv: &bn256::Base) -> Result<bn256::Base, NovaError> {
+  let repr: [u8; N8] = read_bytes(reader, N8)?.try_into().unwrap();
+  let mont = Option::<bn256::Base>::from(bn256::Base::from_repr(repr))
+    .ok_or_else(|| invalid("field element is not canonical"))?;
+  Ok(mont * r_inv)
+}
+
+fn read_g1<R: Read>(reader: &mut R, r_inv: &bn256::Base) -> Result<bn256::Affine, NovaError> {
+  let x = read_fq(reader, r_inv)?;
+  let y = read_fq(reader, r_inv)?;
+  Option::from(bn256::Affine::from_xy(x, y)).ok_or_else(|| invalid("G1 point is not on the curve"))
+}
+
+fn read_g2<R: Read>(reader: &mut R, r_inv: &bn256::Base) -> Result<G2Affine, NovaError> {
+  let x = Fq2 {
+    c0: read_fq(reader, r_inv)?,
+    c1: read_fq(reader, r_inv)?,
+  };
+  let y = Fq2 {
+    c0: read_fq(reader, r_inv)?,
+    c1: read_fq(reader, r_inv)?,
+  };
+  let p: G2Affine =
+    Option::from(G2Affine::from_xy(x, y)).ok_or_else(|| invalid("G2 point is not on the curve"))?;
+
+  // G2 has a non-trivial cofactor, so we also check that the point is in the prime-order
+  // subgroup: multiplying by `-1`, interpreted as the integer `r - 1`, must yield `-p`
+  if G2::from(p) * -bn256::Scalar::ONE != -G2::from(p) {
+    return Err(invalid("G2 point is not in the prime-order subgroup"));
+  }
+  Ok(p)
+}
+
+// checks that `q` in the header is the modulus of the base field of BN256
+fn is_bn256_modulus(q: &[u8]) -> bool {
+  // the modulus is one more than the canonical representation of `-1`
+  let mut modulus = (-bn256::Base::ONE).to_repr();
+  for byte in modulus.iter_mut() {
+    let (b, carry) = byte.overflowing_add(1);
+    *byte = b;
+    if !carry {
+      break;
+    }
+  }
+  q == modulus
+}
+
+impl CommitmentKey<Bn256EngineKZG> {
+  /// Reads a commitment key for committing to vectors of length `n` from a powers-of-tau file
+  /// in the `.ptau` format of snarkjs. Like `setup`, the key has `n.next_power_of_two()`
+  /// generators. Returns an error if the file is malformed or is not over BN256, and
+  /// `NovaError::InvalidCommitmentKeyLength` if the ceremony is too small for `n`.
+  pub fn from_ptau<R: Read>(reader: &mut R, n: usize) -> Result<Self, NovaError> {
+    if read_bytes(reader, 4)? != PTAU_MAGIC {
+      return Err(invalid("missing ptau magic bytes"));
+    }
+    if read_u32(reader)? != PTAU_VERSION {
+      return Err(invalid("unsupported ptau version"));
+    }
+    let num_sections = read_u32(reader)?;
+
+    let num_gens = n.next_power_of_two();
+    let r_inv = montgomery_r_inv();
+    let mut power = None;
+    let mut tau_G1 = None;
+    let mut tau_H = None;
+    for _ in 0..num_sections {
+      let section_type = read_u32(reader)?;
+      let section_len = read_u64(reader)?;
+
+      match section_type {
+        SECTION_HEADER => {
+          if section_len != (4 + N8 + 8) as u64 || read_u32(reader)? as usize != N8 {
+            return Err(invalid("the file is not over BN256"));
+          }
+          if !is_bn256_modulus(&read_bytes(reader, N8)?) {
+            return Err(invalid("the file is not over BN256"));
+          }
+          let p = read_u32(reader)?;
+          let _ceremony_power = read_u32(reader)?;
+          if p >= usize::BITS || (1usize << p) < num_gens {
+            return Err(NovaError::InvalidCommitmentKeyLength);
+          }
+          power = Some(p);
+        }
+        SECTION_TAU_G1 | SECTION_TAU_G2 => {
+          let p = power.ok_or_else(|| invalid("the header must precede the points"))?;
+          let (count, point_len) = if section_type == SECTION_TAU_G1 {
+            ((1u64 << (p + 1)) - 1, 2 * N8 as u64)
+          } else {
+            (1u64 << p, 4 * N8 as u64)
+          };
+          if section_len != count * point_len {
+            return Err(invalid("unexpected section length"));
+          }
+
+          // we only need a prefix of the powers, and skip the rest
+          if section_type == SECTION_TAU_G1 {
+            let points = (0..num_gens)
+              .map(|_| read_g1(reader, &r_inv))
+              .collect::<Result<Vec<_>, _>>()?;
+            skip(reader, section_len - num_gens as u64 * point_len)?;
+            tau_G1 = Some(points);
+          } else {
+            let points = (0..2)
+              .map(|_| read_g2(reader, &r_inv))
+              .collect::<Result<Vec<_>, _>>()?;
+            skip(reader, section_len - 2 * point_len)?;
+            if points[0] != G2Affine::generator() {
+              return Err(invalid("the first power of tau in G2 is not the generator"));
+            }
+            tau_H = Some(points[1]);
+          }
+        }
+        _ => skip(reader, section_len)?,
+      }
+    }
+
+    let (ck, tau_H) = match (tau_G1, tau_H) {
+      (Some(ck), Some(tau_H)) => (ck, tau_H),
+      _ => return Err(invalid("missing powers of tau")),
+    };
+    if ck[0] != bn256::Affine::generator() {
+      return Err(invalid("the first power of tau in G1 is not the generator"));
+    }
+
+    // check that the points are successive powers of the same tau as the one in G2, with a
+    // random linear combination: e(sum_i rho^i ck[i+1], H) = e(sum_i rho^i ck[i], tau_H)
+    if num_gens > 1 {
+      let rho = bn256::Scalar::random(OsRng);
+      let rho_powers = std::iter::successors(Some(bn256::Scalar::ONE), |p| Some(*p * rho))
+        .take(num_gens - 1)
+        .collect::<Vec<_>>();
+      let lhs = bn256::Point::vartime_multiscalar_mul(&rho_powers, &ck[1..]);
+      let rhs = bn256::Point::vartime_multiscalar_mul(&rho_powers, &ck[..num_gens - 1]);
+      if bn256::Point::pairing(&lhs, &G2::generator())
+        != bn256::Point::pairing(&rhs, &G2::from(tau_H))
+      {
+        return Err(invalid("the points are not powers of a single tau"));
+      }
+    }
+
+    Ok(CommitmentKey::new(ck, tau_H))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::traits::commitment::Len;
+  use group::Curve;
+
+  // a ceremony of power 2 over BN256 with tau = 42, in the format written by snarkjs
+  const TINY_PTAU: &[u8] = include_bytes!("fixtures/tiny.ptau");
+
+  #[test]
+  fn test_from_ptau() {
+    let tau = bn256::Scalar::from(42);
+    for n in [1, 3, 4] {
+      let ck = CommitmentKey::<Bn256EngineKZG>::from_ptau(&mut &TINY_PTAU[..], n).unwrap();
+      assert_eq!(ck.length(), n.next_power_of_two());
+
+      let expected = CommitmentKey::new(
+        std::iter::successors(Some(bn256::Scalar::ONE), |p| Some(*p * tau))
+          .take(n.next_power_of_two())
+          .map(|p| (bn256::Point::generator() * p).to_affine())
+          .collect(),
+        (G2::generator() * tau).to_affine(),
+      );
+      assert_eq!(ck, expected);
+    }
+
+    // the ceremony is too small for 5 generators after padding to a power of two
+    assert_eq!(
+      CommitmentKey::<Bn256EngineKZG>::from_ptau(&mut &TINY_PTAU[..], 5).err(),
+      Some(NovaError::InvalidCommitmentKeyLength)
+    );
+
+    // a file over another curve is rejected
+    let mut other_curve = TINY_PTAU.to_vec();
+    other_curve[4 + 4 + 4 + 4 + 8 + 4] ^= 1;
+    assert!(matches!(
+      CommitmentKey::<Bn256EngineKZG>::from_ptau(&mut &other_curve[..], 4),
+      Err(NovaError::InvalidPtauFile { .. })
+    ));
+
+    // a G1 point that is not the right power of tau is rejected
+    let header_len = 4 + 4 + 4 + (4 + 8 + 4 + N8 + 8);
+    let g1_start = header_len + 4 + 8;
+    let mut tampered = TINY_PTAU.to_vec();
+    tampered.copy_within(
+      g1_start + 2 * 2 * N8..g1_start + 3 * 2 * N8,
+      g1_start + 2 * N8,
+    );
+    assert!(matches!(
+      CommitmentKey::<Bn256EngineKZG>::from_ptau(&mut &tampered[..], 4),
+      Err(NovaError::InvalidPtauFile { .. })
+    ));
+
+    assert!(matches!(
+      CommitmentKey::<Bn256EngineKZG>::from_ptau(&mut &TINY_PTAU[..100], 4),
+      Err(NovaError::InvalidPtauFile { .. })
+    ));
+  }
+}
@@ -1,4 +1,5 @@
 //! This module implements the Nova traits for `secp::Point`, `secp::Scalar`, `secq::Point`, `secq::Scalar`.
+use crate::parallel::prelude::*;
 use crate::{
   impl_traits,
   provider::traits::DlogGroup,
@@ -15,7 +16,6 @@ use halo2curves::{
 use num_bigint::BigInt;
 use num_traits::Num;
 use pasta_curves::arithmetic::{CurveAffine, CurveExt};
-use rayon::prelude::*;
 use sha3::Shake256;
 use std::io::Read;
 
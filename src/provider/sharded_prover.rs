@@ -0,0 +1,216 @@
+//! This module implements a prover of IPA evaluation arguments for a polynomial whose entries
+//! are held in shards
+use crate::{
+  errors::NovaError,
+  parallel::prelude::*,
+  provider::{
+    ipa_pc::{inner_product, InnerProductArgument, InnerProductInstance, ProverKey},
+    pedersen::{CommitmentEngine, PartialCommitment},
+    traits::DlogGroup,
+  },
+  traits::Engine,
+  Commitment, CommitmentKey,
+};
+use core::ops::Range;
+use ff::Field;
+
+/// A prover for evaluations of a polynomial whose coefficients are held in shards, each a
+/// contiguous range of positions, for example on different machines. Each shard commits to its
+/// entries with `CommitmentKey::commit_partial`, and the commitment to the polynomial is the
+/// sum of the commitments of the shards.
+///
+/// The first round of the argument pairs each entry of the left half of the polynomial with an
+/// entry of the right half, so it is computed from the contributions of each shard alone. The
+/// folded vector, of half the length, is then assembled from the shards, and the remaining
+/// rounds run as usual. The argument is the one `EvaluationEngine::prove` produces for the
+/// reassembled polynomial, so it verifies against the summed commitment.
+pub struct ShardedProver<E: Engine> {
+  shards: Vec<Shard<E>>,
+}
+
+// the entries of a polynomial at the positions `range`
+pub(crate) struct Shard<E: Engine> {
+  pub(crate) range: Range<usize>,
+  pub(crate) a_vec: Vec<E::Scalar>,
+}
+
+impl<E> Shard<E>
+where
+  E: Engine<CE = CommitmentEngine<E>>,
+  E::GE: DlogGroup,
+{
+  // splits the shard into its entries in the left half and in the right half of a vector of
+  // length `n`, returned with their positions
+  fn halves(&self, n: usize) -> ((Range<usize>, &[E::Scalar]), (Range<usize>, &[E::Scalar])) {
+    let mid = self.range.end.min(n / 2).max(self.range.start);
+    let (a_L, a_R) = self.a_vec.split_at(mid - self.range.start);
+    ((self.range.start..mid, a_L), (mid..self.range.end, a_R))
+  }
+
+  // computes the contribution of the shard to the first round against the public vector
+  // `b_vec`: the partial commitments to its entries in the left half with the generators of
+  // the right half (and vice versa), and its terms of the cross inner products
+  pub(crate) fn first_round(
+    &self,
+    ck: &CommitmentKey<E>,
+    b_vec: &[E::Scalar],
+  ) -> Result<(Vec<PartialCommitment<E>>, E::Scalar, E::Scalar), NovaError> {
+    let half = b_vec.len() / 2;
+    let ((pos_L, a_L), (pos_R, a_R)) = self.halves(b_vec.len());
+
+    let mut parts = Vec::new();
+    let (mut c_L, mut c_R) = (E::Scalar::ZERO, E::Scalar::ZERO);
+    if !a_L.is_empty() {
+      let pos = pos_L.start + half..pos_L.end + half;
+      c_L = inner_product(a_L, &b_vec[pos.clone()]);
+      parts.push(ck.commit_partial(pos, a_L)?);
+    }
+    if !a_R.is_empty() {
+      let pos = pos_R.start - half..pos_R.end - half;
+      c_R = inner_product(a_R, &b_vec[pos.clone()]);
+      parts.push(ck.commit_partial(pos, a_R)?);
+    }
+
+    Ok((parts, c_L, c_R))
+  }
+
+  // adds the contribution of the shard to the witness vector folded with the challenge `r`
+  pub(crate) fn fold_into(&self, a_folded: &mut [E::Scalar], r: &E::Scalar, r_inverse: &E::Scalar) {
+    let n = 2 * a_folded.len();
+    let ((pos_L, a_L), (pos_R, a_R)) = self.halves(n);
+    for (i, a) in pos_L.zip(a_L.iter()) {
+      a_folded[i] += *a * r;
+    }
+    for (i, a) in pos_R.zip(a_R.iter()) {
+      a_folded[i - n / 2] += *a * r_inverse;
+    }
+  }
+}
+
+impl<E> ShardedProver<E>
+where
+  E: Engine<CE = CommitmentEngine<E>>,
+  E::GE: DlogGroup,
+{
+  /// Creates a prover from the shards of a polynomial, each given as the range of positions it
+  /// holds and the corresponding entries. The ranges must be disjoint and cover the positions
+  /// `0..n` for a power of two `n`.
+  pub fn new(shards: Vec<(Range<usize>, Vec<E::Scalar>)>) -> Result<Self, NovaError> {
+    let mut shards = shards
+      .into_iter()
+      .map(|(range, a_vec)| Shard { range, a_vec })
+      .collect::<Vec<_>>();
+    shards.sort_by_key(|shard| shard.range.start);
+
+    let mut n = 0;
+    for shard in &shards {
+      if shard.range.start != n || shard.range.len() != shard.a_vec.len() {
+        return Err(NovaError::InvalidInputLength);
+      }
+      n = shard.range.end;
+    }
+    if !n.is_power_of_two() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    Ok(ShardedProver { shards })
+  }
+
+  // the length of the polynomial
+  fn len(&self) -> usize {
+    self.shards.last().unwrap().range.end
+  }
+
+  /// Returns the commitment of each shard, computed with `CommitmentKey::commit_partial`
+  pub fn commit_shards(
+    &self,
+    ck: &CommitmentKey<E>,
+  ) -> Result<Vec<PartialCommitment<E>>, NovaError> {
+    self
+      .shards
+      .par_iter()
+      .map(|shard| ck.commit_partial(shard.range.clone(), &shard.a_vec))
+      .collect()
+  }
+
+  /// Proves that the polynomial evaluates to `eval` at `point`, where `comm` is the sum of
+  /// the commitments of the shards
+  pub fn prove(
+    &self,
+    ck: &CommitmentKey<E>,
+    pk: &ProverKey<E>,
+    transcript: &mut E::TE,
+    comm: &Commitment<E>,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+  ) -> Result<InnerProductArgument<E>, NovaError> {
+    if point.len() >= usize::BITS as usize || 1 << point.len() != self.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let u = InnerProductInstance::new_eq(comm, point, eval);
+    InnerProductArgument::prove_sharded(ck, &pk.ck_s, &u, &self.shards, transcript)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    provider::{
+      ipa_pc::{tests::random_instance, EvaluationEngine},
+      keccak::Keccak256Transcript,
+      PallasEngine,
+    },
+    traits::{
+      commitment::CommitmentEngineTrait, evaluation::EvaluationEngineTrait, TranscriptEngineTrait,
+    },
+    CE,
+  };
+  use rand::SeedableRng;
+
+  type E = PallasEngine;
+
+  #[test]
+  fn test_ipa_sharded_prover() {
+    let num_vars = 4;
+    let n = 1 << num_vars;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(24);
+    let ck = CE::<E>::setup(b"test", n);
+    let (pk, vk) = EvaluationEngine::<E>::setup(&ck);
+    let (poly, point, eval) = random_instance(num_vars, &mut rng);
+
+    // shards split at the middle, and split inside the left half
+    for mid in [n / 2, 5] {
+      let shards = vec![
+        (mid..n, poly[mid..].to_vec()),
+        (0..mid, poly[..mid].to_vec()),
+      ];
+      let prover = ShardedProver::<E>::new(shards).unwrap();
+      let comm = PartialCommitment::combine(&prover.commit_shards(&ck).unwrap()).unwrap();
+      assert_eq!(comm, CE::<E>::commit(&ck, &poly));
+
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let arg = prover
+        .prove(&ck, &pk, &mut transcript, &comm, &point, &eval)
+        .unwrap();
+
+      // the argument is the one produced over the reassembled polynomial
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      let arg_full =
+        EvaluationEngine::prove(&ck, &pk, &mut transcript, &comm, &poly, &point, &eval).unwrap();
+      assert_eq!(arg.to_bytes(), arg_full.to_bytes());
+
+      let mut transcript = Keccak256Transcript::new(b"TestEval");
+      assert!(EvaluationEngine::verify(&vk, &mut transcript, &comm, &point, &eval, &arg).is_ok());
+    }
+
+    // the shards must cover a power of two positions without overlapping
+    assert!(ShardedProver::<E>::new(vec![(0..3, poly[..3].to_vec())]).is_err());
+    assert!(ShardedProver::<E>::new(vec![
+      (0..8, poly[..8].to_vec()),
+      (7..15, poly[7..15].to_vec())
+    ])
+    .is_err());
+  }
+}
@@ -3,6 +3,7 @@ use core::{
   fmt::Debug,
   ops::{Add, AddAssign, Sub, SubAssign},
 };
+use group::GroupEncoding;
 use serde::{Deserialize, Serialize};
 
 /// A helper trait for types with a group operation.
@@ -27,6 +28,7 @@ impl<T, Rhs, Output> ScalarMulOwned<Rhs, Output> for T where T: for<'r> ScalarMu
 /// A trait that defines extensions to the Group trait
 pub trait DlogGroup:
   Group
+  + GroupEncoding
   + Serialize
   + for<'de> Deserialize<'de>
   + GroupOps
@@ -57,6 +59,12 @@ pub trait DlogGroup:
   /// Returns a group element from a preprocessed group element
   fn group(p: &Self::AffineGroupElement) -> Self;
 
+  /// Produces preprocessed elements for many group elements at once, which implementations
+  /// may do with a single shared field inversion rather than one per element
+  fn batch_affine(points: &[Self]) -> Vec<Self::AffineGroupElement> {
+    points.iter().map(|p| p.affine()).collect()
+  }
+
   /// Returns an element that is the additive identity of the group
   fn zero() -> Self;
 
@@ -125,6 +133,12 @@ macro_rules! impl_traits {
         $name::Point::from(*p)
       }
 
+      fn batch_affine(points: &[Self]) -> Vec<Self::AffineGroupElement> {
+        let mut affine = vec![$name_curve_affine::identity(); points.len()];
+        <Self as Curve>::batch_normalize(points, &mut affine);
+        affine
+      }
+
       fn from_label(label: &'static [u8], n: usize) -> Vec<Self::AffineGroupElement> {
         let mut shake = Shake256::default();
         shake.update(label);
@@ -143,7 +157,7 @@ macro_rules! impl_traits {
           })
           .collect();
 
-        let num_threads = rayon::current_num_threads();
+        let num_threads = $crate::parallel::current_num_threads();
         if gens_proj.len() > num_threads {
           let chunk = (gens_proj.len() as f64 / num_threads as f64).ceil() as usize;
           (0..num_threads)
@@ -16,7 +16,7 @@ use core::{cmp::max, marker::PhantomData};
 use ff::Field;
 use once_cell::sync::OnceCell;
 
-use rayon::prelude::*;
+use crate::parallel::prelude::*;
 use serde::{Deserialize, Serialize};
 
 mod sparse;
@@ -165,9 +165,9 @@ impl<E: Engine> R1CSShape<E> {
       return Err(NovaError::InvalidWitnessLength);
     }
 
-    let (Az, (Bz, Cz)) = rayon::join(
+    let (Az, (Bz, Cz)) = crate::parallel::join(
       || self.A.multiply_vec(z),
-      || rayon::join(|| self.B.multiply_vec(z), || self.C.multiply_vec(z)),
+      || crate::parallel::join(|| self.B.multiply_vec(z), || self.C.multiply_vec(z)),
     );
 
     Ok((Az, Bz, Cz))
@@ -198,7 +198,7 @@ impl<E: Engine> R1CSShape<E> {
     // verify if comm_E and comm_W are commitments to E and W
     let res_comm = {
       let (comm_W, comm_E) =
-        rayon::join(|| CE::<E>::commit(ck, &W.W), || CE::<E>::commit(ck, &W.E));
+        crate::parallel::join(|| CE::<E>::commit(ck, &W.W), || CE::<E>::commit(ck, &W.E));
       U.comm_W == comm_W && U.comm_E == comm_E
     };
 
@@ -166,9 +166,60 @@ impl<E: Engine, S: RelaxedR1CSSNARKTrait<E>, C: StepCircuit<E::Scalar>> DirectSN
 mod tests {
   use super::*;
   use crate::provider::{Bn256EngineKZG, PallasEngine, Secp256k1Engine};
+  use crate::{
+    spartan::polys::multilinear::MultilinearPolynomial,
+    traits::{commitment::CommitmentEngineTrait, evaluation::EvaluationEngineTrait},
+  };
   use ::bellpepper_core::{num::AllocatedNum, ConstraintSystem, SynthesisError};
   use core::marker::PhantomData;
   use ff::PrimeField;
+  use serde::{Deserialize, Serialize};
+
+  // an evaluation engine that assumes nothing of the commitment scheme: the argument is the
+  // polynomial itself, which the verifier commits to and evaluates
+  #[derive(Clone, Debug, Serialize, Deserialize)]
+  struct MockEvaluationEngine<E: Engine> {
+    _p: PhantomData<E>,
+  }
+
+  impl<E: Engine> EvaluationEngineTrait<E> for MockEvaluationEngine<E> {
+    type ProverKey = ();
+    type VerifierKey = CommitmentKey<E>;
+    type EvaluationArgument = Vec<E::Scalar>;
+
+    fn setup(ck: &CommitmentKey<E>) -> (Self::ProverKey, Self::VerifierKey) {
+      ((), ck.clone())
+    }
+
+    fn prove(
+      _ck: &CommitmentKey<E>,
+      _pk: &Self::ProverKey,
+      _transcript: &mut E::TE,
+      _comm: &Commitment<E>,
+      poly: &[E::Scalar],
+      _point: &[E::Scalar],
+      _eval: &E::Scalar,
+    ) -> Result<Self::EvaluationArgument, NovaError> {
+      Ok(poly.to_vec())
+    }
+
+    fn verify(
+      vk: &Self::VerifierKey,
+      _transcript: &mut E::TE,
+      comm: &Commitment<E>,
+      point: &[E::Scalar],
+      eval: &E::Scalar,
+      arg: &Self::EvaluationArgument,
+    ) -> Result<(), NovaError> {
+      if arg.len() != 1 << point.len()
+        || E::CE::commit(vk, arg) != *comm
+        || MultilinearPolynomial::evaluate_with(arg, point) != *eval
+      {
+        return Err(NovaError::InvalidPCS);
+      }
+      Ok(())
+    }
+  }
 
   #[derive(Clone, Debug, Default)]
   struct CubicCircuit<F: PrimeField> {
@@ -228,6 +279,10 @@ mod tests {
     type Spp = crate::spartan::ppsnark::RelaxedR1CSSNARK<E, EE>;
     test_direct_snark_with::<E, Spp>();
 
+    type EEzk = crate::provider::ipa_pc::ZKEvaluationEngine<E>;
+    type Szk = crate::spartan::snark::RelaxedR1CSSNARK<E, EEzk>;
+    test_direct_snark_with::<E, Szk>();
+
     type E2 = Bn256EngineKZG;
     type EE2 = crate::provider::hyperkzg::EvaluationEngine<E2>;
     type S2 = crate::spartan::snark::RelaxedR1CSSNARK<E2, EE2>;
@@ -243,6 +298,14 @@ mod tests {
 
     type S3pp = crate::spartan::ppsnark::RelaxedR1CSSNARK<E3, EE3>;
     test_direct_snark_with::<E3, S3pp>();
+
+    // the SNARKs rely only on the interface of the evaluation engine
+    type EEmock = MockEvaluationEngine<E>;
+    type Smock = crate::spartan::snark::RelaxedR1CSSNARK<E, EEmock>;
+    test_direct_snark_with::<E, Smock>();
+
+    type Smockpp = crate::spartan::ppsnark::RelaxedR1CSSNARK<E, EEmock>;
+    test_direct_snark_with::<E, Smockpp>();
   }
 
   fn test_direct_snark_with<E: Engine, S: RelaxedR1CSSNARKTrait<E>>() {
@@ -12,8 +12,9 @@ pub(crate) mod math;
 pub mod polys;
 pub mod ppsnark;
 pub mod snark;
-mod sumcheck;
+pub(crate) mod sumcheck;
 
+use crate::parallel::prelude::*;
 use crate::{
   r1cs::{R1CSShape, SparseMatrix},
   traits::Engine,
@@ -21,7 +22,6 @@ use crate::{
 };
 use ff::Field;
 use itertools::Itertools as _;
-use rayon::{iter::IntoParallelRefIterator, prelude::*};
 
 // Creates a vector of the first `n` powers of `s`.
 fn powers<E: Engine>(s: &E::Scalar, n: usize) -> Vec<E::Scalar> {
@@ -51,7 +51,7 @@ impl<E: Engine> PolyEvalWitness<E> {
 
     let size_max = W.iter().map(|w| w.p.len()).max().unwrap();
     // Scale the input polynomials by the power of s
-    let num_chunks = rayon::current_num_threads().next_power_of_two();
+    let num_chunks = crate::parallel::current_num_threads().next_power_of_two();
     let chunk_size = size_max / num_chunks;
 
     let p = if chunk_size > 0 {
@@ -120,7 +120,7 @@ impl<E: Engine> PolyEvalWitness<E> {
 
     let powers_of_s = powers::<E>(s, p_vec.len());
 
-    let num_chunks = rayon::current_num_threads().next_power_of_two();
+    let num_chunks = crate::parallel::current_num_threads().next_power_of_two();
     let chunk_size = p_vec[0].len() / num_chunks;
 
     let p = if chunk_size > 0 {
@@ -252,14 +252,14 @@ fn compute_eval_table_sparse<E: Engine>(
     }
   };
 
-  let (A_evals, (B_evals, C_evals)) = rayon::join(
+  let (A_evals, (B_evals, C_evals)) = crate::parallel::join(
     || {
       let mut A_evals: Vec<E::Scalar> = vec![E::Scalar::ZERO; 2 * S.num_vars];
       inner(&S.A, &mut A_evals);
       A_evals
     },
     || {
-      rayon::join(
+      crate::parallel::join(
         || {
           let mut B_evals: Vec<E::Scalar> = vec![E::Scalar::ZERO; 2 * S.num_vars];
           inner(&S.B, &mut B_evals);
@@ -1,7 +1,7 @@
 //! `EqPolynomial`: Represents multilinear extension of equality polynomials, evaluated based on binary input values.
 
+use crate::parallel::prelude::*;
 use ff::PrimeField;
-use rayon::prelude::{IndexedParallelIterator, IntoParallelRefMutIterator, ParallelIterator};
 
 /// Represents the multilinear extension polynomial (MLE) of the equality polynomial $eq(x,e)$, denoted as $\tilde{eq}(x, e)$.
 ///
@@ -17,6 +17,9 @@ use rayon::prelude::{IndexedParallelIterator, IntoParallelRefMutIterator, Parall
 #[derive(Debug)]
 pub struct EqPolynomial<Scalar: PrimeField> {
   pub(in crate::spartan::polys) r: Vec<Scalar>,
+  // the evaluations left after the variables bound by `bind_var`, reused across calls
+  scratch: Vec<Scalar>,
+  num_bound: usize,
 }
 
 impl<Scalar: PrimeField> EqPolynomial<Scalar> {
@@ -24,7 +27,11 @@ impl<Scalar: PrimeField> EqPolynomial<Scalar> {
   ///
   /// Each Scalar in `r` corresponds to a bit from the binary representation of an input value `e`.
   pub const fn new(r: Vec<Scalar>) -> Self {
-    EqPolynomial { r }
+    EqPolynomial {
+      r,
+      scratch: Vec::new(),
+      num_bound: 0,
+    }
   }
 
   /// Evaluates the `EqPolynomial` at a given point `rx`.
@@ -71,12 +78,45 @@ impl<Scalar: PrimeField> EqPolynomial<Scalar> {
 
     evals
   }
+
+  /// Binds the top unbound variable of the evaluations of the `EqPolynomial` to `r`, in
+  /// place, as `MultilinearPolynomial::bind_poly_var_top` folds the other polynomials of a
+  /// sum-check. The first call computes `evals()`, and each call then halves the live region of
+  /// that buffer instead of allocating a new one.
+  ///
+  /// After binding the first `k` variables to `rx`, `bound_evals()` equals the evaluations of
+  /// the `EqPolynomial` on the remaining `|r| - k` variables, scaled by `eq(r[..k], rx)`.
+  ///
+  /// Panics if all the variables are bound.
+  #[allow(dead_code)]
+  pub fn bind_var(&mut self, r: &Scalar) {
+    assert!(self.num_bound < self.r.len());
+    if self.num_bound == 0 {
+      self.scratch = self.evals();
+    }
+
+    let n = self.scratch.len() / 2;
+    let (left, right) = self.scratch.split_at_mut(n);
+    zip_with_for_each!((left.par_iter_mut(), right.par_iter()), |a, b| {
+      *a += *r * (*b - *a);
+    });
+
+    self.scratch.truncate(n);
+    self.num_bound += 1;
+  }
+
+  /// Returns the live evaluations left by the calls to `bind_var`, which are empty before the
+  /// first call.
+  #[allow(dead_code)]
+  pub fn bound_evals(&self) -> &[Scalar] {
+    &self.scratch
+  }
 }
 
 impl<Scalar: PrimeField> FromIterator<Scalar> for EqPolynomial<Scalar> {
   fn from_iter<I: IntoIterator<Item = Scalar>>(iter: I) -> Self {
     let r: Vec<_> = iter.into_iter().collect();
-    EqPolynomial { r }
+    EqPolynomial::new(r)
   }
 }
 
@@ -86,6 +126,7 @@ mod tests {
 
   use super::*;
   use pasta_curves::Fp;
+  use rand::SeedableRng;
 
   fn test_eq_polynomial_with<F: PrimeField>() {
     let eq_poly = EqPolynomial::<F>::new(vec![F::ONE, F::ZERO, F::ONE]);
@@ -105,10 +146,35 @@ mod tests {
     }
   }
 
+  fn test_eq_bind_var_with<F: PrimeField>() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    let r = (0..4).map(|_| F::random(&mut rng)).collect::<Vec<F>>();
+    let rx = (0..4).map(|_| F::random(&mut rng)).collect::<Vec<F>>();
+
+    let mut eq_poly = EqPolynomial::new(r.clone());
+    assert!(eq_poly.bound_evals().is_empty());
+    for k in 1..=r.len() {
+      eq_poly.bind_var(&rx[k - 1]);
+
+      let scale = EqPolynomial::new(r[..k].to_vec()).evaluate(&rx[..k]);
+      let expected = EqPolynomial::evals_from_points(&r[k..])
+        .into_iter()
+        .map(|e| e * scale)
+        .collect::<Vec<F>>();
+      assert_eq!(eq_poly.bound_evals(), expected);
+    }
+  }
+
   #[test]
   fn test_eq_polynomial() {
     test_eq_polynomial_with::<Fp>();
     test_eq_polynomial_with::<provider::bn256_grumpkin::bn256::Scalar>();
     test_eq_polynomial_with::<provider::secp_secq::secp256k1::Scalar>();
   }
+
+  #[test]
+  fn test_eq_bind_var() {
+    test_eq_bind_var_with::<Fp>();
+    test_eq_bind_var_with::<provider::bn256_grumpkin::bn256::Scalar>();
+  }
 }
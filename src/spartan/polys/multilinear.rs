@@ -4,12 +4,9 @@
 
 use std::ops::{Add, Index};
 
+use crate::parallel::prelude::*;
 use ff::PrimeField;
 use itertools::Itertools as _;
-use rayon::prelude::{
-  IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator,
-  IntoParallelRefMutIterator, ParallelIterator,
-};
 use serde::{Deserialize, Serialize};
 
 use crate::spartan::{math::Math, polys::eq::EqPolynomial};
@@ -140,7 +137,7 @@ impl<Scalar: PrimeField> SparsePolynomial<Scalar> {
       .sum();
 
     let common = (0..self.num_vars - 1 - num_vars_z)
-      .map(|i| (Scalar::ONE - r[i]))
+      .map(|i| Scalar::ONE - r[i])
       .product::<Scalar>();
 
     common * eval_partial
@@ -4,6 +4,7 @@
 //! polynomial commitment scheme in which the verifier's costs is succinct.
 //! This code includes experimental optimizations to reduce runtimes and proof sizes.
 //! We have not yet proven the security of these optimizations, so this code is subject to significant changes in the future.
+use crate::parallel::prelude::*;
 use crate::{
   digest::{DigestComputer, SimpleDigestible},
   errors::NovaError,
@@ -28,13 +29,12 @@ use crate::{
     snark::{DigestHelperTrait, RelaxedR1CSSNARKTrait},
     Engine, TranscriptEngineTrait, TranscriptReprTrait,
   },
-  zip_with, Commitment, CommitmentKey,
+  Commitment, CommitmentKey,
 };
 use core::cmp::max;
 use ff::Field;
 use itertools::Itertools as _;
 use once_cell::sync::OnceCell;
-use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
 fn padded<E: Engine>(v: &[E::Scalar], n: usize, e: &E::Scalar) -> Vec<E::Scalar> {
@@ -149,7 +149,7 @@ impl<E: Engine> R1CSShapeSparkRepr<E> {
 
     // timestamp polynomials for row
     let (ts_row, ts_col) =
-      rayon::join(|| timestamp_calc(N, N, &row), || timestamp_calc(N, N, &col));
+      crate::parallel::join(|| timestamp_calc(N, N, &row), || timestamp_calc(N, N, &col));
 
     // a routine to turn a vector of usize into a vector scalars
     let to_vec_scalar = |v: &[usize]| -> Vec<E::Scalar> {
@@ -378,7 +378,7 @@ impl<E: Engine> MemorySumcheckInstance<E> {
      -> (Vec<E::Scalar>, Vec<E::Scalar>) {
       let hash_func = |addr: &E::Scalar, val: &E::Scalar| -> E::Scalar { *val * gamma + *addr };
       assert_eq!(addr.len(), lookups.len());
-      rayon::join(
+      crate::parallel::join(
         || {
           (0..mem.len())
             .map(|i| hash_func(&E::Scalar::from(i as u64), &mem[i]))
@@ -392,7 +392,7 @@ impl<E: Engine> MemorySumcheckInstance<E> {
       )
     };
 
-    let ((T_row, W_row), (T_col, W_col)) = rayon::join(
+    let ((T_row, W_row), (T_col, W_col)) = crate::parallel::join(
       || hash_func_vec(mem_row, addr_row, L_row),
       || hash_func_vec(mem_col, addr_col, L_col),
     );
@@ -439,9 +439,9 @@ impl<E: Engine> MemorySumcheckInstance<E> {
         Result<Vec<E::Scalar>, NovaError>,
       ),
     ) {
-      rayon::join(
+      crate::parallel::join(
         || {
-          rayon::join(
+          crate::parallel::join(
             || {
               let inv = batch_invert(&T.par_iter().map(|e| *e + *r).collect::<Vec<E::Scalar>>())?;
 
@@ -455,7 +455,7 @@ impl<E: Engine> MemorySumcheckInstance<E> {
           )
         },
         || {
-          rayon::join(
+          crate::parallel::join(
             || Ok(T.par_iter().map(|e| *e + *r).collect::<Vec<E::Scalar>>()),
             || Ok(W.par_iter().map(|e| *e + *r).collect::<Vec<E::Scalar>>()),
           )
@@ -466,7 +466,7 @@ impl<E: Engine> MemorySumcheckInstance<E> {
     let (
       ((t_plus_r_inv_row, w_plus_r_inv_row), (t_plus_r_row, w_plus_r_row)),
       ((t_plus_r_inv_col, w_plus_r_inv_col), (t_plus_r_col, w_plus_r_col)),
-    ) = rayon::join(
+    ) = crate::parallel::join(
       || helper(&T_row, &W_row, ts_row, r),
       || helper(&T_col, &W_col, ts_col, r),
     );
@@ -479,15 +479,15 @@ impl<E: Engine> MemorySumcheckInstance<E> {
     let (
       (comm_t_plus_r_inv_row, comm_w_plus_r_inv_row),
       (comm_t_plus_r_inv_col, comm_w_plus_r_inv_col),
-    ) = rayon::join(
+    ) = crate::parallel::join(
       || {
-        rayon::join(
+        crate::parallel::join(
           || E::CE::commit(ck, &t_plus_r_inv_row),
           || E::CE::commit(ck, &w_plus_r_inv_row),
         )
       },
       || {
-        rayon::join(
+        crate::parallel::join(
           || E::CE::commit(ck, &t_plus_r_inv_col),
           || E::CE::commit(ck, &w_plus_r_inv_col),
         )
@@ -968,16 +968,16 @@ impl<E: Engine, EE: EvaluationEngineTrait<E>> RelaxedR1CSSNARK<E, EE> {
     let mut cubic_polys: Vec<CompressedUniPoly<E::Scalar>> = Vec::new();
     let num_rounds = mem.size().log_2();
     for _ in 0..num_rounds {
-      let ((evals_mem, evals_outer), (evals_inner, evals_witness)) = rayon::join(
-        || rayon::join(|| mem.evaluation_points(), || outer.evaluation_points()),
-        || rayon::join(|| inner.evaluation_points(), || witness.evaluation_points()),
+      let ((evals_mem, evals_outer), (evals_inner, evals_witness)) = crate::parallel::join(
+        || crate::parallel::join(|| mem.evaluation_points(), || outer.evaluation_points()),
+        || crate::parallel::join(|| inner.evaluation_points(), || witness.evaluation_points()),
       );
 
       let evals: Vec<Vec<E::Scalar>> = evals_mem
         .into_iter()
-        .chain(evals_outer.into_iter())
-        .chain(evals_inner.into_iter())
-        .chain(evals_witness.into_iter())
+        .chain(evals_outer)
+        .chain(evals_inner)
+        .chain(evals_witness)
         .collect::<Vec<Vec<E::Scalar>>>();
       assert_eq!(evals.len(), claims.len());
 
@@ -1000,9 +1000,9 @@ impl<E: Engine, EE: EvaluationEngineTrait<E>> RelaxedR1CSSNARK<E, EE> {
       let r_i = transcript.squeeze(b"c")?;
       r.push(r_i);
 
-      let _ = rayon::join(
-        || rayon::join(|| mem.bound(&r_i), || outer.bound(&r_i)),
-        || rayon::join(|| inner.bound(&r_i), || witness.bound(&r_i)),
+      let _ = crate::parallel::join(
+        || crate::parallel::join(|| mem.bound(&r_i), || outer.bound(&r_i)),
+        || crate::parallel::join(|| inner.bound(&r_i), || witness.bound(&r_i)),
       );
 
       e = poly.evaluate(&r_i);
@@ -1121,9 +1121,9 @@ impl<E: Engine, EE: EvaluationEngineTrait<E>> RelaxedR1CSSNARKTrait<E> for Relax
     let (mut Az, mut Bz, mut Cz) = S.multiply_vec(&z)?;
 
     // commit to Az, Bz, Cz
-    let (comm_Az, (comm_Bz, comm_Cz)) = rayon::join(
+    let (comm_Az, (comm_Bz, comm_Cz)) = crate::parallel::join(
       || E::CE::commit(ck, &Az),
-      || rayon::join(|| E::CE::commit(ck, &Bz), || E::CE::commit(ck, &Cz)),
+      || crate::parallel::join(|| E::CE::commit(ck, &Bz), || E::CE::commit(ck, &Cz)),
     );
 
     transcript.absorb(b"c", &[comm_Az, comm_Bz, comm_Cz].as_slice());
@@ -1156,7 +1156,7 @@ impl<E: Engine, EE: EvaluationEngineTrait<E>> RelaxedR1CSSNARKTrait<E> for Relax
     // L_col(i) = z(col(i)) for all i
     let (mem_row, mem_col, L_row, L_col) = pk.S_repr.evaluation_oracles(&S, &tau, &z);
     let (comm_L_row, comm_L_col) =
-      rayon::join(|| E::CE::commit(ck, &L_row), || E::CE::commit(ck, &L_col));
+      crate::parallel::join(|| E::CE::commit(ck, &L_row), || E::CE::commit(ck, &L_col));
 
     // since all the three polynomials are opened at tau,
     // we can combine them into a single polynomial opened at tau
@@ -1180,7 +1180,7 @@ impl<E: Engine, EE: EvaluationEngineTrait<E>> RelaxedR1CSSNARKTrait<E> for Relax
     let gamma = transcript.squeeze(b"g")?;
     let r = transcript.squeeze(b"r")?;
 
-    let ((mut outer_sc_inst, mut inner_sc_inst), mem_res) = rayon::join(
+    let ((mut outer_sc_inst, mut inner_sc_inst), mem_res) = crate::parallel::join(
       || {
         // a sum-check instance to prove the first claim
         let outer_sc_inst = OuterSumcheckInstance::new(
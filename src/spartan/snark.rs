@@ -28,7 +28,7 @@ use ff::Field;
 use itertools::Itertools as _;
 use once_cell::sync::OnceCell;
 
-use rayon::prelude::*;
+use crate::parallel::prelude::*;
 use serde::{Deserialize, Serialize};
 
 /// A type that represents the prover's key
@@ -142,9 +142,7 @@ impl<E: Engine, EE: EvaluationEngineTrait<E>> RelaxedR1CSSNARKTrait<E> for Relax
     );
 
     // outer sum-check
-    let tau = (0..num_rounds_x)
-      .map(|_i| transcript.squeeze(b"t"))
-      .collect::<Result<EqPolynomial<_>, NovaError>>()?;
+    let tau = EqPolynomial::new(transcript.squeeze_vec(b"t", num_rounds_x)?);
 
     let mut poly_tau = MultilinearPolynomial::new(tau.evals());
     let (mut poly_Az, mut poly_Bz, poly_Cz, mut poly_uCz_E) = {
@@ -284,9 +282,7 @@ impl<E: Engine, EE: EvaluationEngineTrait<E>> RelaxedR1CSSNARKTrait<E> for Relax
     );
 
     // outer sum-check
-    let tau = (0..num_rounds_x)
-      .map(|_i| transcript.squeeze(b"t"))
-      .collect::<Result<EqPolynomial<_>, NovaError>>()?;
+    let tau = EqPolynomial::new(transcript.squeeze_vec(b"t", num_rounds_x)?);
 
     let (claim_outer_final, r_x) =
       self
@@ -354,7 +350,7 @@ impl<E: Engine, EE: EvaluationEngineTrait<E>> RelaxedR1CSSNARKTrait<E> for Relax
             .sum()
         };
 
-      let (T_x, T_y) = rayon::join(
+      let (T_x, T_y) = crate::parallel::join(
         || EqPolynomial::evals_from_points(r_x),
         || EqPolynomial::evals_from_points(r_y),
       );
@@ -1,4 +1,5 @@
 use crate::errors::NovaError;
+use crate::parallel::prelude::*;
 use crate::spartan::polys::{
   multilinear::MultilinearPolynomial,
   univariate::{CompressedUniPoly, UniPoly},
@@ -6,7 +7,6 @@ use crate::spartan::polys::{
 use crate::traits::{Engine, TranscriptEngineTrait};
 use ff::Field;
 use itertools::Itertools as _;
-use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
 /// Defines a trait for implementing sum-check in a generic manner
@@ -180,7 +180,7 @@ impl<E: Engine> SumcheckProof<E> {
       claim_per_round = poly.evaluate(&r_i);
 
       // bind all tables to the verifier's challenge
-      rayon::join(
+      crate::parallel::join(
         || poly_A.bind_poly_var_top(&r_i),
         || poly_B.bind_poly_var_top(&r_i),
       );
@@ -282,7 +282,7 @@ impl<E: Engine> SumcheckProof<E> {
         ),
         |num_rounds, poly_A, poly_B| {
           if remaining_rounds <= *num_rounds {
-            let _ = rayon::join(
+            let _ = crate::parallel::join(
               || poly_A.bind_poly_var_top(&r_i),
               || poly_B.bind_poly_var_top(&r_i),
             );
@@ -457,15 +457,15 @@ impl<E: Engine> SumcheckProof<E> {
       claim_per_round = poly.evaluate(&r_i);
 
       // bound all tables to the verifier's challenge
-      rayon::join(
+      crate::parallel::join(
         || {
-          rayon::join(
+          crate::parallel::join(
             || poly_A.bind_poly_var_top(&r_i),
             || poly_B.bind_poly_var_top(&r_i),
           )
         },
         || {
-          rayon::join(
+          crate::parallel::join(
             || poly_C.bind_poly_var_top(&r_i),
             || poly_D.bind_poly_var_top(&r_i),
           )
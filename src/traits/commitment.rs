@@ -1,11 +1,15 @@
 //! This module defines a collection of traits that define the behavior of a commitment engine
 //! We require the commitment engine to provide a commitment to vectors with a single group element
-use crate::traits::{AbsorbInROTrait, Engine, TranscriptReprTrait};
+use crate::{
+  errors::NovaError,
+  traits::{AbsorbInROTrait, Engine, TranscriptReprTrait},
+};
 use core::{
   fmt::Debug,
-  ops::{Add, Mul, MulAssign},
+  ops::{Add, Mul, MulAssign, Neg, Sub},
 };
 use serde::{Deserialize, Serialize};
+use subtle::Choice;
 
 /// A helper trait for types implementing scalar multiplication.
 pub trait ScalarMul<Rhs, Output = Self>: Mul<Rhs, Output = Output> + MulAssign<Rhs> {}
@@ -28,10 +32,26 @@ pub trait CommitmentTrait<E: Engine>:
   + for<'de> Deserialize<'de>
   + AbsorbInROTrait<E>
   + Add<Self, Output = Self>
+  + Sub<Self, Output = Self>
+  + Neg<Output = Self>
   + ScalarMul<E::Scalar>
 {
   /// Returns the coordinate representation of the commitment
   fn to_coordinates(&self) -> (E::Base, E::Base, bool);
+
+  /// Returns the number of bytes of the compressed encoding of a commitment
+  fn compressed_len() -> usize;
+
+  /// Returns the compressed encoding of the commitment, in the point encoding of its curve
+  fn to_compressed_bytes(&self) -> Vec<u8>;
+
+  /// Decodes a commitment from its compressed encoding. Returns
+  /// `NovaError::InvalidInputLength` if `bytes` has other than `compressed_len()` bytes, and
+  /// `NovaError::PointNotOnCurve` if they do not encode a point of the group.
+  fn from_compressed_bytes(bytes: &[u8]) -> Result<Self, NovaError>;
+
+  /// Returns whether the commitment is the identity of the group, in constant time
+  fn is_identity(&self) -> Choice;
 }
 
 /// A trait that helps determine the length of a structure.
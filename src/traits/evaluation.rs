@@ -44,3 +44,29 @@ pub trait EvaluationEngineTrait<E: Engine>: Clone + Send + Sync {
     arg: &Self::EvaluationArgument,
   ) -> Result<(), NovaError>;
 }
+
+/// A trait for multilinear polynomial commitment schemes that leaves the representation of the
+/// evaluation point (e.g., as the evaluations of the eq polynomial) to the engine, so that
+/// generic code can open committed polynomials without evaluating them itself
+pub trait MultilinearEvaluation<E: Engine>: EvaluationEngineTrait<E> {
+  /// Evaluates the multilinear polynomial with evaluations `poly` over the boolean hypercube at
+  /// `point`, and proves the evaluation
+  fn open(
+    ck: &<<E as Engine>::CE as CommitmentEngineTrait<E>>::CommitmentKey,
+    pk: &Self::ProverKey,
+    transcript: &mut E::TE,
+    comm: &<<E as Engine>::CE as CommitmentEngineTrait<E>>::Commitment,
+    poly: &[E::Scalar],
+    point: &[E::Scalar],
+  ) -> Result<(E::Scalar, Self::EvaluationArgument), NovaError>;
+
+  /// Verifies an opening produced by `open`
+  fn verify_opening(
+    vk: &Self::VerifierKey,
+    transcript: &mut E::TE,
+    comm: &<<E as Engine>::CE as CommitmentEngineTrait<E>>::Commitment,
+    point: &[E::Scalar],
+    eval: &E::Scalar,
+    arg: &Self::EvaluationArgument,
+  ) -> Result<(), NovaError>;
+}
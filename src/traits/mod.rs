@@ -114,19 +114,81 @@ pub trait TranscriptReprTrait<G: Group>: Send + Sync {
   fn to_transcript_bytes(&self) -> Vec<u8>;
 }
 
+/// A snapshot of a transcript, taken with `TranscriptEngineTrait::checkpoint`, to which the
+/// transcript can be rolled back with `TranscriptEngineTrait::restore`
+#[derive(Clone, Debug)]
+pub struct TranscriptState<T>(T);
+
 /// This trait defines the behavior of a transcript engine compatible with Spartan
-pub trait TranscriptEngineTrait<E: Engine>: Send + Sync {
+pub trait TranscriptEngineTrait<E: Engine>: Clone + Send + Sync {
   /// initializes the transcript
   fn new(label: &'static [u8]) -> Self;
 
-  /// returns a scalar element of the group as a challenge
+  /// returns a snapshot of the transcript, to which it can be rolled back with `restore`; by
+  /// default, this clones the transcript
+  fn checkpoint(&self) -> TranscriptState<Self> {
+    TranscriptState(self.clone())
+  }
+
+  /// rolls the transcript back to the snapshot `state`, after which it derives the same
+  /// challenges as it would have when the snapshot was taken
+  fn restore(&mut self, state: TranscriptState<Self>) {
+    *self = state.0;
+  }
+
+  /// returns a scalar element of the group as a challenge, or `NovaError::TranscriptError`
+  /// carrying `label` if the challenge cannot be derived
   fn squeeze(&mut self, label: &'static [u8]) -> Result<E::Scalar, NovaError>;
 
+  /// returns `n` challenges squeezed under `label`, with no message absorbed between them; by
+  /// default, this squeezes each challenge with `squeeze`
+  fn squeeze_vec(&mut self, label: &'static [u8], n: usize) -> Result<Vec<E::Scalar>, NovaError> {
+    (0..n).map(|_| self.squeeze(label)).collect()
+  }
+
   /// absorbs any type that implements `TranscriptReprTrait` under a label
   fn absorb<T: TranscriptReprTrait<E::GE>>(&mut self, label: &'static [u8], o: &T);
 
   /// adds a domain separator
   fn dom_sep(&mut self, bytes: &'static [u8]);
+
+  /// absorbs the output of a verifiable random function (VRF) on `input`, such as a round of a
+  /// randomness beacon, after checking its `proof` with `vrf`, so that the challenges squeezed
+  /// afterwards are tied to verifiable randomness; returns `NovaError::InvalidVrfProof` without
+  /// changing the transcript if the proof is rejected
+  fn absorb_vrf<V: VrfVerifier>(
+    &mut self,
+    vrf: &V,
+    input: &[u8],
+    output: &[u8],
+    proof: &[u8],
+  ) -> Result<(), NovaError> {
+    if !vrf.verify(input, output, proof) {
+      return Err(NovaError::InvalidVrfProof);
+    }
+
+    self.dom_sep(b"VRF");
+    self.absorb(b"vrf_input", &VrfBytes(input));
+    self.absorb(b"vrf_output", &VrfBytes(output));
+    Ok(())
+  }
+}
+
+/// A verifier of a verifiable random function (VRF), whose outputs can be mixed into a
+/// transcript with `TranscriptEngineTrait::absorb_vrf`
+pub trait VrfVerifier {
+  /// checks that `output` is the output of the VRF on `input`, as attested by `proof`
+  fn verify(&self, input: &[u8], output: &[u8], proof: &[u8]) -> bool;
+}
+
+// the bytes of a VRF input or output, prefixed with their length so that the boundary between
+// the input and the output is unambiguous
+struct VrfBytes<'a>(&'a [u8]);
+
+impl<G: Group> TranscriptReprTrait<G> for VrfBytes<'_> {
+  fn to_transcript_bytes(&self) -> Vec<u8> {
+    [&(self.0.len() as u64).to_le_bytes(), self.0].concat()
+  }
 }
 
 /// Defines additional methods on `PrimeField` objects